@@ -0,0 +1,557 @@
+//! Instruction decoders and error codes generated from Anchor IDLs.
+//!
+//! `generate_ix_decode!("sage.json")` reads the IDL from the crate's manifest directory, like
+//! `anchor_gen::generate_cpi_crate!`, and generates an `ix_decode` module with:
+//!
+//! - `accounts::<Instruction>`: the instruction's accounts by name, account groups flattened
+//!   (a name appearing in several groups is prefixed with its group, e.g.
+//!   `crafting_xp_accounts_user_points_account`);
+//! - `args::<Instruction>`: the instruction's arguments;
+//! - `Instruction`: one variant per instruction holding both and the remaining accounts;
+//! - `decode(program_id, account_keys, data)`, also re-exported as the crate's `ix_decode`.
+//!
+//! `generate_errors!("sage.json")` generates an `error_code` module with an `ErrorCode` enum of
+//! the IDL's `errors`, one variant per custom error with its code as discriminant.
+//!
+//! Both the legacy IDL format (`isMut`, `{"defined": "Type"}`) and the 0.30 one (`writable`,
+//! explicit discriminators) are read. Defined argument types are taken from the crate's
+//! `typedefs`.
+use proc_macro::TokenStream;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::{fmt::Write, path::PathBuf};
+
+#[proc_macro]
+pub fn generate_ix_decode(input: TokenStream) -> TokenStream {
+    let (idl, file_name) = read_idl(input);
+    generate(&idl, &file_name)
+        .parse()
+        .expect("generated ix_decode module")
+}
+
+#[proc_macro]
+pub fn generate_errors(input: TokenStream) -> TokenStream {
+    let (idl, file_name) = read_idl(input);
+    generate_error_code(&idl, &file_name)
+        .parse()
+        .expect("generated error_code module")
+}
+
+/// The IDL named by the macro input, relative to the crate's manifest directory.
+fn read_idl(input: TokenStream) -> (Value, String) {
+    let file_name = input.to_string();
+    let file_name = file_name.trim().trim_matches('"').to_string();
+    let path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR"))
+        .join(&file_name);
+
+    let idl = std::fs::read_to_string(&path)
+        .unwrap_or_else(|err| panic!("read {}: {}", path.display(), err));
+    let idl: Value = serde_json::from_str(&idl)
+        .unwrap_or_else(|err| panic!("parse {}: {}", path.display(), err));
+
+    (idl, file_name)
+}
+
+struct Account {
+    /// Field name, snake case.
+    name: String,
+    docs: Vec<String>,
+}
+
+struct Arg {
+    /// Field name, snake case.
+    name: String,
+    ty: String,
+}
+
+struct Ix {
+    /// Variant and struct name, pascal case.
+    name: String,
+    docs: Vec<String>,
+    discriminator: Vec<u8>,
+    accounts: Vec<Account>,
+    args: Vec<Arg>,
+}
+
+fn parse_instructions(idl: &Value) -> Vec<Ix> {
+    idl["instructions"]
+        .as_array()
+        .expect("IDL instructions")
+        .iter()
+        .map(|ix| {
+            let name = ix["name"].as_str().expect("instruction name");
+            let discriminator = match ix.get("discriminator") {
+                Some(discriminator) => discriminator
+                    .as_array()
+                    .expect("instruction discriminator")
+                    .iter()
+                    .map(|byte| byte.as_u64().expect("discriminator byte") as u8)
+                    .collect(),
+                None => {
+                    let preimage = format!("global:{}", snake_case(name));
+                    Sha256::digest(preimage.as_bytes())[..8].to_vec()
+                }
+            };
+
+            let mut leaves = vec![];
+            flatten_accounts(
+                ix["accounts"].as_array().expect("accounts"),
+                "",
+                &mut leaves,
+            );
+            let accounts = leaves
+                .iter()
+                .map(|(group, leaf)| {
+                    let leaf_name = snake_case(leaf["name"].as_str().expect("account name"));
+                    let repeated = leaves
+                        .iter()
+                        .filter(|(_, other)| {
+                            snake_case(other["name"].as_str().unwrap_or_default()) == leaf_name
+                        })
+                        .count()
+                        > 1;
+                    Account {
+                        name: if repeated && !group.is_empty() {
+                            format!("{}_{}", snake_case(group), leaf_name)
+                        } else {
+                            leaf_name
+                        },
+                        docs: docs(leaf),
+                    }
+                })
+                .collect();
+
+            let args = ix["args"]
+                .as_array()
+                .expect("args")
+                .iter()
+                .map(|arg| Arg {
+                    name: snake_case(arg["name"].as_str().expect("arg name")),
+                    ty: rust_type(&arg["type"]),
+                })
+                .collect();
+
+            Ix {
+                name: pascal_case(name),
+                docs: docs(ix),
+                discriminator,
+                accounts,
+                args,
+            }
+        })
+        .collect()
+}
+
+/// Accounts in order with the name of their innermost group.
+fn flatten_accounts<'a>(
+    accounts: &'a [Value],
+    group: &'a str,
+    leaves: &mut Vec<(&'a str, &'a Value)>,
+) {
+    for account in accounts {
+        match account.get("accounts").and_then(Value::as_array) {
+            Some(nested) => flatten_accounts(
+                nested,
+                account["name"].as_str().expect("account group name"),
+                leaves,
+            ),
+            None => leaves.push((group, account)),
+        }
+    }
+}
+
+fn docs(value: &Value) -> Vec<String> {
+    value
+        .get("docs")
+        .and_then(Value::as_array)
+        .map(|docs| {
+            docs.iter()
+                .filter_map(|doc| doc.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn rust_type(ty: &Value) -> String {
+    match ty {
+        Value::String(name) => match name.as_str() {
+            "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64"
+            | "i128" | "f32" | "f64" => name.clone(),
+            "string" => "String".into(),
+            "bytes" => "Vec<u8>".into(),
+            "publicKey" | "pubkey" => "anchor_lang::prelude::Pubkey".into(),
+            other => panic!("unsupported IDL type {}", other),
+        },
+        Value::Object(object) => {
+            if let Some(inner) = object.get("vec") {
+                format!("Vec<{}>", rust_type(inner))
+            } else if let Some(inner) = object.get("option") {
+                format!("Option<{}>", rust_type(inner))
+            } else if let Some(array) = object.get("array") {
+                format!("[{}; {}]", rust_type(&array[0]), array[1])
+            } else if let Some(defined) = object.get("defined") {
+                let name = defined
+                    .as_str()
+                    .or_else(|| defined["name"].as_str())
+                    .expect("defined type name");
+                format!("crate::typedefs::{}", name)
+            } else {
+                panic!("unsupported IDL type {}", ty)
+            }
+        }
+        _ => panic!("unsupported IDL type {}", ty),
+    }
+}
+
+/// `startSubwarp` and `start_subwarp` to `start_subwarp`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit()) {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+        previous = Some(c);
+    }
+    snake
+}
+
+/// `startSubwarp` and `start_subwarp` to `StartSubwarp`.
+fn pascal_case(name: &str) -> String {
+    snake_case(name)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn doc_attrs(out: &mut String, docs: &[String]) {
+    for doc in docs {
+        writeln!(out, "#[doc = {:?}]", format!(" {}", doc.trim())).unwrap();
+    }
+}
+
+fn generate(idl: &Value, file_name: &str) -> String {
+    let instructions = parse_instructions(idl);
+    let mut out = String::new();
+
+    writeln!(out, "pub mod ix_decode {{").unwrap();
+    writeln!(
+        out,
+        "#![doc = {:?}]",
+        format!(
+            " Instructions decoded with the accounts and arguments of `{}`.",
+            file_name
+        )
+    )
+    .unwrap();
+
+    // named accounts
+    writeln!(out, "pub mod accounts {{").unwrap();
+    for ix in &instructions {
+        writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub struct {} {{", ix.name).unwrap();
+        for account in &ix.accounts {
+            doc_attrs(&mut out, &account.docs);
+            writeln!(out, "pub {}: anchor_lang::prelude::Pubkey,", account.name).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    // arguments
+    writeln!(out, "pub mod args {{").unwrap();
+    for ix in &instructions {
+        writeln!(out, "#[derive(Debug)]").unwrap();
+        writeln!(out, "pub struct {} {{", ix.name).unwrap();
+        for arg in &ix.args {
+            writeln!(out, "pub {}: {},", arg.name, arg.ty).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+
+        let reader = if ix.args.is_empty() {
+            "_reader"
+        } else {
+            "reader"
+        };
+        writeln!(
+            out,
+            "impl anchor_lang::prelude::borsh::BorshDeserialize for {} {{
+                fn deserialize_reader<R: std::io::Read>({}: &mut R) -> std::io::Result<Self> {{
+                    Ok({} {{",
+            ix.name, reader, ix.name
+        )
+        .unwrap();
+        for arg in &ix.args {
+            writeln!(
+                out,
+                "{}: anchor_lang::prelude::borsh::BorshDeserialize::deserialize_reader(reader)?,",
+                arg.name
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}) }} }}").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    // instruction enum
+    writeln!(out, "#[derive(Debug)]").unwrap();
+    writeln!(out, "#[allow(clippy::large_enum_variant)]").unwrap();
+    writeln!(out, "pub enum Instruction {{").unwrap();
+    for ix in &instructions {
+        doc_attrs(&mut out, &ix.docs);
+        writeln!(
+            out,
+            "{0} {{
+                accounts: accounts::{0},
+                args: args::{0},
+                remaining_accounts: Vec<anchor_lang::prelude::Pubkey>,
+            }},",
+            ix.name
+        )
+        .unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    writeln!(
+        out,
+        "impl Instruction {{
+            pub fn name(&self) -> &'static str {{
+                match self {{"
+    )
+    .unwrap();
+    for ix in &instructions {
+        writeln!(out, "Instruction::{0} {{ .. }} => {0:?},", ix.name).unwrap();
+    }
+    writeln!(out, "}} }} }}").unwrap();
+
+    // errors
+    out.push_str(
+        "#[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum DecodeError {
+            /// The instruction is not one of this program's.
+            ProgramMismatch,
+            UnknownDiscriminator,
+            MissingAccounts { expected: usize, actual: usize },
+            InvalidArgs(String),
+        }
+
+        impl std::fmt::Display for DecodeError {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    DecodeError::ProgramMismatch => write!(f, \"instruction of another program\"),
+                    DecodeError::UnknownDiscriminator => write!(f, \"unknown instruction discriminator\"),
+                    DecodeError::MissingAccounts { expected, actual } => {
+                        write!(f, \"{} accounts, expected at least {}\", actual, expected)
+                    }
+                    DecodeError::InvalidArgs(err) => write!(f, \"invalid arguments: {}\", err),
+                }
+            }
+        }
+
+        impl std::error::Error for DecodeError {}
+
+        fn check_accounts(
+            account_keys: &[anchor_lang::prelude::Pubkey],
+            expected: usize,
+        ) -> Result<(), DecodeError> {
+            if account_keys.len() < expected {
+                return Err(DecodeError::MissingAccounts { expected, actual: account_keys.len() });
+            }
+            Ok(())
+        }
+
+        fn deserialize<T: anchor_lang::prelude::borsh::BorshDeserialize>(
+            mut data: &[u8],
+        ) -> Result<T, DecodeError> {
+            T::deserialize_reader(&mut data).map_err(|err| DecodeError::InvalidArgs(err.to_string()))
+        }
+        ",
+    );
+
+    // decoder
+    out.push_str(
+        "/// Decodes an instruction of the program from its program id, account keys and data.
+        pub fn decode(
+            program_id: &anchor_lang::prelude::Pubkey,
+            account_keys: &[anchor_lang::prelude::Pubkey],
+            data: &[u8],
+        ) -> Result<Instruction, DecodeError> {
+            if *program_id != crate::ID {
+                return Err(DecodeError::ProgramMismatch);
+            }
+            if data.len() < 8 {
+                return Err(DecodeError::UnknownDiscriminator);
+            }
+            let (discriminator, args_data) = data.split_at(8);
+            match discriminator {",
+    );
+    for ix in &instructions {
+        let discriminator: Vec<String> = ix.discriminator.iter().map(u8::to_string).collect();
+        writeln!(
+            out,
+            "[{}] => {{
+                check_accounts(account_keys, {})?;
+                Ok(Instruction::{} {{
+                    accounts: accounts::{} {{",
+            discriminator.join(", "),
+            ix.accounts.len(),
+            ix.name,
+            ix.name
+        )
+        .unwrap();
+        for (index, account) in ix.accounts.iter().enumerate() {
+            writeln!(out, "{}: account_keys[{}],", account.name, index).unwrap();
+        }
+        writeln!(
+            out,
+            "}},
+                    args: deserialize(args_data)?,
+                    remaining_accounts: account_keys[{}..].to_vec(),
+                }})
+            }}",
+            ix.accounts.len()
+        )
+        .unwrap();
+    }
+    out.push_str(
+        "_ => Err(DecodeError::UnknownDiscriminator),
+            }
+        }
+    }
+
+    pub use ix_decode::decode as ix_decode;
+    ",
+    );
+
+    out
+}
+
+struct IdlError {
+    code: u32,
+    /// Variant name, as in the IDL (e.g. `URIPrefixTooLong`).
+    name: String,
+    msg: Option<String>,
+}
+
+fn parse_errors(idl: &Value) -> Vec<IdlError> {
+    idl.get("errors")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+        .iter()
+        .map(|error| IdlError {
+            code: error["code"]
+                .as_u64()
+                .and_then(|code| u32::try_from(code).ok())
+                .expect("error code"),
+            name: error["name"].as_str().expect("error name").to_string(),
+            msg: error["msg"].as_str().map(String::from),
+        })
+        .collect()
+}
+
+fn generate_error_code(idl: &Value, file_name: &str) -> String {
+    let errors = parse_errors(idl);
+    let mut out = String::new();
+
+    writeln!(out, "pub mod error_code {{").unwrap();
+    writeln!(
+        out,
+        "#![doc = {:?}]",
+        format!(" Custom errors of `{}`.", file_name)
+    )
+    .unwrap();
+
+    writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]").unwrap();
+    writeln!(out, "#[allow(clippy::upper_case_acronyms)]").unwrap();
+    writeln!(out, "pub enum ErrorCode {{").unwrap();
+    for error in &errors {
+        doc_attrs(
+            &mut out,
+            &[format!(
+                "{} - {}",
+                error.code,
+                error.msg.as_deref().unwrap_or(&error.name)
+            )],
+        );
+        writeln!(out, "{},", error.name).unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+
+    let variants: Vec<String> = errors
+        .iter()
+        .map(|error| format!("ErrorCode::{}", error.name))
+        .collect();
+    writeln!(
+        out,
+        "impl ErrorCode {{
+            pub const ALL: &'static [ErrorCode] = &[{}];",
+        variants.join(", ")
+    )
+    .unwrap();
+
+    writeln!(
+        out,
+        "pub fn from_code(code: u32) -> Option<ErrorCode> {{
+            match code {{"
+    )
+    .unwrap();
+    for error in &errors {
+        writeln!(out, "{} => Some(ErrorCode::{}),", error.code, error.name).unwrap();
+    }
+    writeln!(out, "_ => None, }} }}").unwrap();
+
+    writeln!(out, "pub fn code(&self) -> u32 {{ match *self {{").unwrap();
+    for error in &errors {
+        writeln!(out, "ErrorCode::{} => {},", error.name, error.code).unwrap();
+    }
+    writeln!(out, "}} }}").unwrap();
+
+    writeln!(out, "pub fn name(&self) -> &'static str {{ match *self {{").unwrap();
+    for error in &errors {
+        writeln!(out, "ErrorCode::{0} => {0:?},", error.name).unwrap();
+    }
+    writeln!(out, "}} }}").unwrap();
+
+    writeln!(
+        out,
+        "/// The IDL's `msg`, the name when it has none.
+        pub fn message(&self) -> &'static str {{ match *self {{"
+    )
+    .unwrap();
+    for error in &errors {
+        writeln!(
+            out,
+            "ErrorCode::{} => {:?},",
+            error.name,
+            error.msg.as_deref().unwrap_or(&error.name)
+        )
+        .unwrap();
+    }
+    writeln!(out, "}} }} }}").unwrap();
+
+    out.push_str(
+        "impl std::fmt::Display for ErrorCode {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, \"{}\", self.message())
+            }
+        }
+
+        impl std::error::Error for ErrorCode {}
+    }
+    ",
+    );
+
+    out
+}
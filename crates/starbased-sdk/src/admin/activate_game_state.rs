@@ -1,11 +1,9 @@
 use anchor_lang::InstructionData;
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
-    transaction::Transaction,
 };
 
 use staratlas_sage::{
@@ -13,6 +11,11 @@ use staratlas_sage::{
     ID as SAGE_PROGRAM_ID,
 };
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
 pub struct ActivateGameState<'a> {
     authority_kp: &'a Keypair,
     profile_pk: &'a Pubkey,
@@ -20,6 +23,7 @@ pub struct ActivateGameState<'a> {
     game_state_pk: &'a Pubkey,
     key_index: u16,
     payer_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> ActivateGameState<'a> {
@@ -37,6 +41,7 @@ impl<'a> ActivateGameState<'a> {
             game_state_pk,
             key_index: 0,
             payer_kp,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
@@ -45,9 +50,25 @@ impl<'a> ActivateGameState<'a> {
         self
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for ActivateGameState<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.payer_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let authority_pk = self.authority_kp.pubkey();
-        let payer_pk = self.payer_kp.pubkey();
 
         let ix = Instruction {
             program_id: SAGE_PROGRAM_ID,
@@ -65,15 +86,10 @@ impl<'a> ActivateGameState<'a> {
             .data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&payer_pk),
-            &[self.authority_kp, self.payer_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
-
-        Ok(*self.game_state_pk)
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.payer_kp.into()],
+            vec![*self.game_state_pk],
+        )
     }
 }
@@ -0,0 +1,130 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{instruction::AddConnection as ixAddConnection, ID as SAGE_PROGRAM_ID};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct AddConnection<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    sector1_pk: &'a Pubkey,
+    sector2_pk: &'a Pubkey,
+    sub_coordinates1: [i64; 2],
+    flags1: u8,
+    sub_coordinates2: [i64; 2],
+    flags2: u8,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> AddConnection<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        sector1_pk: &'a Pubkey,
+        sector2_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        AddConnection {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            sector1_pk,
+            sector2_pk,
+            sub_coordinates1: [0, 0],
+            flags1: 0,
+            sub_coordinates2: [0, 0],
+            flags2: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_sub_coordinates1(mut self, sub_coordinates: [i64; 2]) -> Self {
+        self.sub_coordinates1 = sub_coordinates;
+        self
+    }
+
+    pub fn set_flags1(mut self, flags: u8) -> Self {
+        self.flags1 = flags;
+        self
+    }
+
+    pub fn set_sub_coordinates2(mut self, sub_coordinates: [i64; 2]) -> Self {
+        self.sub_coordinates2 = sub_coordinates;
+        self
+    }
+
+    pub fn set_flags2(mut self, flags: u8) -> Self {
+        self.flags2 = flags;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for AddConnection<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // AddConnectionGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // AddConnectionGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // AddConnectionGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(*self.sector1_pk, false),       // pub sector1: AccountInfo<'info>,
+                AccountMeta::new(*self.sector2_pk, false),       // pub sector2: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixAddConnection {
+                _sub_coordinates1: self.sub_coordinates1,
+                _flags1: self.flags1,
+                _sub_coordinates2: self.sub_coordinates2,
+                _flags2: self.flags2,
+                _key_index: self.key_index,
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
@@ -1,21 +1,25 @@
 use anchor_lang::InstructionData;
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
-    transaction::Transaction,
 };
 
 use staratlas_sage::{instruction::InitGame, ID as SAGE_PROGRAM_ID};
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
 pub struct CreateGame<'a> {
     authority_kp: &'a Keypair,
     profile_pk: &'a Pubkey,
     funder_kp: &'a Keypair,
     game_kp: Option<Keypair>,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> CreateGame<'a> {
@@ -25,6 +29,7 @@ impl<'a> CreateGame<'a> {
             profile_pk,
             funder_kp,
             game_kp: None,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
@@ -33,7 +38,24 @@ impl<'a> CreateGame<'a> {
         self
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for CreateGame<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let authority_pk = self.authority_kp.pubkey();
         let funder_pk = self.funder_kp.pubkey();
 
@@ -52,15 +74,14 @@ impl<'a> CreateGame<'a> {
             data: InitGame {}.data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&funder_pk),
-            &[self.authority_kp, self.funder_kp, &game_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
-
-        Ok(game_pk)
+        (
+            vec![ix],
+            vec![
+                self.authority_kp.into(),
+                self.funder_kp.into(),
+                game_kp.into(),
+            ],
+            vec![game_pk],
+        )
     }
 }
@@ -1,18 +1,21 @@
 use anchor_lang::InstructionData;
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
-    transaction::Transaction,
 };
 
 use staratlas_sage::{
     instruction::InitGameState, typedefs::InitGameStateInput, ID as SAGE_PROGRAM_ID,
 };
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
 pub struct CreateGameState<'a> {
     authority_kp: &'a Keypair,
     profile_pk: &'a Pubkey,
@@ -20,6 +23,7 @@ pub struct CreateGameState<'a> {
     game_update_id: u64,
     key_index: u16,
     funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> CreateGameState<'a> {
@@ -36,6 +40,7 @@ impl<'a> CreateGameState<'a> {
             game_update_id: 0,
             key_index: 0,
             funder_kp,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
@@ -49,7 +54,24 @@ impl<'a> CreateGameState<'a> {
         self
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for CreateGameState<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let authority_pk = self.authority_kp.pubkey();
         let funder_pk = self.funder_kp.pubkey();
 
@@ -80,15 +102,10 @@ impl<'a> CreateGameState<'a> {
             .data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&funder_pk),
-            &[self.authority_kp, self.funder_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
-
-        Ok(game_state_pda)
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![game_state_pda],
+        )
     }
 }
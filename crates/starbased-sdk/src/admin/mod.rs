@@ -1,11 +1,29 @@
 mod activate_game_state;
+mod add_connection;
 mod create_game;
 mod create_game_state;
+mod register_mine_item;
+mod register_planet;
+mod register_resource;
 mod register_sector;
+mod register_ship;
+mod register_star;
 mod register_starbase;
+mod register_survey_data_unit_tracker;
+mod update_game;
+mod update_game_state;
 
 pub use activate_game_state::*;
+pub use add_connection::*;
 pub use create_game::*;
 pub use create_game_state::*;
+pub use register_mine_item::*;
+pub use register_planet::*;
+pub use register_resource::*;
 pub use register_sector::*;
+pub use register_ship::*;
+pub use register_star::*;
 pub use register_starbase::*;
+pub use register_survey_data_unit_tracker::*;
+pub use update_game::*;
+pub use update_game_state::*;
@@ -0,0 +1,125 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterMineItem as ixRegisterMineItem, typedefs::RegisterMineItemInput,
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct RegisterMineItem<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    mint_pk: &'a Pubkey,
+    name: [u8; 64],
+    resource_hardness: u16,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterMineItem<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterMineItem {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            mint_pk,
+            name: [0u8; 64],
+            resource_hardness: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        let name_bytes = name.as_bytes();
+        let mut name = [0u8; 64];
+        name[..name_bytes.len()].copy_from_slice(name_bytes);
+
+        self.name = name;
+        self
+    }
+
+    pub fn set_resource_hardness(mut self, resource_hardness: u16) -> Self {
+        self.resource_hardness = resource_hardness;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterMineItem<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (mine_item_pda, _bump) = Pubkey::find_program_address(
+            &[b"MineItem", self.game_pk.as_ref(), self.mint_pk.as_ref()],
+            &SAGE_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterMineItemGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterMineItemGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterMineItemGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(mine_item_pda, false), // pub mine_item: AccountInfo<'info>,
+                AccountMeta::new(*self.mint_pk, false), // pub mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterMineItem {
+                _input: RegisterMineItemInput {
+                    name: self.name,
+                    resource_hardness: self.resource_hardness,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![mine_item_pda],
+        )
+    }
+}
@@ -0,0 +1,161 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterPlanet as ixRegisterPlanet,
+    typedefs::{PlanetType, RegisterPlanetInput},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct RegisterPlanet<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    planet_kp: &'a Keypair,
+    sector_pk: &'a Pubkey,
+    name: [u8; 64],
+    size: u64,
+    max_hp: u64,
+    sub_coordinates: [i64; 2],
+    planet_type: PlanetType,
+    position: u8,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterPlanet<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        planet_kp: &'a Keypair,
+        sector_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterPlanet {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            planet_kp,
+            sector_pk,
+            name: [0u8; 64],
+            size: 0,
+            max_hp: 0,
+            sub_coordinates: [0, 0],
+            planet_type: PlanetType::Terrestrial,
+            position: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        let name_bytes = name.as_bytes();
+        let mut name = [0u8; 64];
+        name[..name_bytes.len()].copy_from_slice(name_bytes);
+
+        self.name = name;
+        self
+    }
+
+    pub fn set_size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn set_max_hp(mut self, max_hp: u64) -> Self {
+        self.max_hp = max_hp;
+        self
+    }
+
+    pub fn set_sub_coordinates(mut self, sub_coordinates: [i64; 2]) -> Self {
+        self.sub_coordinates = sub_coordinates;
+        self
+    }
+
+    pub fn set_planet_type(mut self, planet_type: PlanetType) -> Self {
+        self.planet_type = planet_type;
+        self
+    }
+
+    pub fn set_position(mut self, position: u8) -> Self {
+        self.position = position;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterPlanet<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+        let planet_pk = self.planet_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterPlanetGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterPlanetGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterPlanetGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(planet_pk, true),               // pub planet: Signer<'info>,
+                AccountMeta::new(*self.sector_pk, false),        // pub sector: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterPlanet {
+                _input: RegisterPlanetInput {
+                    name: self.name,
+                    size: self.size,
+                    max_hp: self.max_hp,
+                    sub_coordinates: self.sub_coordinates,
+                    planet_type: self.planet_type as u8,
+                    position: self.position,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![
+                self.authority_kp.into(),
+                self.funder_kp.into(),
+                self.planet_kp.into(),
+            ],
+            vec![planet_pk],
+        )
+    }
+}
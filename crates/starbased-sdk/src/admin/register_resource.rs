@@ -0,0 +1,130 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterResource as ixRegisterResource,
+    typedefs::{LocationType, RegisterResourceInput},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct RegisterResource<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    location_pk: &'a Pubkey,
+    mine_item_pk: &'a Pubkey,
+    location_type: LocationType,
+    system_richness: u16,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterResource<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        location_pk: &'a Pubkey,
+        mine_item_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterResource {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            location_pk,
+            mine_item_pk,
+            location_type: LocationType::Planet,
+            system_richness: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_location_type(mut self, location_type: LocationType) -> Self {
+        self.location_type = location_type;
+        self
+    }
+
+    pub fn set_system_richness(mut self, system_richness: u16) -> Self {
+        self.system_richness = system_richness;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterResource<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (resource_pda, _bump) = Pubkey::find_program_address(
+            &[
+                b"Resource",
+                self.mine_item_pk.as_ref(),
+                self.location_pk.as_ref(),
+            ],
+            &SAGE_PROGRAM_ID,
+        );
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterResourceGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterResourceGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterResourceGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(resource_pda, false), // pub resource: AccountInfo<'info>,
+                AccountMeta::new(*self.location_pk, false), // pub location: AccountInfo<'info>,
+                AccountMeta::new(*self.mine_item_pk, false), // pub mine_item: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterResource {
+                _input: RegisterResourceInput {
+                    location_type: self.location_type as u8,
+                    system_richness: self.system_richness,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![resource_pda],
+        )
+    }
+}
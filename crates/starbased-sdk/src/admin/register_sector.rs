@@ -1,16 +1,31 @@
 use anchor_lang::InstructionData;
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
-    transaction::Transaction,
 };
 
 use staratlas_sage::{instruction::RegisterSector as ixRegisterSector, ID as SAGE_PROGRAM_ID};
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_sector_pda(game_pk: &Pubkey, coordinates: [i64; 2]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"Sector",
+            game_pk.as_ref(),
+            &coordinates[0].to_le_bytes(),
+            &coordinates[1].to_le_bytes(),
+        ],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
 pub struct RegisterSector<'a> {
     authority_kp: &'a Keypair,
     profile_pk: &'a Pubkey,
@@ -20,6 +35,7 @@ pub struct RegisterSector<'a> {
     name: [u8; 64],
     key_index: u16,
     funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> RegisterSector<'a> {
@@ -39,6 +55,7 @@ impl<'a> RegisterSector<'a> {
             name: [0u8; 64],
             key_index: 0,
             funder_kp,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
@@ -61,19 +78,28 @@ impl<'a> RegisterSector<'a> {
         self
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterSector<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let authority_pk = self.authority_kp.pubkey();
         let funder_pk = self.funder_kp.pubkey();
 
-        let (sector_pda, _bump) = Pubkey::find_program_address(
-            &[
-                b"Sector",
-                self.game_pk.as_ref(),
-                &(self.coordinates[0]).to_le_bytes(),
-                &(self.coordinates[1]).to_le_bytes(),
-            ],
-            &SAGE_PROGRAM_ID,
-        );
+        let (sector_pda, _bump) = find_sector_pda(self.game_pk, self.coordinates);
 
         let ix = Instruction {
             program_id: SAGE_PROGRAM_ID,
@@ -94,15 +120,10 @@ impl<'a> RegisterSector<'a> {
             .data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&funder_pk),
-            &[self.authority_kp, self.funder_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
-
-        Ok(sector_pda)
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![sector_pda],
+        )
     }
 }
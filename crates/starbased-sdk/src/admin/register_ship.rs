@@ -0,0 +1,195 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterShip as ixRegisterShip,
+    typedefs::{
+        CargoStats, MiscStats, MovementStats, RegisterShipInput, ShipStatsUnpacked, SizeClass,
+    },
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct RegisterShip<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    ship_kp: &'a Keypair,
+    mint_pk: &'a Pubkey,
+    name: [u8; 64],
+    size_class: SizeClass,
+    movement_stats: MovementStats,
+    cargo_stats: CargoStats,
+    misc_stats: MiscStats,
+    is_active: bool,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterShip<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        ship_kp: &'a Keypair,
+        mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterShip {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            ship_kp,
+            mint_pk,
+            name: [0u8; 64],
+            size_class: SizeClass::Small,
+            movement_stats: MovementStats {
+                subwarp_speed: 0,
+                warp_speed: 0,
+                max_warp_distance: 0,
+                warp_cool_down: 0,
+                subwarp_fuel_consumption_rate: 0,
+                warp_fuel_consumption_rate: 0,
+                planet_exit_fuel_amount: 0,
+            },
+            cargo_stats: CargoStats {
+                cargo_capacity: 0,
+                fuel_capacity: 0,
+                ammo_capacity: 0,
+                ammo_consumption_rate: 0,
+                food_consumption_rate: 0,
+                mining_rate: 0,
+                upgrade_rate: 0,
+                cargo_transfer_rate: 0,
+                tractor_beam_gather_rate: 0,
+            },
+            misc_stats: MiscStats {
+                required_crew: 0,
+                passenger_capacity: 0,
+                crew_count: 0,
+                rented_crew: 0,
+                respawn_time: 0,
+                scan_cool_down: 0,
+                sdu_per_scan: 0,
+                scan_cost: 0,
+                placeholder: 0,
+                placeholder2: 0,
+                placeholder3: 0,
+            },
+            is_active: true,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        let name_bytes = name.as_bytes();
+        let mut name = [0u8; 64];
+        name[..name_bytes.len()].copy_from_slice(name_bytes);
+
+        self.name = name;
+        self
+    }
+
+    pub fn set_size_class(mut self, size_class: SizeClass) -> Self {
+        self.size_class = size_class;
+        self
+    }
+
+    pub fn set_movement_stats(mut self, movement_stats: MovementStats) -> Self {
+        self.movement_stats = movement_stats;
+        self
+    }
+
+    pub fn set_cargo_stats(mut self, cargo_stats: CargoStats) -> Self {
+        self.cargo_stats = cargo_stats;
+        self
+    }
+
+    pub fn set_misc_stats(mut self, misc_stats: MiscStats) -> Self {
+        self.misc_stats = misc_stats;
+        self
+    }
+
+    pub fn set_is_active(mut self, is_active: bool) -> Self {
+        self.is_active = is_active;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterShip<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+        let ship_pk = self.ship_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterShipGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterShipGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterShipGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(ship_pk, true),                 // pub ship: Signer<'info>,
+                AccountMeta::new_readonly(*self.mint_pk, false), // pub mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterShip {
+                _input: RegisterShipInput {
+                    name: self.name,
+                    size_class: self.size_class,
+                    stats: ShipStatsUnpacked {
+                        movement_stats: self.movement_stats,
+                        cargo_stats: self.cargo_stats,
+                        misc_stats: self.misc_stats,
+                    },
+                    key_index: self.key_index,
+                    is_active: self.is_active,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![
+                self.authority_kp.into(),
+                self.funder_kp.into(),
+                self.ship_kp.into(),
+            ],
+            vec![ship_pk],
+        )
+    }
+}
@@ -0,0 +1,145 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterStar as ixRegisterStar,
+    typedefs::{RegisterStarInput, StarType},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct RegisterStar<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    star_kp: &'a Keypair,
+    sector_pk: &'a Pubkey,
+    name: [u8; 64],
+    size: u64,
+    sub_coordinates: [i64; 2],
+    star_type: StarType,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterStar<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        star_kp: &'a Keypair,
+        sector_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterStar {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            star_kp,
+            sector_pk,
+            name: [0u8; 64],
+            size: 0,
+            sub_coordinates: [0, 0],
+            star_type: StarType::Solar,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_name(mut self, name: String) -> Self {
+        let name_bytes = name.as_bytes();
+        let mut name = [0u8; 64];
+        name[..name_bytes.len()].copy_from_slice(name_bytes);
+
+        self.name = name;
+        self
+    }
+
+    pub fn set_size(mut self, size: u64) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn set_sub_coordinates(mut self, sub_coordinates: [i64; 2]) -> Self {
+        self.sub_coordinates = sub_coordinates;
+        self
+    }
+
+    pub fn set_star_type(mut self, star_type: StarType) -> Self {
+        self.star_type = star_type;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterStar<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+        let star_pk = self.star_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterStarGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterStarGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterStarGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(star_pk, true),                 // pub star: Signer<'info>,
+                AccountMeta::new(*self.sector_pk, false),        // pub sector: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterStar {
+                _input: RegisterStarInput {
+                    name: self.name,
+                    size: self.size,
+                    sub_coordinates: self.sub_coordinates,
+                    star_type: self.star_type as u8,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![
+                self.authority_kp.into(),
+                self.funder_kp.into(),
+                self.star_kp.into(),
+            ],
+            vec![star_pk],
+        )
+    }
+}
@@ -1,19 +1,35 @@
 use anchor_lang::InstructionData;
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
-    transaction::Transaction,
 };
 
+use staratlas_profile_faction::typedefs::Faction;
 use staratlas_sage::{
     instruction::RegisterStarbase as ixRegisterStarbase, typedefs::RegisterStarbaseInputUnpacked,
     ID as SAGE_PROGRAM_ID,
 };
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_starbase_pda(game_pk: &Pubkey, coordinates: [i64; 2]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"Starbase",
+            game_pk.as_ref(),
+            &coordinates[0].to_le_bytes(),
+            &coordinates[1].to_le_bytes(),
+        ],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
 pub struct RegisterStarbase<'a> {
     authority_kp: &'a Keypair,
     profile_pk: &'a Pubkey,
@@ -24,9 +40,10 @@ pub struct RegisterStarbase<'a> {
     name: [u8; 64],
     sub_coordinates: [i64; 2],
     starbase_level_index: u8,
-    faction: u8,
+    faction: Faction,
     key_index: u16,
     funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> RegisterStarbase<'a> {
@@ -47,10 +64,11 @@ impl<'a> RegisterStarbase<'a> {
             coordinates: [0, 0],
             name: [0u8; 64],
             sub_coordinates: [0, 0],
-            faction: 0,
+            faction: Faction::Unaligned,
             starbase_level_index: 0,
             key_index: 0,
             funder_kp,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
@@ -73,7 +91,7 @@ impl<'a> RegisterStarbase<'a> {
         self
     }
 
-    pub fn set_faction(mut self, faction: u8) -> Self {
+    pub fn set_faction(mut self, faction: Faction) -> Self {
         self.faction = faction;
         self
     }
@@ -88,19 +106,28 @@ impl<'a> RegisterStarbase<'a> {
         self
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterStarbase<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let authority_pk = self.authority_kp.pubkey();
         let funder_pk = self.funder_kp.pubkey();
 
-        let (starbase_pda, _bump) = Pubkey::find_program_address(
-            &[
-                b"Starbase",
-                self.game_pk.as_ref(),
-                &self.coordinates[0].to_le_bytes(),
-                &self.coordinates[1].to_le_bytes(),
-            ],
-            &SAGE_PROGRAM_ID,
-        );
+        let (starbase_pda, _bump) = find_starbase_pda(self.game_pk, self.coordinates);
 
         let ix = Instruction {
             program_id: SAGE_PROGRAM_ID,
@@ -119,22 +146,17 @@ impl<'a> RegisterStarbase<'a> {
                     name: self.name,
                     sub_coordinates: self.sub_coordinates,
                     starbase_level_index: self.starbase_level_index,
-                    faction: self.faction as u8,
+                    faction: self.faction.into(),
                     key_index: self.key_index,
                 },
             }
             .data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&funder_pk),
-            &[self.authority_kp, self.funder_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
-
-        Ok(starbase_pda)
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.funder_kp.into()],
+            vec![starbase_pda],
+        )
     }
 }
@@ -0,0 +1,204 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterSurveyDataUnitTracker as ixRegisterSurveyDataUnitTracker,
+    typedefs::RegisterSurveyDataUnitTrackerInput, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Registers the tracker paying out SDUs for scans that spend `resource_mint_pk`.
+pub struct RegisterSurveyDataUnitTracker<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    tracker_kp: &'a Keypair,
+    sdu_mint_pk: &'a Pubkey,
+    resource_mint_pk: &'a Pubkey,
+    coordinates_range: [i64; 2],
+    css_coordinates: [[i64; 2]; 3],
+    origin_coordinates: [i64; 2],
+    css_max_distance: u32,
+    origin_max_distance: u32,
+    distance_weighting: u32,
+    t_max: i64,
+    x_mul: u32,
+    y_mul: u32,
+    z_mul: u32,
+    sdu_max_per_sector: u32,
+    scan_chance_regen_period: i16,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterSurveyDataUnitTracker<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        tracker_kp: &'a Keypair,
+        sdu_mint_pk: &'a Pubkey,
+        resource_mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterSurveyDataUnitTracker {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            tracker_kp,
+            sdu_mint_pk,
+            resource_mint_pk,
+            coordinates_range: [0, 0],
+            css_coordinates: [[0, 0]; 3],
+            origin_coordinates: [0, 0],
+            css_max_distance: 0,
+            origin_max_distance: 0,
+            distance_weighting: 0,
+            t_max: 0,
+            x_mul: 0,
+            y_mul: 0,
+            z_mul: 0,
+            sdu_max_per_sector: 0,
+            scan_chance_regen_period: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// Sectors from `[min, min]` to `[max, max]` can be scanned.
+    pub fn set_coordinates_range(mut self, coordinates_range: [i64; 2]) -> Self {
+        self.coordinates_range = coordinates_range;
+        self
+    }
+
+    /// Central space stations of the three factions.
+    pub fn set_css_coordinates(mut self, css_coordinates: [[i64; 2]; 3]) -> Self {
+        self.css_coordinates = css_coordinates;
+        self
+    }
+
+    pub fn set_origin_coordinates(mut self, origin_coordinates: [i64; 2]) -> Self {
+        self.origin_coordinates = origin_coordinates;
+        self
+    }
+
+    pub fn set_css_max_distance(mut self, css_max_distance: u32) -> Self {
+        self.css_max_distance = css_max_distance;
+        self
+    }
+
+    pub fn set_origin_max_distance(mut self, origin_max_distance: u32) -> Self {
+        self.origin_max_distance = origin_max_distance;
+        self
+    }
+
+    pub fn set_distance_weighting(mut self, distance_weighting: u32) -> Self {
+        self.distance_weighting = distance_weighting;
+        self
+    }
+
+    pub fn set_t_max(mut self, t_max: i64) -> Self {
+        self.t_max = t_max;
+        self
+    }
+
+    /// Multipliers of the noise function.
+    pub fn set_noise_multipliers(mut self, x_mul: u32, y_mul: u32, z_mul: u32) -> Self {
+        self.x_mul = x_mul;
+        self.y_mul = y_mul;
+        self.z_mul = z_mul;
+        self
+    }
+
+    pub fn set_sdu_max_per_sector(mut self, sdu_max_per_sector: u32) -> Self {
+        self.sdu_max_per_sector = sdu_max_per_sector;
+        self
+    }
+
+    pub fn set_scan_chance_regen_period(mut self, scan_chance_regen_period: i16) -> Self {
+        self.scan_chance_regen_period = scan_chance_regen_period;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterSurveyDataUnitTracker<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+        let tracker_pk = self.tracker_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority_pk, true), // RegisterSurveyDataUnitTrackerGameAndProfile<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // RegisterSurveyDataUnitTrackerGameAndProfile<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterSurveyDataUnitTrackerGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),               // pub funder: Signer<'info>,
+                AccountMeta::new(tracker_pk, true), // pub survey_data_unit_tracker: Signer<'info>,
+                AccountMeta::new_readonly(*self.sdu_mint_pk, false), // pub sdu_mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.resource_mint_pk, false), // pub resource_mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterSurveyDataUnitTracker {
+                _input: RegisterSurveyDataUnitTrackerInput {
+                    coordinates_range: self.coordinates_range,
+                    css_coordinates: self.css_coordinates,
+                    origin_coordinates: self.origin_coordinates,
+                    css_max_distance: self.css_max_distance,
+                    origin_max_distance: self.origin_max_distance,
+                    distance_weighting: self.distance_weighting,
+                    t_max: self.t_max,
+                    x_mul: self.x_mul,
+                    y_mul: self.y_mul,
+                    z_mul: self.z_mul,
+                    sdu_max_per_sector: self.sdu_max_per_sector,
+                    scan_chance_regen_period: self.scan_chance_regen_period,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![
+                self.authority_kp.into(),
+                self.funder_kp.into(),
+                self.tracker_kp.into(),
+            ],
+            vec![tracker_pk],
+        )
+    }
+}
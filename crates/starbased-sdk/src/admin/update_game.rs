@@ -0,0 +1,214 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use staratlas_sage::{
+    instruction::UpdateGame as ixUpdateGame,
+    typedefs::{RiskZonesDataUnpacked, UpdateGameInput},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// `Game.mints` entries, in `UpdateGameInput.mints` bit order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameMint {
+    Atlas = 0,
+    Polis = 1,
+    Ammo = 2,
+    Food = 3,
+    Fuel = 4,
+    RepairKit = 5,
+}
+
+/// `Game.vaults` entries, in `UpdateGameInput.vaults` bit order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameVault {
+    Atlas = 0,
+    Polis = 1,
+}
+
+/// `Game.points` categories, in `UpdateGameInput.points` bit order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GamePointsCategory {
+    Lp = 0,
+    CouncilRankXp = 1,
+    PilotXp = 2,
+    DataRunningXp = 3,
+    MiningXp = 4,
+    CraftingXp = 5,
+}
+
+pub struct UpdateGame<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    cargo_stats_definition: Option<Pubkey>,
+    crafting_domain: Option<Pubkey>,
+    mints: [Option<Pubkey>; 6],
+    vaults: [Option<Pubkey>; 2],
+    points: [Option<(Pubkey, Pubkey)>; 6],
+    risk_zones: Option<RiskZonesDataUnpacked>,
+    key_index: u16,
+    payer_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> UpdateGame<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        payer_kp: &'a Keypair,
+    ) -> Self {
+        UpdateGame {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            cargo_stats_definition: None,
+            crafting_domain: None,
+            mints: [None; 6],
+            vaults: [None; 2],
+            points: [None; 6],
+            risk_zones: None,
+            key_index: 0,
+            payer_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// The cargo program `CargoStatsDefinition` used by the game.
+    pub fn set_cargo_stats_definition(mut self, stats_definition_pk: Pubkey) -> Self {
+        self.cargo_stats_definition = Some(stats_definition_pk);
+        self
+    }
+
+    /// The crafting program `Domain` used by the game.
+    pub fn set_crafting_domain(mut self, domain_pk: Pubkey) -> Self {
+        self.crafting_domain = Some(domain_pk);
+        self
+    }
+
+    pub fn set_mint(mut self, mint: GameMint, mint_pk: Pubkey) -> Self {
+        self.mints[mint as usize] = Some(mint_pk);
+        self
+    }
+
+    pub fn set_vault(mut self, vault: GameVault, vault_pk: Pubkey) -> Self {
+        self.vaults[vault as usize] = Some(vault_pk);
+        self
+    }
+
+    pub fn set_points_category(
+        mut self,
+        category: GamePointsCategory,
+        points_category_pk: Pubkey,
+        points_modifier_pk: Pubkey,
+    ) -> Self {
+        self.points[category as usize] = Some((points_category_pk, points_modifier_pk));
+        self
+    }
+
+    pub fn set_risk_zones(mut self, risk_zones: RiskZonesDataUnpacked) -> Self {
+        self.risk_zones = Some(risk_zones);
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for UpdateGame<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.payer_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(authority_pk, true), // UpdateGameGameAndProfile<'info> pub key: Signer<'info>,
+            AccountMeta::new_readonly(*self.profile_pk, false), // UpdateGameGameAndProfile<'info>pub profile: AccountInfo<'info>,
+            AccountMeta::new(*self.game_pk, false), // UpdateGameGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+        ];
+
+        // remaining accounts are read in `UpdateGameInput` field order: cargo, crafting, mints, vaults, points
+        let cargo = flag_account(&mut accounts, self.cargo_stats_definition);
+        let crafting = flag_account(&mut accounts, self.crafting_domain);
+        let mints = flag_accounts(&mut accounts, &self.mints);
+        let vaults = flag_accounts(&mut accounts, &self.vaults);
+
+        let mut points = 0u8;
+        for (bit, category) in self.points.iter().enumerate() {
+            if let Some((points_category_pk, points_modifier_pk)) = category {
+                points |= 1 << bit;
+                accounts.push(AccountMeta::new_readonly(*points_category_pk, false));
+                accounts.push(AccountMeta::new_readonly(*points_modifier_pk, false));
+            }
+        }
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts,
+            data: ixUpdateGame {
+                _input: UpdateGameInput {
+                    cargo,
+                    crafting,
+                    mints,
+                    vaults,
+                    points,
+                    risk_zones: self.risk_zones,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.payer_kp.into()],
+            vec![*self.game_pk],
+        )
+    }
+}
+
+fn flag_account(accounts: &mut Vec<AccountMeta>, pubkey: Option<Pubkey>) -> u8 {
+    match pubkey {
+        Some(pubkey) => {
+            accounts.push(AccountMeta::new_readonly(pubkey, false));
+            1
+        }
+        None => 0,
+    }
+}
+
+fn flag_accounts(accounts: &mut Vec<AccountMeta>, pubkeys: &[Option<Pubkey>]) -> u8 {
+    let mut flags = 0u8;
+    for (bit, pubkey) in pubkeys.iter().enumerate() {
+        if let Some(pubkey) = pubkey {
+            flags |= 1 << bit;
+            accounts.push(AccountMeta::new_readonly(*pubkey, false));
+        }
+    }
+    flags
+}
@@ -0,0 +1,175 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use staratlas_sage::{
+    instruction::UpdateGameState as ixUpdateGameState,
+    typedefs::{
+        FleetInput, MiscVariablesInput, StarbaseLevelInfoArrayInput, StarbaseUpkeepInfoArrayInput,
+        StarbaseUpkeepInfoUnpacked, UpdateGameStateInput,
+    },
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Crafting recipe accounts passed along with each `StarbaseLevelInfoArrayInput`.
+///
+/// Levels without an upgrade recipe use the default pubkey for all three accounts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StarbaseLevelRecipes {
+    /// The `recipe_for_upgrade` currently stored for the level
+    pub old_recipe_for_upgrade: Pubkey,
+    /// The recipe that replaces `old_recipe_for_upgrade`
+    pub new_recipe_for_upgrade: Pubkey,
+    /// The recipe category crafted at starbases of this level
+    pub recipe_category_for_level: Pubkey,
+}
+
+pub struct UpdateGameState<'a> {
+    authority_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    starbase_level_info: Vec<(StarbaseLevelInfoArrayInput, StarbaseLevelRecipes)>,
+    upkeep_info: Vec<StarbaseUpkeepInfoArrayInput>,
+    max_fleet_size: Option<u32>,
+    misc: Option<MiscVariablesInput>,
+    key_index: u16,
+    payer_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> UpdateGameState<'a> {
+    pub fn new(
+        authority_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        payer_kp: &'a Keypair,
+    ) -> Self {
+        UpdateGameState {
+            authority_kp,
+            profile_pk,
+            game_pk,
+            game_state_pk,
+            starbase_level_info: vec![],
+            upkeep_info: vec![],
+            max_fleet_size: None,
+            misc: None,
+            key_index: 0,
+            payer_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn add_starbase_level_info(self, level_info: StarbaseLevelInfoArrayInput) -> Self {
+        self.add_starbase_level_info_with_recipes(level_info, StarbaseLevelRecipes::default())
+    }
+
+    pub fn add_starbase_level_info_with_recipes(
+        mut self,
+        level_info: StarbaseLevelInfoArrayInput,
+        recipes: StarbaseLevelRecipes,
+    ) -> Self {
+        self.starbase_level_info.push((level_info, recipes));
+        self
+    }
+
+    pub fn add_upkeep_info(mut self, level: u8, info: StarbaseUpkeepInfoUnpacked) -> Self {
+        self.upkeep_info
+            .push(StarbaseUpkeepInfoArrayInput { level, info });
+        self
+    }
+
+    pub fn set_max_fleet_size(mut self, max_fleet_size: u32) -> Self {
+        self.max_fleet_size = Some(max_fleet_size);
+        self
+    }
+
+    pub fn set_misc_variables(mut self, misc: MiscVariablesInput) -> Self {
+        self.misc = Some(misc);
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for UpdateGameState<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.payer_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let authority_pk = self.authority_kp.pubkey();
+
+        let mut accounts = vec![
+            AccountMeta::new_readonly(authority_pk, true), // UpdateGameStateGameAndProfile<'info> pub key: Signer<'info>,
+            AccountMeta::new_readonly(*self.profile_pk, false), // UpdateGameStateGameAndProfile<'info>pub profile: AccountInfo<'info>,
+            AccountMeta::new_readonly(*self.game_pk, false), // UpdateGameStateGameAndProfile<'info> pub game_id: AccountInfo<'info>,
+            AccountMeta::new(*self.game_state_pk, false),    // pub game_state: AccountInfo<'info>,
+        ];
+
+        let mut starbase_level_info_array = Vec::with_capacity(self.starbase_level_info.len());
+        for (level_info, recipes) in self.starbase_level_info {
+            accounts.push(AccountMeta::new(recipes.old_recipe_for_upgrade, false)); // old_recipe_for_upgrade
+            accounts.push(AccountMeta::new(recipes.new_recipe_for_upgrade, false)); // new_recipe_for_upgrade
+            accounts.push(AccountMeta::new(recipes.recipe_category_for_level, false)); // recipe_category_for_level
+            starbase_level_info_array.push(level_info);
+        }
+
+        let fleet = if starbase_level_info_array.is_empty()
+            && self.upkeep_info.is_empty()
+            && self.max_fleet_size.is_none()
+        {
+            None
+        } else {
+            Some(FleetInput {
+                starbase_level_info_array: (!starbase_level_info_array.is_empty())
+                    .then_some(starbase_level_info_array),
+                upkeep_info_array: (!self.upkeep_info.is_empty()).then_some(self.upkeep_info),
+                max_fleet_size: self.max_fleet_size,
+            })
+        };
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts,
+            data: ixUpdateGameState {
+                _input: UpdateGameStateInput {
+                    fleet,
+                    misc: self.misc,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.authority_kp.into(), self.payer_kp.into()],
+            vec![*self.game_state_pk],
+        )
+    }
+}
@@ -0,0 +1,314 @@
+//! Splitting many builders across transactions.
+//!
+//! Builders are packed in order into transactions under the packet size and compute unit limits,
+//! a builder's instructions always stay in the same transaction. Chunks are sent in order, a chunk
+//! failing after its retries skips the following ones unless `set_stop_on_error(false)`, as later
+//! builders may depend on it (e.g. a starbase on its sector). Only transient errors are retried,
+//! see [`Sender::is_transient`]. The compute budgets of a chunk's builders are merged into its
+//! transaction, its unit limit is the sum of the builders' estimates.
+//!
+//! ```ignore
+//! let mut batcher = Batcher::new(&funder_kp);
+//! for sector in sectors {
+//!     batcher = batcher.add(RegisterSector::new(/* .. */).set_coordinates(sector));
+//! }
+//! for chunk in batcher.send(&mut svm) {
+//!     println!("{:?}: {:?}", chunk.items, chunk.result);
+//! }
+//! ```
+use solana_sdk::{
+    instruction::Instruction,
+    message::Message,
+    packet::PACKET_DATA_SIZE,
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+};
+use std::{fmt, ops::Range};
+
+use crate::{
+    compute_budget::{ComputeBudget, ComputeUnitLimit},
+    sender::Sender,
+    tx::{InstructionBuilder, TxBuilder, TxSigner},
+};
+
+/// Compute units a transaction can request.
+pub const MAX_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Compute units the runtime allots an instruction when the transaction sets no limit.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNITS: u32 = 200_000;
+
+#[derive(Debug)]
+pub enum BatchError<E> {
+    /// A single builder doesn't fit in a transaction.
+    TooLarge {
+        size: usize,
+    },
+    /// Not sent, an earlier chunk failed.
+    Skipped,
+    Transaction(E),
+}
+
+impl<E: fmt::Debug> fmt::Display for BatchError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BatchError::TooLarge { size } => write!(
+                f,
+                "transaction of {} bytes exceeds {} bytes",
+                size, PACKET_DATA_SIZE
+            ),
+            BatchError::Skipped => write!(f, "skipped after an earlier chunk failed"),
+            BatchError::Transaction(err) => write!(f, "transaction failed: {:?}", err),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for BatchError<E> {}
+
+/// Outcome of one transaction of the batch.
+#[derive(Debug)]
+pub struct ChunkResult<E> {
+    /// Indices of the builders in the chunk, in the order they were added.
+    pub items: Range<usize>,
+    /// Derived pubkeys of the chunk's builders.
+    pub pubkeys: Vec<Pubkey>,
+    /// Transactions sent, retries included.
+    pub attempts: usize,
+    pub result: Result<Signature, BatchError<E>>,
+}
+
+struct BatchItem<'a> {
+    instructions: Vec<Instruction>,
+    signers: Vec<TxSigner<'a>>,
+    pubkeys: Vec<Pubkey>,
+    compute_units: u32,
+    compute_budget: ComputeBudget,
+}
+
+pub struct Batcher<'a> {
+    payer_kp: &'a Keypair,
+    items: Vec<BatchItem<'a>>,
+    max_size: usize,
+    max_compute_units: u32,
+    max_retries: usize,
+    stop_on_error: bool,
+}
+
+impl<'a> Batcher<'a> {
+    pub fn new(payer_kp: &'a Keypair) -> Self {
+        Batcher {
+            payer_kp,
+            items: vec![],
+            max_size: PACKET_DATA_SIZE,
+            max_compute_units: MAX_COMPUTE_UNITS,
+            max_retries: 2,
+            stop_on_error: true,
+        }
+    }
+
+    pub fn set_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+
+    pub fn set_max_compute_units(mut self, max_compute_units: u32) -> Self {
+        self.max_compute_units = max_compute_units;
+        self
+    }
+
+    /// Retries of a chunk failing with a transient error.
+    pub fn set_max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    pub fn set_stop_on_error(mut self, stop_on_error: bool) -> Self {
+        self.stop_on_error = stop_on_error;
+        self
+    }
+
+    /// Adds a builder counted with its fixed compute unit limit, or
+    /// [`DEFAULT_INSTRUCTION_COMPUTE_UNITS`] per instruction without one. A chunk with an auto
+    /// limit is simulated when sent, see [`TxBuilder::resolve_compute_budget`].
+    pub fn add(mut self, builder: impl InstructionBuilder<'a>) -> Self {
+        let compute_budget = builder.compute_budget();
+        let (instructions, signers, pubkeys) = builder.instructions();
+        let compute_units = match compute_budget.unit_limit {
+            Some(ComputeUnitLimit::Fixed(units)) => units,
+            _ => DEFAULT_INSTRUCTION_COMPUTE_UNITS.saturating_mul(instructions.len() as u32),
+        };
+        self.items.push(BatchItem {
+            instructions,
+            signers,
+            pubkeys,
+            compute_units,
+            compute_budget,
+        });
+        self
+    }
+
+    /// Adds a builder consuming up to `compute_units`.
+    pub fn add_with_compute_units(
+        mut self,
+        builder: impl InstructionBuilder<'a>,
+        compute_units: u32,
+    ) -> Self {
+        let compute_budget = builder.compute_budget();
+        let (instructions, signers, pubkeys) = builder.instructions();
+        self.items.push(BatchItem {
+            instructions,
+            signers,
+            pubkeys,
+            compute_units,
+            compute_budget,
+        });
+        self
+    }
+
+    /// Adds instructions that are always sent in the same transaction.
+    pub fn add_instructions(
+        mut self,
+        instructions: Vec<Instruction>,
+        signers: Vec<TxSigner<'a>>,
+        pubkeys: Vec<Pubkey>,
+        compute_units: u32,
+    ) -> Self {
+        self.items.push(BatchItem {
+            instructions,
+            signers,
+            pubkeys,
+            compute_units,
+            compute_budget: ComputeBudget::default(),
+        });
+        self
+    }
+
+    /// Ranges of builders sent together, a builder too large on its own gets its own chunk.
+    pub fn chunks(&self) -> Vec<Range<usize>> {
+        let payer_pk = self.payer_kp.pubkey();
+
+        let mut chunks = vec![];
+        let mut start = 0;
+        let mut instructions: Vec<Instruction> = vec![];
+        let mut compute_units = 0u32;
+        let mut compute_budget = ComputeBudget::default();
+        for (index, item) in self.items.iter().enumerate() {
+            if index > start {
+                let next_units = compute_units.saturating_add(item.compute_units);
+                let mut next = chunk_budget(compute_budget.merge(item.compute_budget), next_units)
+                    .instructions();
+                next.extend(instructions.iter().cloned());
+                next.extend(item.instructions.iter().cloned());
+                let fits = transaction_size(&next, &payer_pk) <= self.max_size
+                    && next_units <= self.max_compute_units;
+                if !fits {
+                    chunks.push(start..index);
+                    start = index;
+                    instructions.clear();
+                    compute_units = 0;
+                    compute_budget = ComputeBudget::default();
+                }
+            }
+            instructions.extend(item.instructions.iter().cloned());
+            compute_units = compute_units.saturating_add(item.compute_units);
+            compute_budget = compute_budget.merge(item.compute_budget);
+        }
+        if start < self.items.len() {
+            chunks.push(start..self.items.len());
+        }
+
+        chunks
+    }
+
+    /// Sends the chunks in order, one result per chunk.
+    pub fn send<S: Sender>(self, sender: &mut S) -> Vec<ChunkResult<S::Error>> {
+        let chunks = self.chunks();
+        let payer_pk = self.payer_kp.pubkey();
+
+        let mut items = self.items.into_iter();
+        let mut failed = false;
+        let mut results = Vec::with_capacity(chunks.len());
+        for range in chunks {
+            let mut tx_builder = TxBuilder::new(self.payer_kp);
+            let mut compute_budget = ComputeBudget::default();
+            let mut compute_units = 0u32;
+            for item in items.by_ref().take(range.len()) {
+                tx_builder =
+                    tx_builder.add_instructions(item.instructions, item.signers, item.pubkeys);
+                compute_budget = compute_budget.merge(item.compute_budget);
+                compute_units = compute_units.saturating_add(item.compute_units);
+            }
+            let compute_budget = chunk_budget(compute_budget, compute_units);
+            let mut tx_builder = tx_builder.set_compute_budget(compute_budget);
+            let pubkeys = tx_builder.pubkeys().to_vec();
+
+            let mut instructions = compute_budget.instructions();
+            instructions.extend(tx_builder.instructions().iter().cloned());
+            let size = transaction_size(&instructions, &payer_pk);
+            let (attempts, result) = if failed && self.stop_on_error {
+                (0, Err(BatchError::Skipped))
+            } else if size > self.max_size {
+                (0, Err(BatchError::TooLarge { size }))
+            } else {
+                send_with_retries(sender, &mut tx_builder, self.max_retries)
+            };
+            failed |= result.is_err();
+
+            results.push(ChunkResult {
+                items: range,
+                pubkeys,
+                attempts,
+                result,
+            });
+        }
+
+        results
+    }
+}
+
+/// The merged budget of a chunk's builders with their summed `compute_units` as limit, an auto
+/// limit is kept to be resolved by simulation.
+fn chunk_budget(compute_budget: ComputeBudget, compute_units: u32) -> ComputeBudget {
+    if compute_budget.is_auto() {
+        compute_budget
+    } else {
+        compute_budget.set_unit_limit(compute_units)
+    }
+}
+
+/// Serialized size of a legacy transaction with `instructions`, signatures included.
+pub fn transaction_size(instructions: &[Instruction], payer_pk: &Pubkey) -> usize {
+    let message = Message::new(instructions, Some(payer_pk));
+    let signatures = message.header.num_required_signatures as usize;
+    short_vec_len(signatures) + signatures * 64 + message.serialize().len()
+}
+
+/// Size of a compact-u16 length prefix.
+fn short_vec_len(len: usize) -> usize {
+    match len {
+        0..=0x7f => 1,
+        0x80..=0x3fff => 2,
+        _ => 3,
+    }
+}
+
+fn send_with_retries<S: Sender>(
+    sender: &mut S,
+    tx_builder: &mut TxBuilder,
+    max_retries: usize,
+) -> (usize, Result<Signature, BatchError<S::Error>>) {
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let result = tx_builder
+            .resolve_compute_budget(sender, &[])
+            .and_then(|_| sender.latest_blockhash())
+            .and_then(|block_hash| tx_builder.transaction(block_hash).map_err(S::Error::from))
+            .and_then(|tx| sender.send(tx));
+        match result {
+            Ok(signature) => return (attempts, Ok(signature)),
+            Err(err) if S::is_transient(&err) && attempts <= max_retries => continue,
+            Err(err) => return (attempts, Err(BatchError::Transaction(err))),
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_cargo::{
+    instruction::InitCargoType as ixInitCargoType, state::CargoType, typedefs::InitCargoTypeInput,
+    ID as CARGO_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Length of the `CargoType` account data before its stats, discriminator included.
+const CARGO_TYPE_LEN: usize = 8 + 1 + 32 + 32 + 32 + 1 + 2 + 2;
+
+pub fn find_cargo_type_pda(
+    stats_definition_pk: &Pubkey,
+    mint_pk: &Pubkey,
+    seq_id: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"cargo_type",
+            stats_definition_pk.as_ref(),
+            mint_pk.as_ref(),
+            &seq_id.to_le_bytes(),
+        ],
+        &CARGO_PROGRAM_ID,
+    )
+}
+
+/// The stat values following the `CargoType` account data, `None` when `data` doesn't decode.
+/// The first stat is the cargo space a unit takes up.
+pub fn cargo_type_stats(data: &[u8]) -> Option<Vec<u64>> {
+    let cargo_type = CargoType::try_deserialize(&mut &data[..]).ok()?;
+    let stats = data.get(CARGO_TYPE_LEN..)?;
+
+    stats
+        .chunks_exact(8)
+        .take(cargo_type.stats_count as usize)
+        .map(|stat| stat.try_into().ok().map(u64::from_le_bytes))
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| values.len() == cargo_type.stats_count as usize)
+}
+
+/// Registers the cargo type of `mint_pk` in the stats definition, signed by a cargo manager key.
+pub struct InitCargoType<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    stats_definition_pk: &'a Pubkey,
+    mint_pk: &'a Pubkey,
+    values: Vec<u64>,
+    seq_id: u16,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> InitCargoType<'a> {
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        stats_definition_pk: &'a Pubkey,
+        mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        InitCargoType {
+            key_kp,
+            profile_pk,
+            stats_definition_pk,
+            mint_pk,
+            values: vec![1],
+            seq_id: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// A value for every stat of the definition, defaults to a cargo space of 1 per unit.
+    pub fn set_values(mut self, values: Vec<u64>) -> Self {
+        self.values = values;
+        self
+    }
+
+    /// The `seq_id` of the stats definition.
+    pub fn set_seq_id(mut self, seq_id: u16) -> Self {
+        self.seq_id = seq_id;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for InitCargoType<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let funder_pk = self.funder_kp.pubkey();
+        let (cargo_type_pda, _bump) =
+            find_cargo_type_pda(self.stats_definition_pk, self.mint_pk, self.seq_id);
+
+        let ix = Instruction {
+            program_id: CARGO_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // pub profile: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),                  // pub funder: Signer<'info>,
+                AccountMeta::new_readonly(*self.mint_pk, false),    // pub mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.stats_definition_pk, false), // pub stats_definition: AccountInfo<'info>,
+                AccountMeta::new(cargo_type_pda, false), // pub cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixInitCargoType {
+                _input: InitCargoTypeInput {
+                    key_index: self.key_index,
+                    values: self.values,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![cargo_type_pda],
+        )
+    }
+}
@@ -0,0 +1,91 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_cargo::{
+    instruction::InitDefinition as ixInitDefinition, typedefs::InitDefinitionInput,
+    ID as CARGO_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct InitDefinition<'a> {
+    profile_pk: &'a Pubkey,
+    stats_definition_kp: &'a Keypair,
+    cargo_stats: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> InitDefinition<'a> {
+    pub fn new(
+        profile_pk: &'a Pubkey,
+        stats_definition_kp: &'a Keypair,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        InitDefinition {
+            profile_pk,
+            stats_definition_kp,
+            cargo_stats: 1,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_cargo_stats(mut self, cargo_stats: u16) -> Self {
+        self.cargo_stats = cargo_stats;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for InitDefinition<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let funder_pk = self.funder_kp.pubkey();
+        let stats_definition_pk = self.stats_definition_kp.pubkey();
+
+        let ix = Instruction {
+            program_id: CARGO_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(*self.profile_pk, false), // pub profile: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),                  // pub funder: Signer<'info>,
+                AccountMeta::new(stats_definition_pk, true), // pub stats_definition: Signer<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixInitDefinition {
+                _input: InitDefinitionInput {
+                    cargo_stats: self.cargo_stats,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.funder_kp.into(), self.stats_definition_kp.into()],
+            vec![stats_definition_pk],
+        )
+    }
+}
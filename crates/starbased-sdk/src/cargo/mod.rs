@@ -0,0 +1,5 @@
+mod init_cargo_type;
+mod init_definition;
+
+pub use init_cargo_type::*;
+pub use init_definition::*;
@@ -0,0 +1,138 @@
+//! Compute unit limit and priority fee of transactions.
+//!
+//! A [`ComputeBudget`] is sent as `ComputeBudget` program instructions ahead of the builders'
+//! instructions. In a transaction of several builders the limits add up, a builder without one
+//! counting the runtime default per instruction, and the highest price wins.
+//! [`TxBuilder::set_compute_budget`](crate::tx::TxBuilder::set_compute_budget) overrides both. An
+//! auto limit simulates the transaction first and requests the consumed units plus a margin.
+//!
+//! ```ignore
+//! StartSubwarp::new(&key, &profile, &profile_faction, &fleet, &game, &game_state, [2, 2], &funder)
+//!     .set_compute_budget(ComputeBudget::new().set_auto_unit_limit(10).set_unit_price(426))
+//!     .send(&mut client)?;
+//! ```
+use solana_sdk::{compute_budget::ComputeBudgetInstruction, instruction::Instruction};
+
+use crate::batch::{DEFAULT_INSTRUCTION_COMPUTE_UNITS, MAX_COMPUTE_UNITS};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeUnitLimit {
+    Fixed(u32),
+    /// Units consumed in a simulation plus `margin_percent`.
+    Auto {
+        margin_percent: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ComputeBudget {
+    /// `None` keeps the runtime default of 200k units per instruction.
+    pub unit_limit: Option<ComputeUnitLimit>,
+    /// Micro-lamports per compute unit, `None` pays no priority fee.
+    pub unit_price: Option<u64>,
+}
+
+impl ComputeBudget {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_unit_limit(mut self, units: u32) -> Self {
+        self.unit_limit = Some(ComputeUnitLimit::Fixed(units));
+        self
+    }
+
+    pub fn set_auto_unit_limit(mut self, margin_percent: u32) -> Self {
+        self.unit_limit = Some(ComputeUnitLimit::Auto { margin_percent });
+        self
+    }
+
+    pub fn set_unit_price(mut self, micro_lamports: u64) -> Self {
+        self.unit_price = Some(micro_lamports);
+        self
+    }
+
+    pub fn is_auto(&self) -> bool {
+        matches!(self.unit_limit, Some(ComputeUnitLimit::Auto { .. }))
+    }
+
+    /// [`ComputeBudget::merge`] of the budgets of `instructions` and `other_instructions`
+    /// instructions. Merged with a fixed limit, a missing one counts
+    /// [`DEFAULT_INSTRUCTION_COMPUTE_UNITS`] per instruction, as the runtime would allot them.
+    pub fn merge_counting(
+        self,
+        instructions: usize,
+        other: Self,
+        other_instructions: usize,
+    ) -> Self {
+        let default_limit = |budget: Self, instructions: usize| {
+            budget.set_unit_limit(
+                DEFAULT_INSTRUCTION_COMPUTE_UNITS.saturating_mul(instructions as u32),
+            )
+        };
+
+        match (self.unit_limit, other.unit_limit) {
+            (None, Some(ComputeUnitLimit::Fixed(_))) => {
+                default_limit(self, instructions).merge(other)
+            }
+            (Some(ComputeUnitLimit::Fixed(_)), None) => {
+                self.merge(default_limit(other, other_instructions))
+            }
+            _ => self.merge(other),
+        }
+    }
+
+    /// Budget of a transaction holding both: fixed limits add up, an auto limit makes the whole
+    /// transaction auto with the largest margin, the highest price wins. A missing limit counts
+    /// no units, see [`ComputeBudget::merge_counting`].
+    pub fn merge(self, other: Self) -> Self {
+        use ComputeUnitLimit::*;
+
+        let unit_limit = match (self.unit_limit, other.unit_limit) {
+            (None, limit) | (limit, None) => limit,
+            (Some(Fixed(a)), Some(Fixed(b))) => Some(Fixed(a.saturating_add(b))),
+            (Some(Auto { margin_percent: a }), Some(Auto { margin_percent: b })) => Some(Auto {
+                margin_percent: a.max(b),
+            }),
+            (Some(Auto { margin_percent }), _) | (_, Some(Auto { margin_percent })) => {
+                Some(Auto { margin_percent })
+            }
+        };
+
+        ComputeBudget {
+            unit_limit,
+            unit_price: self.unit_price.max(other.unit_price),
+        }
+    }
+
+    /// Replaces an auto limit with `units_consumed` plus its margin.
+    pub fn resolve(self, units_consumed: u64) -> Self {
+        let Some(ComputeUnitLimit::Auto { margin_percent }) = self.unit_limit else {
+            return self;
+        };
+
+        let units = units_consumed.saturating_mul(100 + margin_percent as u64) / 100;
+        self.set_unit_limit(units.min(MAX_COMPUTE_UNITS as u64) as u32)
+    }
+
+    /// The `ComputeBudget` program instructions, an unresolved auto limit requests
+    /// [`MAX_COMPUTE_UNITS`].
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut instructions = vec![];
+        match self.unit_limit {
+            Some(ComputeUnitLimit::Fixed(units)) => instructions.push(
+                ComputeBudgetInstruction::set_compute_unit_limit(units.min(MAX_COMPUTE_UNITS)),
+            ),
+            Some(ComputeUnitLimit::Auto { .. }) => instructions.push(
+                ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS),
+            ),
+            None => {}
+        }
+        if let Some(micro_lamports) = self.unit_price {
+            instructions.push(ComputeBudgetInstruction::set_compute_unit_price(
+                micro_lamports,
+            ));
+        }
+        instructions
+    }
+}
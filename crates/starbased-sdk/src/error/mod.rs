@@ -0,0 +1,336 @@
+//! Typed custom program errors of failed transactions.
+//!
+//! `InstructionError::Custom(code)` is mapped to a [`ProgramError`] of the failing program: the
+//! `ErrorCode` generated from the `errors` list of its IDL (e.g.
+//! [`staratlas_sage::error_code::ErrorCode`]), or an Anchor framework error for codes below
+//! 6000. The failing program is read from the transaction logs, the first
+//! `Program <id> failed: custom program error` line, so errors raised in a CPI (e.g. cargo called
+//! by SAGE) are attributed to the inner program.
+//!
+//! ```ignore
+//! let err = builder.send(&mut svm).unwrap_err();
+//! let err = StarAtlasError::from_failed(&err).unwrap();
+//! assert_eq!(err.error, Some(ProgramError::Sage(SageErrorCode::InsufficientFuel)));
+//! ```
+mod framework;
+
+use litesvm::types::FailedTransactionMetadata;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+use std::{fmt, str::FromStr};
+
+pub use staratlas_cargo::error_code::ErrorCode as CargoErrorCode;
+pub use staratlas_crew::error_code::ErrorCode as CrewErrorCode;
+pub use staratlas_fleet_rentals::error_code::ErrorCode as FleetRentalsErrorCode;
+pub use staratlas_player_profile::error_code::ErrorCode as PlayerProfileErrorCode;
+pub use staratlas_profile_faction::error_code::ErrorCode as ProfileFactionErrorCode;
+pub use staratlas_sage::error_code::ErrorCode as SageErrorCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Program {
+    Sage,
+    Cargo,
+    Crew,
+    PlayerProfile,
+    ProfileFaction,
+    FleetRentals,
+}
+
+impl Program {
+    pub const ALL: [Program; 6] = [
+        Program::Sage,
+        Program::Cargo,
+        Program::Crew,
+        Program::PlayerProfile,
+        Program::ProfileFaction,
+        Program::FleetRentals,
+    ];
+
+    pub fn id(&self) -> Pubkey {
+        let id = match self {
+            Program::Sage => staratlas_sage::ID.to_bytes(),
+            Program::Cargo => staratlas_cargo::ID.to_bytes(),
+            Program::Crew => staratlas_crew::ID.to_bytes(),
+            Program::PlayerProfile => staratlas_player_profile::ID.to_bytes(),
+            Program::ProfileFaction => staratlas_profile_faction::ID.to_bytes(),
+            Program::FleetRentals => staratlas_fleet_rentals::ID.to_bytes(),
+        };
+        Pubkey::new_from_array(id)
+    }
+
+    pub fn from_id(program_id: &Pubkey) -> Option<Program> {
+        Program::ALL
+            .into_iter()
+            .find(|program| program.id() == *program_id)
+    }
+
+    /// The program's custom errors, from its IDL.
+    pub fn errors(&self) -> Vec<ProgramError> {
+        match self {
+            Program::Sage => SageErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::Sage)
+                .collect(),
+            Program::Cargo => CargoErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::Cargo)
+                .collect(),
+            Program::Crew => CrewErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::Crew)
+                .collect(),
+            Program::PlayerProfile => PlayerProfileErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::PlayerProfile)
+                .collect(),
+            Program::ProfileFaction => ProfileFactionErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::ProfileFaction)
+                .collect(),
+            Program::FleetRentals => FleetRentalsErrorCode::ALL
+                .iter()
+                .copied()
+                .map(ProgramError::FleetRentals)
+                .collect(),
+        }
+    }
+
+    /// The program's custom error or, for codes below 6000, the Anchor framework error.
+    pub fn error(&self, code: u32) -> Option<ProgramError> {
+        if code < ANCHOR_ERROR_CODE_OFFSET {
+            return AnchorError::from_code(code).map(ProgramError::Anchor);
+        }
+        match self {
+            Program::Sage => SageErrorCode::from_code(code).map(ProgramError::Sage),
+            Program::Cargo => CargoErrorCode::from_code(code).map(ProgramError::Cargo),
+            Program::Crew => CrewErrorCode::from_code(code).map(ProgramError::Crew),
+            Program::PlayerProfile => {
+                PlayerProfileErrorCode::from_code(code).map(ProgramError::PlayerProfile)
+            }
+            Program::ProfileFaction => {
+                ProfileFactionErrorCode::from_code(code).map(ProgramError::ProfileFaction)
+            }
+            Program::FleetRentals => {
+                FleetRentalsErrorCode::from_code(code).map(ProgramError::FleetRentals)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Program::Sage => "SAGE",
+            Program::Cargo => "Cargo",
+            Program::Crew => "Crew",
+            Program::PlayerProfile => "Player Profile",
+            Program::ProfileFaction => "Profile Faction",
+            Program::FleetRentals => "Fleet Rentals",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// First code of the programs' custom errors, Anchor's framework errors are below it.
+const ANCHOR_ERROR_CODE_OFFSET: u32 = 6000;
+
+/// An error of Anchor's framework, e.g. `AccountNotInitialized` (3012).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AnchorError {
+    pub code: u32,
+    pub name: &'static str,
+    pub message: &'static str,
+}
+
+impl AnchorError {
+    pub fn from_code(code: u32) -> Option<AnchorError> {
+        framework::FRAMEWORK_ERRORS
+            .iter()
+            .find(|(error_code, _, _)| *error_code == code)
+            .map(|(code, name, message)| AnchorError {
+                code: *code,
+                name,
+                message,
+            })
+    }
+}
+
+/// A typed error of a Star Atlas program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProgramError {
+    Sage(SageErrorCode),
+    Cargo(CargoErrorCode),
+    Crew(CrewErrorCode),
+    PlayerProfile(PlayerProfileErrorCode),
+    ProfileFaction(ProfileFactionErrorCode),
+    FleetRentals(FleetRentalsErrorCode),
+    /// Raised by the Anchor framework of any of the programs.
+    Anchor(AnchorError),
+}
+
+impl ProgramError {
+    pub fn code(&self) -> u32 {
+        match self {
+            ProgramError::Sage(error) => error.code(),
+            ProgramError::Cargo(error) => error.code(),
+            ProgramError::Crew(error) => error.code(),
+            ProgramError::PlayerProfile(error) => error.code(),
+            ProgramError::ProfileFaction(error) => error.code(),
+            ProgramError::FleetRentals(error) => error.code(),
+            ProgramError::Anchor(error) => error.code,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProgramError::Sage(error) => error.name(),
+            ProgramError::Cargo(error) => error.name(),
+            ProgramError::Crew(error) => error.name(),
+            ProgramError::PlayerProfile(error) => error.name(),
+            ProgramError::ProfileFaction(error) => error.name(),
+            ProgramError::FleetRentals(error) => error.name(),
+            ProgramError::Anchor(error) => error.name,
+        }
+    }
+
+    pub fn message(&self) -> &'static str {
+        match self {
+            ProgramError::Sage(error) => error.message(),
+            ProgramError::Cargo(error) => error.message(),
+            ProgramError::Crew(error) => error.message(),
+            ProgramError::PlayerProfile(error) => error.message(),
+            ProgramError::ProfileFaction(error) => error.message(),
+            ProgramError::FleetRentals(error) => error.message(),
+            ProgramError::Anchor(error) => error.message,
+        }
+    }
+}
+
+/// A custom program error of a failed transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StarAtlasError {
+    /// `None` when the failing program is not a Star Atlas program.
+    pub program: Option<Program>,
+    pub program_id: Option<Pubkey>,
+    pub code: u32,
+    /// `None` when the program or the code is unknown.
+    pub error: Option<ProgramError>,
+    /// Error name, `"Unknown"` when the code is not known.
+    pub name: String,
+    pub message: String,
+    /// Index of the failing instruction in the transaction.
+    pub instruction_index: u8,
+    /// Log lines of the failing program invocation.
+    pub logs: Vec<String>,
+}
+
+impl StarAtlasError {
+    pub fn from_failed(meta: &FailedTransactionMetadata) -> Option<Self> {
+        Self::from_transaction_error(&meta.err, &meta.meta.logs)
+    }
+
+    /// Logs are only available for errors of the preflight simulation.
+    pub fn from_client_error(err: &ClientError) -> Option<Self> {
+        let logs = match err.kind() {
+            ClientErrorKind::RpcError(RpcError::RpcResponseError {
+                data: RpcResponseErrorData::SendTransactionPreflightFailure(result),
+                ..
+            }) => result.logs.clone().unwrap_or_default(),
+            _ => Vec::new(),
+        };
+        Self::from_transaction_error(&err.get_transaction_error()?, &logs)
+    }
+
+    /// `None` unless `err` is a custom instruction error.
+    pub fn from_transaction_error(err: &TransactionError, logs: &[String]) -> Option<Self> {
+        let TransactionError::InstructionError(instruction_index, InstructionError::Custom(code)) =
+            err
+        else {
+            return None;
+        };
+
+        let program_id = failed_program(logs, *code)
+            .or_else(|| top_level_program(logs, *instruction_index as usize));
+        let program = program_id.as_ref().and_then(Program::from_id);
+        let error = program.and_then(|program| program.error(*code));
+
+        Some(StarAtlasError {
+            program,
+            program_id,
+            code: *code,
+            error,
+            name: error.map_or("Unknown", |error| error.name()).to_string(),
+            message: error.map_or_else(
+                || format!("custom program error: {:#x}", code),
+                |error| error.message().to_string(),
+            ),
+            instruction_index: *instruction_index,
+            logs: program_id
+                .map(|program_id| invocation_logs(logs, &program_id))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl fmt::Display for StarAtlasError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.program {
+            Some(program) => write!(f, "{} ", program)?,
+            None => write!(f, "program ")?,
+        }
+        write!(
+            f,
+            "error {} ({}) in instruction {}: {}",
+            self.name, self.code, self.instruction_index, self.message
+        )
+    }
+}
+
+impl std::error::Error for StarAtlasError {}
+
+/// The program of the first `Program <id> failed: custom program error: <code>` line.
+fn failed_program(logs: &[String], code: u32) -> Option<Pubkey> {
+    let suffix = format!(" failed: custom program error: {:#x}", code);
+    logs.iter().find_map(|line| {
+        let program_id = line.strip_prefix("Program ")?.strip_suffix(&suffix)?;
+        Pubkey::from_str(program_id).ok()
+    })
+}
+
+/// The program of the `index`th top level instruction.
+fn top_level_program(logs: &[String], index: usize) -> Option<Pubkey> {
+    logs.iter()
+        .filter_map(|line| {
+            line.strip_prefix("Program ")?
+                .strip_suffix(" invoke [1]")
+                .and_then(|program_id| Pubkey::from_str(program_id).ok())
+        })
+        .nth(index)
+}
+
+/// Log lines from the last invocation of `program_id` up to its failure.
+fn invocation_logs(logs: &[String], program_id: &Pubkey) -> Vec<String> {
+    let invoke = format!("Program {} invoke", program_id);
+    let failed = format!("Program {} failed", program_id);
+
+    let end = logs
+        .iter()
+        .position(|line| line.starts_with(&failed))
+        .unwrap_or(logs.len().saturating_sub(1));
+    let start = logs[..end.min(logs.len())]
+        .iter()
+        .rposition(|line| line.starts_with(&invoke))
+        .unwrap_or(0);
+
+    logs.get(start..=end)
+        .map(<[String]>::to_vec)
+        .unwrap_or_default()
+}
@@ -0,0 +1,149 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::AddShipToFleet as ixAddShipToFleet, typedefs::AddShipToFleetInput,
+    ID as SAGE_PROGRAM_ID,
+};
+
+use super::find_fleet_ships_pda;
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct AddShipToFleet<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    ship_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    ship_amount: u8,
+    ship_escrow_index: u32,
+    fleet_ship_info_index: Option<u32>,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> AddShipToFleet<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        ship_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        AddShipToFleet {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            ship_pk,
+            starbase_pk,
+            starbase_player_pk,
+            ship_amount: 1,
+            ship_escrow_index: 0,
+            fleet_ship_info_index: None,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_ship_amount(mut self, ship_amount: u8) -> Self {
+        self.ship_amount = ship_amount;
+        self
+    }
+
+    pub fn set_ship_escrow_index(mut self, ship_escrow_index: u32) -> Self {
+        self.ship_escrow_index = ship_escrow_index;
+        self
+    }
+
+    /// Index of the ship in the fleet ships info, `None` appends the ship.
+    pub fn set_fleet_ship_info_index(mut self, fleet_ship_info_index: Option<u32>) -> Self {
+        self.fleet_ship_info_index = fleet_ship_info_index;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for AddShipToFleet<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let key_pk = self.key_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (fleet_ships_pda, _bump) = find_fleet_ships_pda(self.fleet_pk);
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(key_pk, true), // AddShipToFleetGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // AddShipToFleetGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // AddShipToFleetGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // AddShipToFleetGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // AddShipToFleetGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // AddShipToFleetGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),                     // pub funder: Signer<'info>,
+                AccountMeta::new(fleet_ships_pda, false), // pub fleet_ships: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.ship_pk, false), // pub ship: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // AddShipToFleetStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new(*self.starbase_player_pk, false), // AddShipToFleetStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixAddShipToFleet {
+                _input: AddShipToFleetInput {
+                    ship_amount: self.ship_amount,
+                    ship_escrow_index: self.ship_escrow_index,
+                    fleet_ship_info_index: self.fleet_ship_info_index,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![fleet_ships_pda],
+        )
+    }
+}
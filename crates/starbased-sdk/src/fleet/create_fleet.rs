@@ -0,0 +1,186 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::CreateFleet as ixCreateFleet, typedefs::CreateFleetInput, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_fleet_pda(
+    game_pk: &Pubkey,
+    profile_pk: &Pubkey,
+    fleet_label: &[u8; 32],
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"Fleet", game_pk.as_ref(), profile_pk.as_ref(), fleet_label],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
+pub fn find_fleet_ships_pda(fleet_pk: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"fleet_ships", fleet_pk.as_ref()], &SAGE_PROGRAM_ID)
+}
+
+pub fn find_cargo_pod_pda(pod_seeds: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[b"cargo_pod", pod_seeds], &CARGO_PROGRAM_ID)
+}
+
+pub struct CreateFleet<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    ship_pk: &'a Pubkey,
+    cargo_stats_definition_pk: &'a Pubkey,
+    fleet_label: [u8; 32],
+    ship_amount: u8,
+    ship_escrow_index: u32,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> CreateFleet<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        ship_pk: &'a Pubkey,
+        cargo_stats_definition_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        CreateFleet {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            game_pk,
+            game_state_pk,
+            starbase_pk,
+            starbase_player_pk,
+            ship_pk,
+            cargo_stats_definition_pk,
+            fleet_label: [0u8; 32],
+            ship_amount: 1,
+            ship_escrow_index: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_fleet_label(mut self, fleet_label: String) -> Self {
+        let label_bytes = fleet_label.as_bytes();
+        let mut fleet_label = [0u8; 32];
+        fleet_label[..label_bytes.len()].copy_from_slice(label_bytes);
+
+        self.fleet_label = fleet_label;
+        self
+    }
+
+    pub fn set_ship_amount(mut self, ship_amount: u8) -> Self {
+        self.ship_amount = ship_amount;
+        self
+    }
+
+    pub fn set_ship_escrow_index(mut self, ship_escrow_index: u32) -> Self {
+        self.ship_escrow_index = ship_escrow_index;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for CreateFleet<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let key_pk = self.key_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (fleet_pda, _bump) = find_fleet_pda(self.game_pk, self.profile_pk, &self.fleet_label);
+        let (fleet_ships_pda, _bump) = find_fleet_ships_pda(&fleet_pda);
+
+        let cargo_hold_seeds = Keypair::new().pubkey().to_bytes();
+        let fuel_tank_seeds = Keypair::new().pubkey().to_bytes();
+        let ammo_bank_seeds = Keypair::new().pubkey().to_bytes();
+        let (cargo_hold_pda, _bump) = find_cargo_pod_pda(&cargo_hold_seeds);
+        let (fuel_tank_pda, _bump) = find_cargo_pod_pda(&fuel_tank_seeds);
+        let (ammo_bank_pda, _bump) = find_cargo_pod_pda(&ammo_bank_seeds);
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(key_pk, true), // CreateFleetGameAccountsAndProfileGameAndProfileAndFaction<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // CreateFleetGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // CreateFleetGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile_faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // CreateFleetGameAccountsAndProfileGameAndProfileAndFaction<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // CreateFleetGameAccountsAndProfile<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),                     // pub funder: Signer<'info>,
+                AccountMeta::new(fleet_pda, false), // pub fleet: AccountInfo<'info>,
+                AccountMeta::new(fleet_ships_pda, false), // pub fleet_ships: AccountInfo<'info>,
+                AccountMeta::new(cargo_hold_pda, false), // pub cargo_hold: AccountInfo<'info>,
+                AccountMeta::new(fuel_tank_pda, false), // pub fuel_tank: AccountInfo<'info>,
+                AccountMeta::new(ammo_bank_pda, false), // pub ammo_bank: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.ship_pk, false), // pub ship: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // CreateFleetStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new(*self.starbase_player_pk, false), // CreateFleetStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_stats_definition_pk, false), // pub cargo_stats_definition: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixCreateFleet {
+                _input: CreateFleetInput {
+                    ship_amount: self.ship_amount,
+                    fleet_label: self.fleet_label,
+                    ship_escrow_index: self.ship_escrow_index,
+                    cargo_hold_seeds,
+                    fuel_tank_seeds,
+                    ammo_bank_seeds,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![fleet_pda],
+        )
+    }
+}
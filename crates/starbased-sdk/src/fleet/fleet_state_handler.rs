@@ -0,0 +1,107 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::FleetStateHandler as ixFleetStateHandler, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Completes a finished move or mining, needed once the fleet's eta has passed.
+pub struct FleetStateHandler<'a> {
+    fleet_pk: &'a Pubkey,
+    /// Fuel tank, fuel cargo type, cargo stats definition and fuel mint.
+    fuel: Option<(&'a Pubkey, &'a Pubkey, &'a Pubkey, &'a Pubkey)>,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> FleetStateHandler<'a> {
+    pub fn new(fleet_pk: &'a Pubkey, funder_kp: &'a Keypair) -> Self {
+        FleetStateHandler {
+            fleet_pk,
+            fuel: None,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// Fuel accounts of the fleet, needed to complete a subwarp which burns its fuel on arrival.
+    pub fn set_fuel(
+        mut self,
+        fuel_tank_pk: &'a Pubkey,
+        cargo_type_pk: &'a Pubkey,
+        stats_definition_pk: &'a Pubkey,
+        fuel_mint_pk: &'a Pubkey,
+    ) -> Self {
+        self.fuel = Some((
+            fuel_tank_pk,
+            cargo_type_pk,
+            stats_definition_pk,
+            fuel_mint_pk,
+        ));
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let mut accounts = vec![
+            AccountMeta::new(*self.fleet_pk, false), // pub fleet: AccountInfo<'info>,
+        ];
+
+        // remaining accounts
+        if let Some((fuel_tank_pk, cargo_type_pk, stats_definition_pk, fuel_mint_pk)) = self.fuel {
+            let token_from = get_associated_token_address(fuel_tank_pk, fuel_mint_pk);
+            accounts.extend([
+                AccountMeta::new(*fuel_tank_pk, false),           // fuel_tank
+                AccountMeta::new_readonly(*cargo_type_pk, false), // cargo_type
+                AccountMeta::new_readonly(*stats_definition_pk, false), // stats_definition
+                AccountMeta::new(token_from, false),              // token_from
+                AccountMeta::new(*fuel_mint_pk, false),           // token_mint
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // cargo_program
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // token_program
+            ]);
+        }
+
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts,
+            data: ixFleetStateHandler {}.data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for FleetStateHandler<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
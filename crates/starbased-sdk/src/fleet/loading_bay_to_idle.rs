@@ -0,0 +1,108 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use staratlas_sage::{instruction::LoadingBayToIdle as ixLoadingBayToIdle, ID as SAGE_PROGRAM_ID};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Undocks the fleet from the starbase, it becomes idle in the starbase's sector.
+pub struct LoadingBayToIdle<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> LoadingBayToIdle<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        LoadingBayToIdle {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            starbase_pk,
+            starbase_player_pk,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // LoadingBayToIdleGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // LoadingBayToIdleGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // LoadingBayToIdleGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // LoadingBayToIdleGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // LoadingBayToIdleGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // LoadingBayToIdleGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // LoadingBayToIdleStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new(*self.starbase_player_pk, false), // LoadingBayToIdleStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+            ],
+            data: ixLoadingBayToIdle {
+                _key_index: self.key_index,
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for LoadingBayToIdle<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
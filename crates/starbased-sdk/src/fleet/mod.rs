@@ -0,0 +1,21 @@
+mod add_ship_to_fleet;
+mod create_fleet;
+mod deposit_cargo_to_fleet;
+mod fleet_state_handler;
+mod loading_bay_to_idle;
+mod route;
+mod scan_for_survey_data_units;
+mod start_subwarp;
+mod warp_lane;
+mod warp_to_coordinate;
+
+pub use add_ship_to_fleet::*;
+pub use create_fleet::*;
+pub use deposit_cargo_to_fleet::*;
+pub use fleet_state_handler::*;
+pub use loading_bay_to_idle::*;
+pub use route::*;
+pub use scan_for_survey_data_units::*;
+pub use start_subwarp::*;
+pub use warp_lane::*;
+pub use warp_to_coordinate::*;
@@ -0,0 +1,154 @@
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey, signature::Keypair};
+
+use staratlas_sage::route::{Route, RouteStep};
+
+use super::{FleetStateHandler, StartSubwarp, WarpLane, WarpToCoordinate};
+
+/// Accounts of the fleet moving along a route.
+pub struct RouteAccounts<'a> {
+    pub key_kp: &'a Keypair,
+    pub profile_pk: &'a Pubkey,
+    pub profile_faction_pk: &'a Pubkey,
+    pub fleet_pk: &'a Pubkey,
+    pub game_pk: &'a Pubkey,
+    pub game_state_pk: &'a Pubkey,
+    pub fuel_tank_pk: &'a Pubkey,
+    pub fuel_cargo_type_pk: &'a Pubkey,
+    pub cargo_stats_definition_pk: &'a Pubkey,
+    pub fuel_mint_pk: &'a Pubkey,
+    /// Warp lane fee source, destination and mint.
+    pub fee_token_from_pk: &'a Pubkey,
+    pub fee_token_to_pk: &'a Pubkey,
+    pub fee_mint_pk: &'a Pubkey,
+    pub key_index: u16,
+    pub funder_kp: &'a Keypair,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouteAction {
+    /// Send the instruction.
+    Instruction(Instruction),
+    /// Wait for the fleet to arrive or for the warp cool down to expire.
+    Wait { seconds: u64 },
+    /// Refuel the fleet at the starbase, it is docked and undocked by the caller.
+    Refuel { starbase: Pubkey },
+}
+
+/// The actions to move a fleet along `route`, every move is completed with a `fleetStateHandler`.
+///
+/// `warp_cool_down` is the fleet's warp cool down in seconds.
+pub fn route_actions(
+    route: &Route,
+    accounts: &RouteAccounts<'_>,
+    warp_cool_down: u16,
+) -> Vec<RouteAction> {
+    let mut actions = Vec::new();
+    // seconds since the start of the route, and when the next warp is possible
+    let mut clock = 0;
+    let mut warp_ready_at = 0;
+
+    for step in route.steps.iter() {
+        let instruction = match step {
+            RouteStep::Refuel { starbase } => {
+                actions.push(RouteAction::Refuel {
+                    starbase: Pubkey::new_from_array(starbase.to_bytes()),
+                });
+                continue;
+            }
+            RouteStep::Warp { to, .. } => WarpToCoordinate::new(
+                accounts.key_kp,
+                accounts.profile_pk,
+                accounts.profile_faction_pk,
+                accounts.fleet_pk,
+                accounts.game_pk,
+                accounts.game_state_pk,
+                accounts.fuel_tank_pk,
+                accounts.fuel_cargo_type_pk,
+                accounts.cargo_stats_definition_pk,
+                accounts.fuel_mint_pk,
+                *to,
+                accounts.funder_kp,
+            )
+            .set_profile_key_index(accounts.key_index)
+            .instruction(),
+            RouteStep::WarpLane {
+                from_sector,
+                to_sector,
+                from_starbase,
+                to_starbase,
+                to_sector_index,
+                from_sector_index,
+                ..
+            } => {
+                let from_starbase = Pubkey::new_from_array(from_starbase.to_bytes());
+                let to_starbase = Pubkey::new_from_array(to_starbase.to_bytes());
+                let from_sector = Pubkey::new_from_array(from_sector.to_bytes());
+                let to_sector = Pubkey::new_from_array(to_sector.to_bytes());
+                WarpLane::new(
+                    accounts.key_kp,
+                    accounts.profile_pk,
+                    accounts.profile_faction_pk,
+                    accounts.fleet_pk,
+                    accounts.game_pk,
+                    accounts.game_state_pk,
+                    &from_starbase,
+                    &to_starbase,
+                    &from_sector,
+                    &to_sector,
+                    accounts.fuel_tank_pk,
+                    accounts.fuel_cargo_type_pk,
+                    accounts.cargo_stats_definition_pk,
+                    accounts.fuel_mint_pk,
+                    accounts.fee_token_from_pk,
+                    accounts.fee_token_to_pk,
+                    accounts.fee_mint_pk,
+                    accounts.funder_kp,
+                )
+                .set_to_sector_index(*to_sector_index)
+                .set_from_sector_index(*from_sector_index)
+                .set_profile_key_index(accounts.key_index)
+                .instruction()
+            }
+            RouteStep::Subwarp { to, .. } => StartSubwarp::new(
+                accounts.key_kp,
+                accounts.profile_pk,
+                accounts.profile_faction_pk,
+                accounts.fleet_pk,
+                accounts.game_pk,
+                accounts.game_state_pk,
+                *to,
+                accounts.funder_kp,
+            )
+            .set_profile_key_index(accounts.key_index)
+            .instruction(),
+        };
+
+        let is_warp = matches!(step, RouteStep::Warp { .. } | RouteStep::WarpLane { .. });
+        if is_warp && clock < warp_ready_at {
+            actions.push(RouteAction::Wait {
+                seconds: warp_ready_at - clock,
+            });
+            clock = warp_ready_at;
+        }
+        if is_warp {
+            warp_ready_at = clock + warp_cool_down as u64;
+        }
+
+        let duration = step.duration().ceil() as u64;
+        actions.push(RouteAction::Instruction(instruction));
+        actions.push(RouteAction::Wait { seconds: duration });
+        actions.push(RouteAction::Instruction(
+            FleetStateHandler::new(accounts.fleet_pk, accounts.funder_kp)
+                .set_fuel(
+                    accounts.fuel_tank_pk,
+                    accounts.fuel_cargo_type_pk,
+                    accounts.cargo_stats_definition_pk,
+                    accounts.fuel_mint_pk,
+                )
+                .instruction(),
+        ));
+        clock += duration;
+    }
+
+    actions
+}
@@ -0,0 +1,322 @@
+use anchor_lang::{AccountDeserialize, InstructionData};
+use litesvm::types::FailedTransactionMetadata;
+use solana_sdk::{
+    account::from_account,
+    clock::Clock,
+    instruction::{AccountMeta, Instruction},
+    program_pack::Pack,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::{state::Account as TokenAccount, ID as TOKEN_PROGRAM_ID};
+use std::fmt;
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::ScanForSurveyDataUnits as ixScanForSurveyDataUnits, state::Fleet,
+    typedefs::ScanForSurveyDataUnitsInput, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    cargo::cargo_type_stats,
+    compute_budget::ComputeBudget,
+    sender::Sender,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_survey_data_unit_tracker_signer_pda(tracker_pk: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"SurveyDataUnitTrackerSigner", tracker_pk.as_ref()],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
+#[derive(Debug)]
+pub enum ScanError<E = FailedTransactionMetadata> {
+    /// The fleet scanned too recently, mirrors SAGE's `ScanIsOnCooldown`.
+    ScanIsOnCooldown {
+        expires_at: i64,
+    },
+    /// The fleet account is missing or could not be decoded.
+    InvalidFleet,
+    /// A cargo type account is missing or could not be decoded.
+    InvalidCargoType,
+    Transaction(Box<E>),
+}
+
+impl<E: fmt::Debug> fmt::Display for ScanError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanError::ScanIsOnCooldown { expires_at } => {
+                write!(f, "ScanIsOnCooldown: until {}", expires_at)
+            }
+            ScanError::InvalidFleet => write!(f, "invalid fleet account"),
+            ScanError::InvalidCargoType => write!(f, "invalid cargo type account"),
+            ScanError::Transaction(err) => write!(f, "scan failed: {:?}", err),
+        }
+    }
+}
+
+impl<E: fmt::Debug> std::error::Error for ScanError<E> {}
+
+impl<E> From<E> for ScanError<E> {
+    fn from(err: E) -> Self {
+        ScanError::Transaction(Box::new(err))
+    }
+}
+
+/// Points program accounts credited by a scan.
+pub struct ScanXpAccounts<'a> {
+    pub data_running_user_points_pk: &'a Pubkey,
+    pub data_running_points_category_pk: &'a Pubkey,
+    pub data_running_points_modifier_pk: &'a Pubkey,
+    pub council_rank_user_points_pk: &'a Pubkey,
+    pub council_rank_points_category_pk: &'a Pubkey,
+    pub council_rank_points_modifier_pk: &'a Pubkey,
+    pub progression_config_pk: &'a Pubkey,
+    pub points_program_pk: &'a Pubkey,
+}
+
+/// Scans the fleet's sector for SDUs, spending `resource_mint_pk` (food) from the cargo hold.
+#[derive(Clone, Copy)]
+pub struct ScanForSurveyDataUnits<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    tracker_pk: &'a Pubkey,
+    cargo_hold_pk: &'a Pubkey,
+    sector_pk: &'a Pubkey,
+    sdu_mint_pk: &'a Pubkey,
+    resource_mint_pk: &'a Pubkey,
+    sdu_cargo_type_pk: &'a Pubkey,
+    resource_cargo_type_pk: &'a Pubkey,
+    cargo_stats_definition_pk: &'a Pubkey,
+    xp_accounts: &'a ScanXpAccounts<'a>,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> ScanForSurveyDataUnits<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        tracker_pk: &'a Pubkey,
+        cargo_hold_pk: &'a Pubkey,
+        sector_pk: &'a Pubkey,
+        sdu_mint_pk: &'a Pubkey,
+        resource_mint_pk: &'a Pubkey,
+        sdu_cargo_type_pk: &'a Pubkey,
+        resource_cargo_type_pk: &'a Pubkey,
+        cargo_stats_definition_pk: &'a Pubkey,
+        xp_accounts: &'a ScanXpAccounts<'a>,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        ScanForSurveyDataUnits {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            tracker_pk,
+            cargo_hold_pk,
+            sector_pk,
+            sdu_mint_pk,
+            resource_mint_pk,
+            sdu_cargo_type_pk,
+            resource_cargo_type_pk,
+            cargo_stats_definition_pk,
+            xp_accounts,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let (tracker_signer_pda, _bump) = find_survey_data_unit_tracker_signer_pda(self.tracker_pk);
+        let sdu_token_from = get_associated_token_address(&tracker_signer_pda, self.sdu_mint_pk);
+        let sdu_token_to = get_associated_token_address(self.cargo_hold_pk, self.sdu_mint_pk);
+        let resource_token_from =
+            get_associated_token_address(self.cargo_hold_pk, self.resource_mint_pk);
+        let xp = self.xp_accounts;
+
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // ScanForSurveyDataUnitsGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // ScanForSurveyDataUnitsGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // ScanForSurveyDataUnitsGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // ScanForSurveyDataUnitsGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // ScanForSurveyDataUnitsGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // ScanForSurveyDataUnitsGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.tracker_pk, false), // pub survey_data_unit_tracker: AccountInfo<'info>,
+                AccountMeta::new_readonly(tracker_signer_pda, false), // pub survey_data_unit_tracker_signer: AccountInfo<'info>,
+                AccountMeta::new(*self.cargo_hold_pk, false), // pub cargo_hold: AccountInfo<'info>,
+                AccountMeta::new(*self.sector_pk, false),     // pub sector: AccountInfo<'info>,
+                AccountMeta::new(sdu_token_from, false), // pub sdu_token_from: AccountInfo<'info>,
+                AccountMeta::new(sdu_token_to, false),   // pub sdu_token_to: AccountInfo<'info>,
+                AccountMeta::new(resource_token_from, false), // pub resource_token_from: AccountInfo<'info>,
+                AccountMeta::new(*self.resource_mint_pk, false), // pub resource_mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.sdu_cargo_type_pk, false), // pub sdu_cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.resource_cargo_type_pk, false), // pub resource_cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_stats_definition_pk, false), // pub cargo_stats_definition: AccountInfo<'info>,
+                AccountMeta::new(*xp.data_running_user_points_pk, false), // DataRunningXpAccounts<'info> pub user_points_account: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.data_running_points_category_pk, false), // DataRunningXpAccounts<'info> pub points_category: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.data_running_points_modifier_pk, false), // DataRunningXpAccounts<'info> pub points_modifier_account: AccountInfo<'info>,
+                AccountMeta::new(*xp.council_rank_user_points_pk, false), // CouncilRankXpAccounts<'info> pub user_points_account: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.council_rank_points_category_pk, false), // CouncilRankXpAccounts<'info> pub points_category: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.council_rank_points_modifier_pk, false), // CouncilRankXpAccounts<'info> pub points_modifier_account: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.progression_config_pk, false), // pub progression_config: AccountInfo<'info>,
+                AccountMeta::new_readonly(*xp.points_program_pk, false), // pub points_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // pub token_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(sysvar::instructions::ID, false), // pub instructions_sysvar: AccountInfo<'info>,
+                AccountMeta::new_readonly(sysvar::slot_hashes::ID, false), // pub recent_slothashes: AccountInfo<'info>,
+            ],
+            data: ixScanForSurveyDataUnits {
+                _input: ScanForSurveyDataUnitsInput {
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        }
+    }
+
+    /// Unix timestamp the fleet can scan again, `None` when it can scan now.
+    pub fn cooldown<S: Sender>(&self, sender: &mut S) -> Result<Option<i64>, ScanError<S::Error>> {
+        let expires_at = self.fleet(sender)?.scan_cooldown_expires_at;
+        let now = sender
+            .get_account(&sysvar::clock::ID)?
+            .and_then(|clock_acc| from_account::<Clock, _>(&clock_acc))
+            .map_or(0, |clock| clock.unix_timestamp);
+
+        Ok((expires_at > now).then_some(expires_at))
+    }
+
+    fn fleet<S: Sender>(&self, sender: &mut S) -> Result<Fleet, ScanError<S::Error>> {
+        let fleet_acc = sender
+            .get_account(self.fleet_pk)?
+            .ok_or(ScanError::InvalidFleet)?;
+        Fleet::try_deserialize(&mut &fleet_acc.data[..]).map_err(|_| ScanError::InvalidFleet)
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Refuses to send while the fleet's scan is on cooldown.
+    pub fn send<S: Sender>(self, sender: &mut S) -> Result<(), ScanError<S::Error>> {
+        if let Some(expires_at) = self.cooldown(sender)? {
+            return Err(ScanError::ScanIsOnCooldown { expires_at });
+        }
+
+        InstructionBuilder::send(self, sender)?;
+
+        Ok(())
+    }
+
+    /// SDUs held in the cargo hold.
+    pub fn sdu_amount<S: Sender>(&self, sender: &mut S) -> Result<u64, S::Error> {
+        token_amount(sender, self.cargo_hold_pk, self.sdu_mint_pk)
+    }
+
+    /// Cargo space the SDUs and the resource take up in the cargo hold, in units of the fleet's
+    /// `cargo_capacity`.
+    pub fn cargo_used<S: Sender>(&self, sender: &mut S) -> Result<u64, ScanError<S::Error>> {
+        let sdu_space = cargo_space(sender, self.sdu_cargo_type_pk)?;
+        let resource_space = cargo_space(sender, self.resource_cargo_type_pk)?;
+        let sdus = self.sdu_amount(sender)?;
+        let resources = token_amount(sender, self.cargo_hold_pk, self.resource_mint_pk)?;
+
+        Ok(sdus
+            .saturating_mul(sdu_space)
+            .saturating_add(resources.saturating_mul(resource_space)))
+    }
+
+    /// Scans, waits out the cooldown and repeats until the SDUs and the resource fill the fleet's
+    /// cargo capacity, or `max_scans` scans were sent. See [`Sender::wait_until`] for how a
+    /// sender waits.
+    ///
+    /// Returns the number of scans sent.
+    pub fn scan_loop<S: Sender>(
+        &self,
+        sender: &mut S,
+        max_scans: usize,
+    ) -> Result<usize, ScanError<S::Error>> {
+        let cargo_capacity = u64::from(self.fleet(sender)?.stats.cargo_stats.cargo_capacity);
+
+        let mut scans = 0;
+        while scans < max_scans && self.cargo_used(sender)? < cargo_capacity {
+            let expires_at = self.fleet(sender)?.scan_cooldown_expires_at;
+            sender.wait_until(expires_at)?;
+
+            self.send(sender)?;
+            scans += 1;
+        }
+
+        Ok(scans)
+    }
+}
+
+/// Without the cooldown check of [`ScanForSurveyDataUnits::send`].
+impl<'a> InstructionBuilder<'a> for ScanForSurveyDataUnits<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
+
+fn token_amount<S: Sender>(
+    sender: &mut S,
+    owner_pk: &Pubkey,
+    mint_pk: &Pubkey,
+) -> Result<u64, S::Error> {
+    Ok(sender
+        .get_account(&get_associated_token_address(owner_pk, mint_pk))?
+        .and_then(|acc| TokenAccount::unpack(&acc.data).ok())
+        .map_or(0, |token_account| token_account.amount))
+}
+
+/// Cargo space a unit of the cargo type takes up, its first stat.
+fn cargo_space<S: Sender>(
+    sender: &mut S,
+    cargo_type_pk: &Pubkey,
+) -> Result<u64, ScanError<S::Error>> {
+    sender
+        .get_account(cargo_type_pk)?
+        .and_then(|acc| cargo_type_stats(&acc.data))
+        .and_then(|stats| stats.first().copied())
+        .ok_or(ScanError::InvalidCargoType)
+}
@@ -0,0 +1,107 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+
+use staratlas_sage::{
+    instruction::StartSubwarp as ixStartSubwarp, typedefs::StartSubwarpInput, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct StartSubwarp<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    to_sector: [i64; 2],
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> StartSubwarp<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        to_sector: [i64; 2],
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        StartSubwarp {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            to_sector,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // StartSubwarpGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // StartSubwarpGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // StartSubwarpGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // StartSubwarpGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // StartSubwarpGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // StartSubwarpGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+            ],
+            data: ixStartSubwarp {
+                _input: StartSubwarpInput {
+                    to_sector: self.to_sector,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for StartSubwarp<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
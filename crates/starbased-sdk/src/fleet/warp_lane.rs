@@ -0,0 +1,174 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::WarpLane as ixWarpLane, typedefs::WarpLaneInput, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Warps between two connected sectors with starbases, paying the lane fee in `fee_mint_pk`.
+pub struct WarpLane<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    from_starbase_pk: &'a Pubkey,
+    to_starbase_pk: &'a Pubkey,
+    from_sector_pk: &'a Pubkey,
+    to_sector_pk: &'a Pubkey,
+    fuel_tank_pk: &'a Pubkey,
+    cargo_type_pk: &'a Pubkey,
+    stats_definition_pk: &'a Pubkey,
+    fuel_mint_pk: &'a Pubkey,
+    fee_token_from_pk: &'a Pubkey,
+    fee_token_to_pk: &'a Pubkey,
+    fee_mint_pk: &'a Pubkey,
+    to_sector_index: u16,
+    from_sector_index: u16,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> WarpLane<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        from_starbase_pk: &'a Pubkey,
+        to_starbase_pk: &'a Pubkey,
+        from_sector_pk: &'a Pubkey,
+        to_sector_pk: &'a Pubkey,
+        fuel_tank_pk: &'a Pubkey,
+        cargo_type_pk: &'a Pubkey,
+        stats_definition_pk: &'a Pubkey,
+        fuel_mint_pk: &'a Pubkey,
+        fee_token_from_pk: &'a Pubkey,
+        fee_token_to_pk: &'a Pubkey,
+        fee_mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        WarpLane {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            from_starbase_pk,
+            to_starbase_pk,
+            from_sector_pk,
+            to_sector_pk,
+            fuel_tank_pk,
+            cargo_type_pk,
+            stats_definition_pk,
+            fuel_mint_pk,
+            fee_token_from_pk,
+            fee_token_to_pk,
+            fee_mint_pk,
+            to_sector_index: 0,
+            from_sector_index: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// Index of the to sector in the connections of the from sector.
+    pub fn set_to_sector_index(mut self, to_sector_index: u16) -> Self {
+        self.to_sector_index = to_sector_index;
+        self
+    }
+
+    /// Index of the from sector in the connections of the to sector.
+    pub fn set_from_sector_index(mut self, from_sector_index: u16) -> Self {
+        self.from_sector_index = from_sector_index;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let fuel_token_from = get_associated_token_address(self.fuel_tank_pk, self.fuel_mint_pk);
+
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // WarpLaneGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // WarpLaneGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // WarpLaneGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // WarpLaneGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // WarpLaneGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // WarpLaneGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.from_starbase_pk, false), // pub from_starbase: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.to_starbase_pk, false), // pub to_starbase: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.from_sector_pk, false), // pub from_sector: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.to_sector_pk, false), // pub to_sector: AccountInfo<'info>,
+                AccountMeta::new(*self.fuel_tank_pk, false), // pub fuel_tank: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_type_pk, false), // pub cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.stats_definition_pk, false), // pub stats_definition: AccountInfo<'info>,
+                AccountMeta::new(fuel_token_from, false), // pub fuel_token_from: AccountInfo<'info>,
+                AccountMeta::new(*self.fuel_mint_pk, false), // pub fuel_mint: AccountInfo<'info>,
+                AccountMeta::new(*self.fee_token_from_pk, false), // pub fee_token_from: AccountInfo<'info>,
+                AccountMeta::new(*self.fee_token_to_pk, false), // pub fee_token_to: AccountInfo<'info>,
+                AccountMeta::new(*self.fee_mint_pk, false),     // pub fee_mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // pub token_program: AccountInfo<'info>,
+            ],
+            data: ixWarpLane {
+                _input: WarpLaneInput {
+                    key_index: self.key_index,
+                    to_sector_index: self.to_sector_index,
+                    from_sector_index: self.from_sector_index,
+                },
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for WarpLane<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
@@ -0,0 +1,132 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::WarpToCoordinate as ixWarpToCoordinate, typedefs::WarpToCoordinateInput,
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct WarpToCoordinate<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    fleet_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    fuel_tank_pk: &'a Pubkey,
+    cargo_type_pk: &'a Pubkey,
+    stats_definition_pk: &'a Pubkey,
+    fuel_mint_pk: &'a Pubkey,
+    to_sector: [i64; 2],
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> WarpToCoordinate<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        fleet_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        fuel_tank_pk: &'a Pubkey,
+        cargo_type_pk: &'a Pubkey,
+        stats_definition_pk: &'a Pubkey,
+        fuel_mint_pk: &'a Pubkey,
+        to_sector: [i64; 2],
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        WarpToCoordinate {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            fleet_pk,
+            game_pk,
+            game_state_pk,
+            fuel_tank_pk,
+            cargo_type_pk,
+            stats_definition_pk,
+            fuel_mint_pk,
+            to_sector,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let token_from = get_associated_token_address(self.fuel_tank_pk, self.fuel_mint_pk);
+
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // WarpToCoordinateGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // WarpToCoordinateGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // WarpToCoordinateGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub owning_profile_faction: AccountInfo<'info>,
+                AccountMeta::new(*self.fleet_pk, false), // WarpToCoordinateGameAccountsFleetAndOwnerGameFleetAndOwnerFleetAndOwner<'info> pub fleet: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // WarpToCoordinateGameAccountsFleetAndOwnerGameFleetAndOwner<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // WarpToCoordinateGameAccountsFleetAndOwner<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new(*self.fuel_tank_pk, false), // pub fuel_tank: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_type_pk, false), // pub cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.stats_definition_pk, false), // pub stats_definition: AccountInfo<'info>,
+                AccountMeta::new(token_from, false), // pub token_from: AccountInfo<'info>,
+                AccountMeta::new(*self.fuel_mint_pk, false), // pub token_mint: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // pub token_program: AccountInfo<'info>,
+            ],
+            data: ixWarpToCoordinate {
+                _input: WarpToCoordinateInput {
+                    key_index: self.key_index,
+                    to_sector: self.to_sector,
+                },
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for WarpToCoordinate<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
@@ -0,0 +1,141 @@
+use serde::{Deserialize, Deserializer, Serialize};
+use solana_sdk::pubkey::Pubkey;
+use std::str::FromStr;
+
+/// An item of the Star Atlas galaxy catalog (`data/galaxy.json`).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GalaxyItem {
+    #[serde(rename = "_id")]
+    pub id: String,
+    pub name: String,
+    pub symbol: String,
+    pub mint: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub image: String,
+    #[serde(default)]
+    pub deactivated: bool,
+    #[serde(default)]
+    pub total_supply: Option<u64>,
+    pub attributes: ItemAttributes,
+    #[serde(default)]
+    pub markets: Vec<Market>,
+    #[serde(default)]
+    pub trade_settings: TradeSettings,
+}
+
+impl GalaxyItem {
+    pub fn mint_pubkey(&self) -> Option<Pubkey> {
+        Pubkey::from_str(&self.mint).ok()
+    }
+
+    /// Name with class and spec, e.g. `Pearce X4 (xx-small, fighter)`.
+    pub fn label(&self) -> String {
+        let details: Vec<&str> = [Some(&self.attributes.class), self.attributes.spec.as_ref()]
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|detail| !detail.is_empty())
+            .collect();
+
+        match details.is_empty() {
+            true => self.name.clone(),
+            false => format!("{} ({})", self.name, details.join(", ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemType {
+    Ship,
+    Resource,
+    Structure,
+    Crew,
+    Collectible,
+    Story,
+    Access,
+    Memories,
+    Currency,
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemAttributes {
+    pub item_type: ItemType,
+    /// Size class of ships (e.g. `xx-small`, `capital`), the kind of other items.
+    pub class: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default)]
+    pub rarity: Option<String>,
+    #[serde(default)]
+    pub tier: Option<u8>,
+    /// Ship manufacturer.
+    #[serde(default)]
+    pub make: Option<String>,
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Ship role, e.g. `fighter`, `freighter`.
+    #[serde(default)]
+    pub spec: Option<String>,
+    #[serde(default)]
+    pub unit_height: Option<f64>,
+    #[serde(default)]
+    pub unit_length: Option<f64>,
+    #[serde(default)]
+    pub unit_width: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Market {
+    /// Market address.
+    pub id: String,
+    pub quote_pair: String,
+    #[serde(default)]
+    pub serum_program_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TradeSettings {
+    #[serde(default, deserialize_with = "optional_timestamp")]
+    pub sale_time: Option<i64>,
+    #[serde(default, deserialize_with = "optional_timestamp")]
+    pub expire_time: Option<i64>,
+    #[serde(default)]
+    pub msrp: Option<Msrp>,
+    #[serde(default)]
+    pub vwap: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Msrp {
+    pub currency_symbol: String,
+    pub value: f64,
+}
+
+/// Timestamps are numbers, or empty strings when unset.
+fn optional_timestamp<'de, D>(deserializer: D) -> Result<Option<i64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Number(i64),
+        Text(String),
+    }
+
+    match Option::<Timestamp>::deserialize(deserializer)? {
+        Some(Timestamp::Number(timestamp)) => Ok(Some(timestamp)),
+        Some(Timestamp::Text(text)) => Ok(text.parse().ok()),
+        None => Ok(None),
+    }
+}
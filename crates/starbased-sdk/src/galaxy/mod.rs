@@ -0,0 +1,134 @@
+//! Typed Star Atlas galaxy catalog (`data/galaxy.json`), joined with SAGE `Ship` and
+//! `MineItem` accounts by mint.
+//!
+//! ```ignore
+//! let galaxy = Galaxy::from_path("data/galaxy.json")?;
+//! let label = galaxy.ship_label(&ship); // "Pearce X4 (xx-small, fighter)"
+//! ```
+mod catalog;
+
+pub use catalog::*;
+
+use anchor_lang::{AccountDeserialize, Discriminator};
+use solana_sdk::pubkey::Pubkey;
+use std::{collections::HashMap, fmt, fs, path::Path};
+
+use staratlas_sage::state::{MineItem, Ship};
+
+use crate::snapshot::Snapshot;
+
+#[derive(Debug)]
+pub enum GalaxyError {
+    /// The catalog file could not be read.
+    Io(std::io::Error),
+    /// The catalog could not be parsed.
+    Parse(String),
+}
+
+impl fmt::Display for GalaxyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GalaxyError::Io(err) => write!(f, "failed to read galaxy: {}", err),
+            GalaxyError::Parse(err) => write!(f, "failed to parse galaxy: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for GalaxyError {}
+
+impl From<std::io::Error> for GalaxyError {
+    fn from(err: std::io::Error) -> Self {
+        GalaxyError::Io(err)
+    }
+}
+
+/// The galaxy catalog indexed by mint.
+#[derive(Debug, Clone, Default)]
+pub struct Galaxy {
+    pub items: Vec<GalaxyItem>,
+    by_mint: HashMap<Pubkey, usize>,
+}
+
+impl Galaxy {
+    pub fn new(items: Vec<GalaxyItem>) -> Self {
+        let by_mint = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| item.mint_pubkey().map(|mint| (mint, index)))
+            .collect();
+
+        Galaxy { items, by_mint }
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, GalaxyError> {
+        serde_json::from_str(s)
+            .map(Self::new)
+            .map_err(|err| GalaxyError::Parse(err.to_string()))
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, GalaxyError> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_json_str(&contents)
+    }
+
+    pub fn item(&self, mint: &Pubkey) -> Option<&GalaxyItem> {
+        self.by_mint.get(mint).map(|index| &self.items[*index])
+    }
+
+    pub fn items_of(&self, item_type: ItemType) -> impl Iterator<Item = &GalaxyItem> {
+        self.items
+            .iter()
+            .filter(move |item| item.attributes.item_type == item_type)
+    }
+
+    /// Catalog entry of the ship's mint.
+    pub fn ship(&self, ship: &Ship) -> Option<&GalaxyItem> {
+        self.item(&Pubkey::new_from_array(ship.mint.to_bytes()))
+    }
+
+    /// Catalog entry of the mine item's mint.
+    pub fn mine_item(&self, mine_item: &MineItem) -> Option<&GalaxyItem> {
+        self.item(&Pubkey::new_from_array(mine_item.mint.to_bytes()))
+    }
+
+    /// The catalog label of the mint, or the mint address when it is not in the catalog.
+    pub fn label(&self, mint: &Pubkey) -> String {
+        self.item(mint)
+            .map(GalaxyItem::label)
+            .unwrap_or_else(|| mint.to_string())
+    }
+
+    pub fn ship_label(&self, ship: &Ship) -> String {
+        self.label(&Pubkey::new_from_array(ship.mint.to_bytes()))
+    }
+
+    pub fn mine_item_label(&self, mine_item: &MineItem) -> String {
+        self.label(&Pubkey::new_from_array(mine_item.mint.to_bytes()))
+    }
+
+    /// Catalog entries of the snapshot's `Ship` and `MineItem` accounts, by account address.
+    pub fn join(&self, snapshot: &Snapshot) -> HashMap<Pubkey, &GalaxyItem> {
+        let mut joined = HashMap::new();
+        for (discriminator, accounts) in snapshot.accounts.iter() {
+            for (pubkey, account) in accounts.iter() {
+                let item = if discriminator.as_slice() == Ship::DISCRIMINATOR {
+                    Ship::try_deserialize(&mut &account.data[..])
+                        .ok()
+                        .and_then(|ship| self.ship(&ship))
+                } else if discriminator.as_slice() == MineItem::DISCRIMINATOR {
+                    MineItem::try_deserialize(&mut &account.data[..])
+                        .ok()
+                        .and_then(|mine_item| self.mine_item(&mine_item))
+                } else {
+                    None
+                };
+
+                if let Some(item) = item {
+                    joined.insert(*pubkey, item);
+                }
+            }
+        }
+
+        joined
+    }
+}
@@ -0,0 +1,130 @@
+//! Typed instructions of transaction data.
+//!
+//! Each program crate has an IDL generated `ix_decode` (e.g. [`staratlas_sage::ix_decode`]) that
+//! turns a program id, the instruction's account keys and its data into an enum variant with
+//! named accounts and decoded arguments. [`decode_instruction`] dispatches over all Star Atlas
+//! programs, [`decode_message`] over the top-level instructions of a transaction message.
+//!
+//! ```ignore
+//! for (index, ix) in decode_message(&tx.message, None) {
+//!     if let StarAtlasInstruction::Sage(sage::Instruction::StartSubwarp { accounts, args, .. }) = ix {
+//!         println!("#{} fleet {} to {:?}", index, accounts.fleet, args.input.to_sector);
+//!     }
+//! }
+//! ```
+use solana_sdk::{
+    instruction::Instruction,
+    message::{v0::LoadedAddresses, AccountKeys, VersionedMessage},
+    pubkey::Pubkey,
+};
+
+use crate::error::Program;
+
+pub use staratlas_cargo::ix_decode as cargo;
+pub use staratlas_crew::ix_decode as crew;
+pub use staratlas_fleet_rentals::ix_decode as fleet_rentals;
+pub use staratlas_player_profile::ix_decode as player_profile;
+pub use staratlas_profile_faction::ix_decode as profile_faction;
+pub use staratlas_sage::ix_decode as sage;
+
+#[derive(Debug)]
+pub enum StarAtlasInstruction {
+    Sage(sage::Instruction),
+    Cargo(cargo::Instruction),
+    Crew(crew::Instruction),
+    PlayerProfile(player_profile::Instruction),
+    ProfileFaction(profile_faction::Instruction),
+    FleetRentals(fleet_rentals::Instruction),
+}
+
+impl StarAtlasInstruction {
+    pub fn program(&self) -> Program {
+        match self {
+            StarAtlasInstruction::Sage(_) => Program::Sage,
+            StarAtlasInstruction::Cargo(_) => Program::Cargo,
+            StarAtlasInstruction::Crew(_) => Program::Crew,
+            StarAtlasInstruction::PlayerProfile(_) => Program::PlayerProfile,
+            StarAtlasInstruction::ProfileFaction(_) => Program::ProfileFaction,
+            StarAtlasInstruction::FleetRentals(_) => Program::FleetRentals,
+        }
+    }
+
+    /// The instruction name from the IDL, e.g. `StartSubwarp`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StarAtlasInstruction::Sage(ix) => ix.name(),
+            StarAtlasInstruction::Cargo(ix) => ix.name(),
+            StarAtlasInstruction::Crew(ix) => ix.name(),
+            StarAtlasInstruction::PlayerProfile(ix) => ix.name(),
+            StarAtlasInstruction::ProfileFaction(ix) => ix.name(),
+            StarAtlasInstruction::FleetRentals(ix) => ix.name(),
+        }
+    }
+}
+
+/// `None` when `program_id` is not a Star Atlas program or the instruction doesn't decode.
+pub fn decode_instruction(
+    program_id: &Pubkey,
+    account_keys: &[Pubkey],
+    data: &[u8],
+) -> Option<StarAtlasInstruction> {
+    let program = Program::from_id(program_id)?;
+    let program_id = anchor_lang::prelude::Pubkey::new_from_array(program_id.to_bytes());
+    let account_keys: Vec<_> = account_keys
+        .iter()
+        .map(|key| anchor_lang::prelude::Pubkey::new_from_array(key.to_bytes()))
+        .collect();
+
+    let ix = match program {
+        Program::Sage => {
+            StarAtlasInstruction::Sage(sage::decode(&program_id, &account_keys, data).ok()?)
+        }
+        Program::Cargo => {
+            StarAtlasInstruction::Cargo(cargo::decode(&program_id, &account_keys, data).ok()?)
+        }
+        Program::Crew => {
+            StarAtlasInstruction::Crew(crew::decode(&program_id, &account_keys, data).ok()?)
+        }
+        Program::PlayerProfile => StarAtlasInstruction::PlayerProfile(
+            player_profile::decode(&program_id, &account_keys, data).ok()?,
+        ),
+        Program::ProfileFaction => StarAtlasInstruction::ProfileFaction(
+            profile_faction::decode(&program_id, &account_keys, data).ok()?,
+        ),
+        Program::FleetRentals => StarAtlasInstruction::FleetRentals(
+            fleet_rentals::decode(&program_id, &account_keys, data).ok()?,
+        ),
+    };
+    Some(ix)
+}
+
+pub fn decode(ix: &Instruction) -> Option<StarAtlasInstruction> {
+    let account_keys: Vec<_> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+    decode_instruction(&ix.program_id, &account_keys, &ix.data)
+}
+
+/// The decoded top-level instructions of `message` with their index.
+///
+/// `loaded_addresses` are the accounts a v0 message loads from its address lookup tables, writable
+/// ones first, e.g. from the transaction status meta. Without them, instructions using loaded
+/// accounts are skipped.
+pub fn decode_message(
+    message: &VersionedMessage,
+    loaded_addresses: Option<&LoadedAddresses>,
+) -> Vec<(usize, StarAtlasInstruction)> {
+    let keys = AccountKeys::new(message.static_account_keys(), loaded_addresses);
+    message
+        .instructions()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, ix)| {
+            let program_id = keys.get(ix.program_id_index as usize)?;
+            let account_keys = ix
+                .accounts
+                .iter()
+                .map(|account| keys.get(*account as usize).copied())
+                .collect::<Option<Vec<_>>>()?;
+            decode_instruction(program_id, &account_keys, &ix.data).map(|ix| (index, ix))
+        })
+        .collect()
+}
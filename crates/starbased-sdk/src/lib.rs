@@ -1,2 +1,21 @@
 pub mod admin;
+pub mod batch;
+pub mod cargo;
+pub mod compute_budget;
+pub mod error;
+pub mod fleet;
+pub mod galaxy;
+pub mod ix_decode;
+pub mod lookup_table;
+pub mod player;
 pub mod profile;
+pub mod sender;
+pub mod simulation;
+pub mod snapshot;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod token;
+pub mod tx;
+pub mod world;
+
+pub use staratlas_profile_faction::typedefs::Faction;
@@ -0,0 +1,191 @@
+//! Address lookup tables for account-heavy instructions.
+//!
+//! A table is derived from a recent slot and filled in chunks, addresses added in a slot are only
+//! usable from the next one. A table holds up to [`LOOKUP_TABLE_MAX_ADDRESSES`].
+//! [`LookupTableCache`] keeps fetched tables and reuses a table holding all the addresses of a
+//! transaction.
+//!
+//! ```ignore
+//! let tx_builder = TxBuilder::new(&funder_kp).add(builder);
+//! let mut cache = LookupTableCache::new();
+//! let addresses = lookup_addresses(tx_builder.instructions());
+//! let tables = cache.ensure(&mut svm, &authority_kp, &funder_kp, &addresses)?;
+//! tx_builder.send_with_lookup_tables(&mut svm, &tables)?;
+//! ```
+use solana_sdk::{
+    address_lookup_table::{
+        instruction,
+        state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+    },
+    instruction::Instruction,
+    message::AddressLookupTableAccount,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use std::collections::HashMap;
+
+use crate::{sender::Sender, tx::TxBuilder};
+
+/// Addresses added per `ExtendLookupTable` transaction.
+pub const MAX_EXTEND_ADDRESSES: usize = 30;
+
+/// Creates a lookup table owned by `authority_kp`.
+pub fn create_lookup_table<S: Sender>(
+    sender: &mut S,
+    authority_kp: &Keypair,
+    payer_kp: &Keypair,
+) -> Result<Pubkey, S::Error> {
+    let recent_slot = sender.recent_slot()?;
+    let (ix, lookup_table_pk) =
+        instruction::create_lookup_table(authority_kp.pubkey(), payer_kp.pubkey(), recent_slot);
+
+    TxBuilder::new(payer_kp)
+        .add_instructions(vec![ix], vec![authority_kp.into()], vec![])
+        .send(sender)?;
+
+    Ok(lookup_table_pk)
+}
+
+/// Adds `addresses` in chunks of [`MAX_EXTEND_ADDRESSES`] and waits for them to become usable.
+/// Extending past [`LOOKUP_TABLE_MAX_ADDRESSES`] fails.
+pub fn extend_lookup_table<S: Sender>(
+    sender: &mut S,
+    lookup_table_pk: &Pubkey,
+    authority_kp: &Keypair,
+    payer_kp: &Keypair,
+    addresses: &[Pubkey],
+) -> Result<(), S::Error> {
+    for chunk in addresses.chunks(MAX_EXTEND_ADDRESSES) {
+        let ix = instruction::extend_lookup_table(
+            *lookup_table_pk,
+            authority_kp.pubkey(),
+            Some(payer_kp.pubkey()),
+            chunk.to_vec(),
+        );
+        TxBuilder::new(payer_kp)
+            .add_instructions(vec![ix], vec![authority_kp.into()], vec![])
+            .send(sender)?;
+    }
+
+    sender.wait_for_next_slot()
+}
+
+/// `None` when the account doesn't exist or isn't a lookup table.
+pub fn fetch_lookup_table<S: Sender>(
+    sender: &mut S,
+    lookup_table_pk: &Pubkey,
+) -> Result<Option<AddressLookupTableAccount>, S::Error> {
+    let lookup_table = sender.get_account(lookup_table_pk)?.and_then(|account| {
+        AddressLookupTable::deserialize(&account.data)
+            .map(|table| AddressLookupTableAccount {
+                key: *lookup_table_pk,
+                addresses: table.addresses.to_vec(),
+            })
+            .ok()
+    });
+
+    Ok(lookup_table)
+}
+
+/// Accounts of `instructions` that can be loaded from a lookup table: neither signers nor
+/// invoked programs, deduplicated in order.
+pub fn lookup_addresses(instructions: &[Instruction]) -> Vec<Pubkey> {
+    let excluded: Vec<Pubkey> = instructions
+        .iter()
+        .flat_map(|ix| {
+            ix.accounts
+                .iter()
+                .filter(|account| account.is_signer)
+                .map(|account| account.pubkey)
+                .chain([ix.program_id])
+        })
+        .collect();
+
+    let mut addresses: Vec<Pubkey> = vec![];
+    for account in instructions.iter().flat_map(|ix| &ix.accounts) {
+        if !excluded.contains(&account.pubkey) && !addresses.contains(&account.pubkey) {
+            addresses.push(account.pubkey);
+        }
+    }
+    addresses
+}
+
+/// Fetched lookup tables by address.
+#[derive(Debug, Default)]
+pub struct LookupTableCache {
+    tables: HashMap<Pubkey, AddressLookupTableAccount>,
+}
+
+impl LookupTableCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn get(&self, lookup_table_pk: &Pubkey) -> Option<&AddressLookupTableAccount> {
+        self.tables.get(lookup_table_pk)
+    }
+
+    pub fn insert(&mut self, lookup_table: AddressLookupTableAccount) {
+        self.tables.insert(lookup_table.key, lookup_table);
+    }
+
+    /// Forgets a table, e.g. after extending it outside of the cache.
+    pub fn invalidate(&mut self, lookup_table_pk: &Pubkey) {
+        self.tables.remove(lookup_table_pk);
+    }
+
+    /// The cached table, fetched on a miss.
+    pub fn get_or_fetch<S: Sender>(
+        &mut self,
+        sender: &mut S,
+        lookup_table_pk: &Pubkey,
+    ) -> Result<Option<AddressLookupTableAccount>, S::Error> {
+        if let Some(lookup_table) = self.tables.get(lookup_table_pk) {
+            return Ok(Some(lookup_table.clone()));
+        }
+
+        let lookup_table = fetch_lookup_table(sender, lookup_table_pk)?;
+        if let Some(lookup_table) = &lookup_table {
+            self.insert(lookup_table.clone());
+        }
+        Ok(lookup_table)
+    }
+
+    /// A cached table holding all of `addresses`.
+    pub fn find(&self, addresses: &[Pubkey]) -> Option<&AddressLookupTableAccount> {
+        self.tables.values().find(|lookup_table| {
+            addresses
+                .iter()
+                .all(|address| lookup_table.addresses.contains(address))
+        })
+    }
+
+    /// A cached table holding all of `addresses`, or new tables created and extended with them,
+    /// [`LOOKUP_TABLE_MAX_ADDRESSES`] per table.
+    pub fn ensure<S: Sender>(
+        &mut self,
+        sender: &mut S,
+        authority_kp: &Keypair,
+        payer_kp: &Keypair,
+        addresses: &[Pubkey],
+    ) -> Result<Vec<AddressLookupTableAccount>, S::Error> {
+        if let Some(lookup_table) = self.find(addresses) {
+            return Ok(vec![lookup_table.clone()]);
+        }
+
+        let mut lookup_tables = vec![];
+        for chunk in addresses.chunks(LOOKUP_TABLE_MAX_ADDRESSES) {
+            let lookup_table_pk = create_lookup_table(sender, authority_kp, payer_kp)?;
+            extend_lookup_table(sender, &lookup_table_pk, authority_kp, payer_kp, chunk)?;
+
+            let lookup_table = AddressLookupTableAccount {
+                key: lookup_table_pk,
+                addresses: chunk.to_vec(),
+            };
+            self.insert(lookup_table.clone());
+            lookup_tables.push(lookup_table);
+        }
+        Ok(lookup_tables)
+    }
+}
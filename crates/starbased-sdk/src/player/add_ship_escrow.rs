@@ -0,0 +1,174 @@
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use litesvm::LiteSVM;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+use staratlas_sage::{
+    instruction::AddShipEscrow as ixAddShipEscrow,
+    typedefs::{AddShipEscrowInput, WrappedShipEscrow},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Size of the fixed `StarbasePlayer` data (discriminator included), ship escrows follow it.
+const STARBASE_PLAYER_SIZE: usize = 8 + 1 + 32 + 32 + 32 + 32 + 1 + 4 + 4 + 4 + 8 + 8 + 4;
+
+/// Ship escrow token account of a starbase player, the ATA of the starbase player for the ship mint.
+pub fn find_ship_escrow_token_account(
+    starbase_player_pk: &Pubkey,
+    ship_mint_pk: &Pubkey,
+) -> Pubkey {
+    get_associated_token_address(starbase_player_pk, ship_mint_pk)
+}
+
+/// Reads the `WrappedShipEscrow`s stored after the `StarbasePlayer` account data.
+pub fn get_ship_escrows(svm: &LiteSVM, starbase_player_pk: &Pubkey) -> Vec<WrappedShipEscrow> {
+    let Some(acc) = svm.get_account(starbase_player_pk) else {
+        return Vec::new();
+    };
+
+    let mut data = acc.data.get(STARBASE_PLAYER_SIZE..).unwrap_or_default();
+    let mut escrows = Vec::new();
+    while !data.is_empty() {
+        match WrappedShipEscrow::deserialize(&mut data) {
+            Ok(escrow) => escrows.push(escrow),
+            Err(_) => break,
+        }
+    }
+
+    escrows
+}
+
+pub struct AddShipEscrow<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    sage_player_profile_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    ship_pk: &'a Pubkey,
+    ship_mint_pk: &'a Pubkey,
+    origin_token_account_pk: &'a Pubkey,
+    ship_amount: u64,
+    index: Option<u32>,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> AddShipEscrow<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        sage_player_profile_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        ship_pk: &'a Pubkey,
+        ship_mint_pk: &'a Pubkey,
+        origin_token_account_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        AddShipEscrow {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            game_pk,
+            game_state_pk,
+            sage_player_profile_pk,
+            starbase_pk,
+            starbase_player_pk,
+            ship_pk,
+            ship_mint_pk,
+            origin_token_account_pk,
+            ship_amount: 1,
+            index: None,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_ship_amount(mut self, ship_amount: u64) -> Self {
+        self.ship_amount = ship_amount;
+        self
+    }
+
+    /// Index of an existing escrow for the ship, `None` creates a new escrow.
+    pub fn set_index(mut self, index: Option<u32>) -> Self {
+        self.index = index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for AddShipEscrow<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let key_pk = self.key_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let ship_escrow_token_account_pk =
+            find_ship_escrow_token_account(self.starbase_player_pk, self.ship_mint_pk);
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(funder_pk, true), // pub funder: Signer<'info>,
+                AccountMeta::new(*self.sage_player_profile_pk, false), // pub sage_player_profile: AccountInfo<'info>,
+                AccountMeta::new(*self.origin_token_account_pk, false), // pub origin_token_account: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.ship_pk, false), // pub ship: AccountInfo<'info>,
+                AccountMeta::new(ship_escrow_token_account_pk, false), // pub ship_escrow_token_account: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // AddShipEscrowStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new(*self.starbase_player_pk, false), // AddShipEscrowStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new_readonly(key_pk, true), // AddShipEscrowGameAccountsAndProfileGameAndProfileAndFaction<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // AddShipEscrowGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // AddShipEscrowGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile_faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // AddShipEscrowGameAccountsAndProfileGameAndProfileAndFaction<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // AddShipEscrowGameAccountsAndProfile<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // pub token_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixAddShipEscrow {
+                _input: AddShipEscrowInput {
+                    ship_amount: self.ship_amount,
+                    index: self.index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![ship_escrow_token_account_pk],
+        )
+    }
+}
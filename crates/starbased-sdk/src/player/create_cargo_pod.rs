@@ -0,0 +1,122 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::CreateCargoPod as ixCreateCargoPod, typedefs::StarbaseCreateCargoPodInput,
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    fleet::find_cargo_pod_pda,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Creates a cargo pod of the starbase player, it holds the player's cargo at the starbase.
+pub struct CreateCargoPod<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    cargo_stats_definition_pk: &'a Pubkey,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> CreateCargoPod<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        cargo_stats_definition_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        CreateCargoPod {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            game_pk,
+            game_state_pk,
+            starbase_pk,
+            starbase_player_pk,
+            cargo_stats_definition_pk,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for CreateCargoPod<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let pod_seeds = Keypair::new().pubkey().to_bytes();
+        let (cargo_pod_pda, _bump) = find_cargo_pod_pda(&pod_seeds);
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(self.funder_kp.pubkey(), true), // pub funder: Signer<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // CreateCargoPodStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_player_pk, false), // CreateCargoPodStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new(cargo_pod_pda, false), // pub cargo_pod: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_stats_definition_pk, false), // pub cargo_stats_definition: AccountInfo<'info>,
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // CreateCargoPodGameAccountsAndProfileGameAndProfileAndFaction<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // CreateCargoPodGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // CreateCargoPodGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile_faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // CreateCargoPodGameAccountsAndProfileGameAndProfileAndFaction<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // CreateCargoPodGameAccountsAndProfile<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixCreateCargoPod {
+                _input: StarbaseCreateCargoPodInput {
+                    pod_seeds,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![cargo_pod_pda],
+        )
+    }
+}
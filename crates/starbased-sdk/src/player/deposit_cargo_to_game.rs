@@ -0,0 +1,143 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::ID as TOKEN_PROGRAM_ID;
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    instruction::DepositCargoToGame as ixDepositCargoToGame, typedefs::CargoToGameInput,
+    ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+/// Moves tokens of `mint_pk` from the key's token account into the player's starbase cargo pod,
+/// the pod needs a token account for the mint.
+pub struct DepositCargoToGame<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_player_pk: &'a Pubkey,
+    cargo_pod_pk: &'a Pubkey,
+    cargo_type_pk: &'a Pubkey,
+    cargo_stats_definition_pk: &'a Pubkey,
+    mint_pk: &'a Pubkey,
+    amount: u64,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> DepositCargoToGame<'a> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        starbase_player_pk: &'a Pubkey,
+        cargo_pod_pk: &'a Pubkey,
+        cargo_type_pk: &'a Pubkey,
+        cargo_stats_definition_pk: &'a Pubkey,
+        mint_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        DepositCargoToGame {
+            key_kp,
+            profile_pk,
+            profile_faction_pk,
+            game_pk,
+            game_state_pk,
+            starbase_pk,
+            starbase_player_pk,
+            cargo_pod_pk,
+            cargo_type_pk,
+            cargo_stats_definition_pk,
+            mint_pk,
+            amount: 0,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_amount(mut self, amount: u64) -> Self {
+        self.amount = amount;
+        self
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn instruction(&self) -> Instruction {
+        let token_from = get_associated_token_address(&self.key_kp.pubkey(), self.mint_pk);
+        let token_to = get_associated_token_address(self.cargo_pod_pk, self.mint_pk);
+
+        Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(*self.starbase_pk, false), // DepositCargoToGameStarbaseAndStarbasePlayer<'info> pub starbase: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_player_pk, false), // DepositCargoToGameStarbaseAndStarbasePlayer<'info> pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new(*self.cargo_pod_pk, false), // pub cargo_pod: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_type_pk, false), // pub cargo_type: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.cargo_stats_definition_pk, false), // pub cargo_stats_definition: AccountInfo<'info>,
+                AccountMeta::new_readonly(self.key_kp.pubkey(), true), // DepositCargoToGameGameAccountsAndProfileGameAndProfileAndFaction<'info> pub key: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // DepositCargoToGameGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // DepositCargoToGameGameAccountsAndProfileGameAndProfileAndFaction<'info> pub profile_faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // DepositCargoToGameGameAccountsAndProfileGameAndProfileAndFaction<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // DepositCargoToGameGameAccountsAndProfile<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new(token_from, false), // pub token_from: AccountInfo<'info>,
+                AccountMeta::new(token_to, false),   // pub token_to: AccountInfo<'info>,
+                AccountMeta::new_readonly(CARGO_PROGRAM_ID, false), // pub cargo_program: AccountInfo<'info>,
+                AccountMeta::new_readonly(TOKEN_PROGRAM_ID, false), // pub token_program: AccountInfo<'info>,
+            ],
+            data: ixDepositCargoToGame {
+                _input: CargoToGameInput {
+                    amount: self.amount,
+                    key_index: self.key_index,
+                },
+            }
+            .data(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for DepositCargoToGame<'a> {
+    type Output = ();
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        (
+            vec![self.instruction()],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![],
+        )
+    }
+}
@@ -0,0 +1,11 @@
+mod add_ship_escrow;
+mod create_cargo_pod;
+mod deposit_cargo_to_game;
+mod register_sage_player_profile;
+mod register_starbase_player;
+
+pub use add_ship_escrow::*;
+pub use create_cargo_pod::*;
+pub use deposit_cargo_to_game::*;
+pub use register_sage_player_profile::*;
+pub use register_starbase_player::*;
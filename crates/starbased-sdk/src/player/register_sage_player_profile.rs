@@ -0,0 +1,96 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterSagePlayerProfile as ixRegisterSagePlayerProfile, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_sage_player_profile_pda(profile_pk: &Pubkey, game_pk: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"sage_player_profile",
+            profile_pk.as_ref(),
+            game_pk.as_ref(),
+        ],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
+pub struct RegisterSagePlayerProfile<'a> {
+    profile_pk: &'a Pubkey,
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterSagePlayerProfile<'a> {
+    pub fn new(
+        profile_pk: &'a Pubkey,
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterSagePlayerProfile {
+            profile_pk,
+            game_pk,
+            game_state_pk,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterSagePlayerProfile<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (sage_player_profile_pda, _bump) =
+            find_sage_player_profile_pda(self.profile_pk, self.game_pk);
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(*self.profile_pk, false), // pub profile: AccountInfo<'info>,
+                AccountMeta::new(funder_pk, true),                  // pub funder: Signer<'info>,
+                AccountMeta::new(sage_player_profile_pda, false), // pub sage_player_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterSagePlayerProfileGameAccounts<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // RegisterSagePlayerProfileGameAccounts<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterSagePlayerProfile {}.data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.funder_kp.into()],
+            vec![sage_player_profile_pda],
+        )
+    }
+}
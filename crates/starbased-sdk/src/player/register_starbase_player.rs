@@ -0,0 +1,119 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_sage::{
+    instruction::RegisterStarbasePlayer as ixRegisterStarbasePlayer, ID as SAGE_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub fn find_starbase_player_pda(
+    starbase_pk: &Pubkey,
+    sage_player_profile_pk: &Pubkey,
+    starbase_seq_id: u16,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            b"starbase_player",
+            starbase_pk.as_ref(),
+            sage_player_profile_pk.as_ref(),
+            &starbase_seq_id.to_le_bytes(),
+        ],
+        &SAGE_PROGRAM_ID,
+    )
+}
+
+pub struct RegisterStarbasePlayer<'a> {
+    game_pk: &'a Pubkey,
+    game_state_pk: &'a Pubkey,
+    sage_player_profile_pk: &'a Pubkey,
+    profile_faction_pk: &'a Pubkey,
+    starbase_pk: &'a Pubkey,
+    starbase_seq_id: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> RegisterStarbasePlayer<'a> {
+    pub fn new(
+        game_pk: &'a Pubkey,
+        game_state_pk: &'a Pubkey,
+        sage_player_profile_pk: &'a Pubkey,
+        profile_faction_pk: &'a Pubkey,
+        starbase_pk: &'a Pubkey,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        RegisterStarbasePlayer {
+            game_pk,
+            game_state_pk,
+            sage_player_profile_pk,
+            profile_faction_pk,
+            starbase_pk,
+            starbase_seq_id: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_starbase_seq_id(mut self, starbase_seq_id: u16) -> Self {
+        self.starbase_seq_id = starbase_seq_id;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for RegisterStarbasePlayer<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (starbase_player_pda, _bump) = find_starbase_player_pda(
+            self.starbase_pk,
+            self.sage_player_profile_pk,
+            self.starbase_seq_id,
+        );
+
+        let ix = Instruction {
+            program_id: SAGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(funder_pk, true), // pub funder: Signer<'info>,
+                AccountMeta::new_readonly(*self.game_pk, false), // RegisterStarbasePlayerGameAccounts<'info> pub game_id: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.game_state_pk, false), // RegisterStarbasePlayerGameAccounts<'info> pub game_state: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.sage_player_profile_pk, false), // pub sage_player_profile: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.profile_faction_pk, false), // pub profile_faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(*self.starbase_pk, false), // pub starbase: AccountInfo<'info>,
+                AccountMeta::new(starbase_player_pda, false), // pub starbase_player: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixRegisterStarbasePlayer {}.data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.funder_kp.into()],
+            vec![starbase_player_pda],
+        )
+    }
+}
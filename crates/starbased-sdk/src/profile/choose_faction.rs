@@ -0,0 +1,96 @@
+use anchor_lang::InstructionData;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    system_program,
+};
+
+use staratlas_profile_faction::{
+    instruction::ChooseFaction as ixChooseFaction, state::ProfileFactionAccount, typedefs::Faction,
+    ID as PROFILE_FACTION_PROGRAM_ID,
+};
+
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
+pub struct ChooseFaction<'a> {
+    key_kp: &'a Keypair,
+    profile_pk: &'a Pubkey,
+    faction: Faction,
+    key_index: u16,
+    funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> ChooseFaction<'a> {
+    pub fn new(
+        key_kp: &'a Keypair,
+        profile_pk: &'a Pubkey,
+        faction: Faction,
+        funder_kp: &'a Keypair,
+    ) -> Self {
+        ChooseFaction {
+            key_kp,
+            profile_pk,
+            faction,
+            key_index: 0,
+            funder_kp,
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    pub fn set_profile_key_index(mut self, key_index: u16) -> Self {
+        self.key_index = key_index;
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for ChooseFaction<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
+        let key_pk = self.key_kp.pubkey();
+        let funder_pk = self.funder_kp.pubkey();
+
+        let (profile_faction_pda, _bump) = ProfileFactionAccount::find_address(self.profile_pk);
+
+        let ix = Instruction {
+            program_id: PROFILE_FACTION_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new_readonly(key_pk, true), // pub key: Signer<'info>,
+                AccountMeta::new(funder_pk, true),       // pub funder: Signer<'info>,
+                AccountMeta::new_readonly(*self.profile_pk, false), // pub profile: AccountInfo<'info>,
+                AccountMeta::new(profile_faction_pda, false), // pub faction: AccountInfo<'info>,
+                AccountMeta::new_readonly(system_program::ID, false), // pub system_program: AccountInfo<'info>,
+            ],
+            data: ixChooseFaction {
+                _key_index: self.key_index,
+                _faction: self.faction,
+            }
+            .data(),
+        };
+
+        (
+            vec![ix],
+            vec![self.key_kp.into(), self.funder_kp.into()],
+            vec![profile_faction_pda],
+        )
+    }
+}
@@ -1,12 +1,10 @@
 use anchor_lang::{prelude::Pubkey as AnchorPubkey, InstructionData};
-use litesvm::{types::FailedTransactionMetadata, LiteSVM};
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
     pubkey::Pubkey,
     signature::Keypair,
     signer::Signer,
     system_program,
-    transaction::Transaction,
 };
 
 // use staratlas_sage::{instruction::InitGame, ID as SAGE_PROGRAM_ID};
@@ -15,54 +13,98 @@ use staratlas_player_profile::{
     ID as PLAYER_PROFILE_PROGRAM_ID,
 };
 
+use crate::{
+    compute_budget::ComputeBudget,
+    tx::{InstructionBuilder, TxSigner},
+};
+
 pub struct CreateProfile<'a> {
     profile_kp: &'a Keypair,
+    keys: Vec<(&'a Keypair, Pubkey, [u8; 8])>,
     funder_kp: &'a Keypair,
+    compute_budget: ComputeBudget,
 }
 
 impl<'a> CreateProfile<'a> {
     pub fn new(profile_kp: &'a Keypair, funder_kp: &'a Keypair) -> Self {
         CreateProfile {
             profile_kp,
+            keys: Vec::new(),
             funder_kp,
+            compute_budget: ComputeBudget::default(),
         }
     }
 
-    pub fn send(self, svm: &mut LiteSVM) -> Result<Pubkey, FailedTransactionMetadata> {
+    /// Adds a key to the profile, the first key added is the auth key (index 0).
+    ///
+    /// When no keys are added the profile keypair is used as the auth key.
+    pub fn add_key(mut self, key_kp: &'a Keypair, scope: Pubkey, permissions: [u8; 8]) -> Self {
+        self.keys.push((key_kp, scope, permissions));
+        self
+    }
+
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+}
+
+impl<'a> InstructionBuilder<'a> for CreateProfile<'a> {
+    type Output = Pubkey;
+
+    fn payer(&self) -> &'a Keypair {
+        self.funder_kp
+    }
+
+    fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>) {
         let funder_pk = self.funder_kp.pubkey();
         let profile_pk = self.profile_kp.pubkey();
 
+        let keys = if self.keys.is_empty() {
+            // Auth Authority - Full permissions
+            vec![(
+                self.profile_kp,
+                PLAYER_PROFILE_PROGRAM_ID,
+                [0xFF, 0xFF, 0, 0, 0, 0, 0, 0], // All permissions enabled
+            )]
+        } else {
+            self.keys
+        };
+
+        let mut accounts = vec![
+            AccountMeta::new(funder_pk, true),
+            AccountMeta::new(profile_pk, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ];
+        accounts.extend(
+            keys.iter()
+                .map(|(key_kp, _, _)| AccountMeta::new(key_kp.pubkey(), true)),
+        );
+
         let ix = Instruction {
             program_id: PLAYER_PROFILE_PROGRAM_ID,
-            accounts: vec![
-                AccountMeta::new(funder_pk, true),
-                AccountMeta::new(profile_pk, true),
-                AccountMeta::new_readonly(system_program::ID, false),
-                AccountMeta::new(profile_pk, true), // auth authority (writable, signer)
-            ],
+            accounts,
             data: ixCreateProfile {
-                _key_permissions: vec![
-                    // Auth Authority - Full permissions
-                    AddKeyInput {
-                        scope: AnchorPubkey::new_from_array(PLAYER_PROFILE_PROGRAM_ID.to_bytes()),
+                _key_permissions: keys
+                    .iter()
+                    .map(|(_, scope, permissions)| AddKeyInput {
+                        scope: AnchorPubkey::new_from_array(scope.to_bytes()),
                         expire_time: -1,
-                        permissions: [0xFF, 0xFF, 0, 0, 0, 0, 0, 0], // All permissions enabled
-                    },
-                ],
+                        permissions: *permissions,
+                    })
+                    .collect(),
                 _key_threshold: 1,
             }
             .data(),
         };
 
-        let block_hash = svm.latest_blockhash();
-        let tx = Transaction::new_signed_with_payer(
-            &[ix],
-            Some(&funder_pk),
-            &[self.profile_kp, self.funder_kp],
-            block_hash,
-        );
-        svm.send_transaction(tx)?;
+        let mut signers: Vec<TxSigner<'a>> = vec![self.profile_kp.into(), self.funder_kp.into()];
+        signers.extend(keys.iter().map(|(key_kp, _, _)| (*key_kp).into()));
 
-        Ok(profile_pk)
+        (vec![ix], signers, vec![profile_pk])
     }
 }
@@ -1,3 +1,9 @@
+mod choose_faction;
 mod create_profile;
+mod profile_faction;
 
+pub mod permissions;
+
+pub use choose_faction::*;
 pub use create_profile::*;
+pub use profile_faction::*;
@@ -0,0 +1,22 @@
+/// Player profile auth key, all permissions enabled.
+pub const PROFILE_AUTH_PERMISSIONS: [u8; 8] = [0xFF, 0xFF, 0, 0, 0, 0, 0, 0];
+
+/// `CargoPermissions.all()`
+pub const CARGO_ALL_PERMISSIONS: [u8; 8] = [0xFF, 0xFF, 0, 0, 0, 0, 0, 0];
+
+/// `SagePermissions.all()`
+///
+/// byte[0] = 0xFF: MANAGE_GAME through MANAGE_MINE_ITEM
+/// byte[1] = 0xFF: MANAGE_RESOURCE through MANAGE_CARGO_POD
+/// byte[2] = 0xFF: ADD_REMOVE_CARGO through SCAN_SURVEY_DATA_UNIT
+/// byte[3] = 0x1F: DO_STAR_BASE_UPKEEP through WITHDRAW_CREW (only 5 bits used)
+pub const SAGE_ALL_PERMISSIONS: [u8; 8] = [0xFF, 0xFF, 0xFF, 0x1F, 0, 0, 0, 0];
+
+/// Key index of the auth key on a superuser profile.
+pub const AUTH_KEY_INDEX: u16 = 0;
+/// Key index of the cargo superuser key on a superuser profile.
+pub const CARGO_MANAGER_KEY_INDEX: u16 = 1;
+/// Key index of the SAGE superuser key on a superuser profile.
+pub const SAGE_MANAGER_KEY_INDEX: u16 = 2;
+/// Key index of the fleet manager key on a superuser profile.
+pub const FLEET_MANAGER_KEY_INDEX: u16 = 3;
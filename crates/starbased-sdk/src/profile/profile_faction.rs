@@ -0,0 +1,13 @@
+use anchor_lang::AccountDeserialize;
+use litesvm::LiteSVM;
+use solana_sdk::pubkey::Pubkey;
+
+use staratlas_profile_faction::state::ProfileFactionAccount;
+
+/// Decodes the `ProfileFactionAccount` for `profile_pk`, if the profile has chosen a faction.
+pub fn get_profile_faction(svm: &LiteSVM, profile_pk: &Pubkey) -> Option<ProfileFactionAccount> {
+    let (profile_faction_pda, _bump) = ProfileFactionAccount::find_address(profile_pk);
+    let account = svm.get_account(&profile_faction_pda)?;
+
+    ProfileFactionAccount::try_deserialize(&mut &account.data[..]).ok()
+}
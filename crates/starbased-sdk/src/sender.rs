@@ -0,0 +1,391 @@
+//! Transaction senders.
+//!
+//! Builders send through a [`Sender`], so the same bot runs against a [`LiteSVM`] in tests and
+//! against a cluster (e.g. `solana-test-validator`) through an RPC client.
+//!
+//! ```ignore
+//! let mut client = RpcClient::new_with_commitment(url, CommitmentConfig::confirmed());
+//! StartSubwarp::new(&key, &profile, &profile_faction, &fleet, &game, &game_state, [2, 2], &funder)
+//!     .send(&mut client)?;
+//! ```
+use litesvm::{
+    types::{FailedTransactionMetadata, TransactionMetadata},
+    LiteSVM,
+};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking,
+    rpc_client::RpcClient,
+    rpc_config::{RpcSimulateTransactionAccountsConfig, RpcSimulateTransactionConfig},
+    rpc_response::RpcSimulateTransactionResult,
+};
+use solana_sdk::{
+    account::{from_account, Account},
+    clock::{Clock, Slot},
+    commitment_config::CommitmentConfig,
+    hash::Hash,
+    pubkey::Pubkey,
+    signature::Signature,
+    slot_hashes::SlotHashes,
+    sysvar,
+    transaction::{TransactionError, VersionedTransaction},
+};
+use std::{future::Future, thread, time::Duration};
+use tokio::runtime::{Handle, RuntimeFlavor};
+
+use crate::tx::TxError;
+
+/// Interval between slot polls of the RPC senders.
+const SLOT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Outcome of a simulated transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Simulation {
+    /// `None` when the transaction would succeed.
+    pub err: Option<TransactionError>,
+    pub logs: Vec<String>,
+    pub units_consumed: u64,
+    /// State of the requested accounts after the transaction, `None` for accounts that don't
+    /// exist. Empty when the transaction fails.
+    pub accounts: Vec<(Pubkey, Option<Account>)>,
+}
+
+pub trait Sender {
+    /// Transactions the builders fail to build are reported as this error too.
+    type Error: From<TxError>;
+
+    fn latest_blockhash(&mut self) -> Result<Hash, Self::Error>;
+
+    /// Sends the transaction, waiting for it to be confirmed on a cluster.
+    fn send(&mut self, tx: impl Into<VersionedTransaction>) -> Result<Signature, Self::Error>;
+
+    /// Simulates the transaction, a failing transaction is a [`Simulation`] with an error.
+    fn simulate(&mut self, tx: impl Into<VersionedTransaction>) -> Result<Simulation, Self::Error> {
+        self.simulate_with_accounts(tx, &[])
+    }
+
+    /// Simulates the transaction, returning the state of `addresses` after it.
+    fn simulate_with_accounts(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, Self::Error>;
+
+    /// `None` when the account does not exist.
+    fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, Self::Error>;
+
+    /// A slot in the `SlotHashes` sysvar, to derive address lookup tables from.
+    fn recent_slot(&mut self) -> Result<Slot, Self::Error>;
+
+    /// Returns once the slot moved past the current one, e.g. for addresses added to a lookup
+    /// table to become usable.
+    fn wait_for_next_slot(&mut self) -> Result<(), Self::Error>;
+
+    /// Returns once the cluster clock reached `unix_timestamp`, e.g. for a cooldown to expire.
+    fn wait_until(&mut self, unix_timestamp: i64) -> Result<(), Self::Error>;
+
+    /// Whether sending again may succeed, e.g. after an expired blockhash or a dropped
+    /// connection. A failing program fails again.
+    fn is_transient(err: &Self::Error) -> bool;
+}
+
+/// A `SignatureFailure` for a missing signer, a `SanitizeFailure` for a message that doesn't
+/// compile, the error is logged.
+impl From<TxError> for FailedTransactionMetadata {
+    fn from(err: TxError) -> Self {
+        FailedTransactionMetadata {
+            err: match err {
+                TxError::Signer(_) => TransactionError::SignatureFailure,
+                TxError::Compile(_) => TransactionError::SanitizeFailure,
+            },
+            meta: TransactionMetadata {
+                logs: vec![err.to_string()],
+                ..TransactionMetadata::default()
+            },
+        }
+    }
+}
+
+impl Sender for LiteSVM {
+    type Error = FailedTransactionMetadata;
+
+    fn latest_blockhash(&mut self) -> Result<Hash, Self::Error> {
+        Ok(LiteSVM::latest_blockhash(self))
+    }
+
+    fn send(&mut self, tx: impl Into<VersionedTransaction>) -> Result<Signature, Self::Error> {
+        let meta = self.send_transaction(tx)?;
+        Ok(meta.signature)
+    }
+
+    /// Accounts the transaction doesn't load are read from the current state.
+    fn simulate_with_accounts(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, Self::Error> {
+        let simulation = match self.simulate_transaction(tx) {
+            Ok(info) => {
+                let accounts = addresses
+                    .iter()
+                    .map(|address| {
+                        let account = info
+                            .post_accounts
+                            .iter()
+                            .find(|(pubkey, _)| pubkey == address)
+                            .map(|(_, account)| Account::from(account.clone()))
+                            .or_else(|| LiteSVM::get_account(self, address))
+                            .filter(|account| account.lamports > 0);
+                        (*address, account)
+                    })
+                    .collect();
+                Simulation {
+                    err: None,
+                    logs: info.meta.logs,
+                    units_consumed: info.meta.compute_units_consumed,
+                    accounts,
+                }
+            }
+            Err(failed) => Simulation {
+                err: Some(failed.err),
+                logs: failed.meta.logs,
+                units_consumed: failed.meta.compute_units_consumed,
+                accounts: vec![],
+            },
+        };
+        Ok(simulation)
+    }
+
+    fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, Self::Error> {
+        Ok(LiteSVM::get_account(self, pubkey))
+    }
+
+    /// LiteSVM doesn't record slot hashes, the current slot is added to `SlotHashes`.
+    fn recent_slot(&mut self) -> Result<Slot, Self::Error> {
+        let slot = self.get_sysvar::<Clock>().slot;
+        let mut slot_hashes = self.get_sysvar::<SlotHashes>();
+        if slot_hashes.get(&slot).is_none() {
+            slot_hashes.add(slot, LiteSVM::latest_blockhash(self));
+            self.set_sysvar(&slot_hashes);
+        }
+        Ok(slot)
+    }
+
+    /// Warps to the next slot.
+    fn wait_for_next_slot(&mut self) -> Result<(), Self::Error> {
+        let slot = self.get_sysvar::<Clock>().slot;
+        self.warp_to_slot(slot + 1);
+        Ok(())
+    }
+
+    /// Sets the clock forward and moves to the next slot, with a new blockhash.
+    fn wait_until(&mut self, unix_timestamp: i64) -> Result<(), Self::Error> {
+        let mut clock = self.get_sysvar::<Clock>();
+        clock.unix_timestamp = clock.unix_timestamp.max(unix_timestamp);
+        clock.slot += 1;
+        self.set_sysvar(&clock);
+        self.expire_blockhash();
+        Ok(())
+    }
+
+    fn is_transient(err: &Self::Error) -> bool {
+        err.err == TransactionError::BlockhashNotFound
+    }
+}
+
+impl From<TxError> for ClientError {
+    fn from(err: TxError) -> Self {
+        match err {
+            TxError::Signer(err) => ClientErrorKind::SigningError(err).into(),
+            TxError::Compile(err) => ClientErrorKind::Custom(err.to_string()).into(),
+        }
+    }
+}
+
+impl Sender for RpcClient {
+    type Error = ClientError;
+
+    fn latest_blockhash(&mut self) -> Result<Hash, Self::Error> {
+        self.get_latest_blockhash()
+    }
+
+    fn send(&mut self, tx: impl Into<VersionedTransaction>) -> Result<Signature, Self::Error> {
+        self.send_and_confirm_transaction(&tx.into())
+    }
+
+    fn simulate_with_accounts(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, Self::Error> {
+        let config = simulate_config(self.commitment(), addresses);
+        let result = self
+            .simulate_transaction_with_config(&tx.into(), config)?
+            .value;
+        Ok(simulation(result, addresses))
+    }
+
+    fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, Self::Error> {
+        Ok(self
+            .get_account_with_commitment(pubkey, self.commitment())?
+            .value)
+    }
+
+    fn recent_slot(&mut self) -> Result<Slot, Self::Error> {
+        self.get_slot()
+    }
+
+    fn wait_for_next_slot(&mut self) -> Result<(), Self::Error> {
+        let slot = self.get_slot()?;
+        while self.get_slot()? <= slot {
+            thread::sleep(SLOT_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    fn wait_until(&mut self, unix_timestamp: i64) -> Result<(), Self::Error> {
+        while clock_timestamp(Sender::get_account(self, &sysvar::clock::ID)?) < unix_timestamp {
+            thread::sleep(SLOT_POLL_INTERVAL);
+        }
+        Ok(())
+    }
+
+    fn is_transient(err: &Self::Error) -> bool {
+        is_transient_client_error(err)
+    }
+}
+
+/// Blocks on the client's futures, which needs a multi-threaded tokio runtime (`#[tokio::main]`
+/// or `#[tokio::test(flavor = "multi_thread")]`). Every method returns a
+/// [`ClientErrorKind::Custom`] error when called outside of one.
+impl Sender for nonblocking::rpc_client::RpcClient {
+    type Error = ClientError;
+
+    fn latest_blockhash(&mut self) -> Result<Hash, Self::Error> {
+        block_on(self.get_latest_blockhash())
+    }
+
+    fn send(&mut self, tx: impl Into<VersionedTransaction>) -> Result<Signature, Self::Error> {
+        block_on(self.send_and_confirm_transaction(&tx.into()))
+    }
+
+    fn simulate_with_accounts(
+        &mut self,
+        tx: impl Into<VersionedTransaction>,
+        addresses: &[Pubkey],
+    ) -> Result<Simulation, Self::Error> {
+        let config = simulate_config(self.commitment(), addresses);
+        let result = block_on(self.simulate_transaction_with_config(&tx.into(), config))?.value;
+        Ok(simulation(result, addresses))
+    }
+
+    fn get_account(&mut self, pubkey: &Pubkey) -> Result<Option<Account>, Self::Error> {
+        let commitment = self.commitment();
+        Ok(block_on(self.get_account_with_commitment(pubkey, commitment))?.value)
+    }
+
+    fn recent_slot(&mut self) -> Result<Slot, Self::Error> {
+        block_on(self.get_slot())
+    }
+
+    fn wait_for_next_slot(&mut self) -> Result<(), Self::Error> {
+        block_on(async {
+            let slot = self.get_slot().await?;
+            while self.get_slot().await? <= slot {
+                tokio::time::sleep(SLOT_POLL_INTERVAL).await;
+            }
+            Ok(())
+        })
+    }
+
+    fn wait_until(&mut self, unix_timestamp: i64) -> Result<(), Self::Error> {
+        let commitment = self.commitment();
+        block_on(async {
+            loop {
+                let clock = self
+                    .get_account_with_commitment(&sysvar::clock::ID, commitment)
+                    .await?
+                    .value;
+                if clock_timestamp(clock) >= unix_timestamp {
+                    return Ok(());
+                }
+                tokio::time::sleep(SLOT_POLL_INTERVAL).await;
+            }
+        })
+    }
+
+    fn is_transient(err: &Self::Error) -> bool {
+        is_transient_client_error(err)
+    }
+}
+
+fn simulate_config(
+    commitment: CommitmentConfig,
+    addresses: &[Pubkey],
+) -> RpcSimulateTransactionConfig {
+    RpcSimulateTransactionConfig {
+        commitment: Some(commitment),
+        accounts: (!addresses.is_empty()).then(|| RpcSimulateTransactionAccountsConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            addresses: addresses.iter().map(Pubkey::to_string).collect(),
+        }),
+        ..RpcSimulateTransactionConfig::default()
+    }
+}
+
+fn simulation(result: RpcSimulateTransactionResult, addresses: &[Pubkey]) -> Simulation {
+    let accounts = match result.accounts {
+        Some(accounts) if result.err.is_none() => addresses
+            .iter()
+            .copied()
+            .zip(
+                accounts
+                    .into_iter()
+                    .map(|account| account.and_then(|account| account.decode::<Account>())),
+            )
+            .collect(),
+        _ => vec![],
+    };
+
+    Simulation {
+        err: result.err,
+        logs: result.logs.unwrap_or_default(),
+        units_consumed: result.units_consumed.unwrap_or_default(),
+        accounts,
+    }
+}
+
+/// Transport and RPC errors, and transactions failing on their blockhash. Errors building or
+/// signing the transaction are not.
+fn is_transient_client_error(err: &ClientError) -> bool {
+    match err.kind() {
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) => true,
+        ClientErrorKind::RpcError(_) | ClientErrorKind::TransactionError(_) => matches!(
+            err.get_transaction_error(),
+            None | Some(TransactionError::BlockhashNotFound)
+        ),
+        _ => false,
+    }
+}
+
+/// `i64::MIN` when the clock sysvar can't be read, to keep polling.
+fn clock_timestamp(clock: Option<Account>) -> i64 {
+    clock
+        .and_then(|clock| from_account::<Clock, _>(&clock))
+        .map_or(i64::MIN, |clock| clock.unix_timestamp)
+}
+
+/// Blocks on `future` in the current tokio runtime. Outside a runtime or in a current thread one
+/// (e.g. a plain `#[tokio::test]`) blocking would panic or deadlock, an error is returned instead.
+fn block_on<T>(future: impl Future<Output = Result<T, ClientError>>) -> Result<T, ClientError> {
+    let handle = Handle::try_current()
+        .map_err(|err| ClientErrorKind::Custom(format!("nonblocking RpcClient sender: {}", err)))?;
+    if handle.runtime_flavor() != RuntimeFlavor::MultiThread {
+        return Err(ClientErrorKind::Custom(
+            "nonblocking RpcClient sender needs a multi-threaded tokio runtime".into(),
+        )
+        .into());
+    }
+    tokio::task::block_in_place(|| handle.block_on(future))
+}
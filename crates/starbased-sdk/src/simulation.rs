@@ -0,0 +1,148 @@
+//! Dry runs of transactions.
+//!
+//! A [`SimulationReport`] holds the outcome of a simulated transaction: the decoded Star Atlas
+//! error, the consumed compute units, the logs and the changes of the writable accounts, decoded
+//! like [`crate::snapshot::diff`] (e.g. a fleet's `state` going from `Idle` to `MoveWarp`, a cargo
+//! pod token account's `amount` by `-50`).
+//!
+//! ```ignore
+//! let report = StartSubwarp::new(/* .. */).simulate(&mut svm)?;
+//! println!("{}", report);
+//! ```
+use solana_sdk::{account::Account, pubkey::Pubkey, transaction::TransactionError};
+use std::fmt;
+
+use crate::{
+    error::StarAtlasError,
+    sender::Simulation,
+    snapshot::{account_fields, account_name, diff_fields, FieldDiff},
+};
+
+#[derive(Debug, Clone)]
+pub struct SimulationReport {
+    /// `None` when the transaction would succeed.
+    pub err: Option<TransactionError>,
+    /// `err` decoded from the IDL of the failing program.
+    pub error: Option<StarAtlasError>,
+    pub units_consumed: u64,
+    pub logs: Vec<String>,
+    /// Writable accounts the transaction would change, in transaction order. Empty when it fails.
+    pub accounts: Vec<AccountChange>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AccountChange {
+    pub pubkey: Pubkey,
+    /// Decoded account type (e.g. `Fleet`, `TokenAccount`), `None` when unknown.
+    pub name: Option<&'static str>,
+    pub created: bool,
+    pub closed: bool,
+    pub fields: Vec<FieldDiff>,
+}
+
+impl SimulationReport {
+    /// `before` holds the state of the simulated accounts before the transaction.
+    pub fn new(simulation: Simulation, before: &[(Pubkey, Option<Account>)]) -> Self {
+        let accounts = simulation
+            .accounts
+            .iter()
+            .filter_map(|(pubkey, after)| {
+                let before = before
+                    .iter()
+                    .find(|(before_pk, _)| before_pk == pubkey)
+                    .and_then(|(_, account)| account.as_ref());
+                account_change(pubkey, before, after.as_ref())
+            })
+            .collect();
+
+        SimulationReport {
+            error: simulation
+                .err
+                .as_ref()
+                .and_then(|err| StarAtlasError::from_transaction_error(err, &simulation.logs)),
+            err: simulation.err,
+            units_consumed: simulation.units_consumed,
+            logs: simulation.logs,
+            accounts,
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.err.is_none()
+    }
+
+    pub fn account(&self, pubkey: &Pubkey) -> Option<&AccountChange> {
+        self.accounts.iter().find(|change| change.pubkey == *pubkey)
+    }
+}
+
+impl AccountChange {
+    /// The change of a decoded field, e.g. `state` or `amount`.
+    pub fn field(&self, field: &str) -> Option<&FieldDiff> {
+        self.fields.iter().find(|diff| diff.field == field)
+    }
+}
+
+/// `None` when no decoded field changed.
+fn account_change(
+    pubkey: &Pubkey,
+    before: Option<&Account>,
+    after: Option<&Account>,
+) -> Option<AccountChange> {
+    let decoded = |account: Option<&Account>| account.map(account_fields).unwrap_or_default();
+    let fields = diff_fields(&decoded(before), &decoded(after));
+    if fields.is_empty() {
+        return None;
+    }
+
+    Some(AccountChange {
+        pubkey: *pubkey,
+        name: after.or(before).and_then(account_name),
+        created: before.is_none(),
+        closed: after.is_none(),
+        fields,
+    })
+}
+
+impl fmt::Display for SimulationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.error, &self.err) {
+            (_, None) => writeln!(f, "ok, {} compute units", self.units_consumed)?,
+            (Some(error), _) => writeln!(f, "{}, {} compute units", error, self.units_consumed)?,
+            (None, Some(err)) => writeln!(f, "{}, {} compute units", err, self.units_consumed)?,
+        }
+
+        for change in self.accounts.iter() {
+            let marker = match (change.created, change.closed) {
+                (true, _) => '+',
+                (_, true) => '-',
+                _ => '~',
+            };
+            writeln!(
+                f,
+                "  {} {} {}",
+                marker,
+                change.name.unwrap_or("unknown"),
+                change.pubkey
+            )?;
+            for field in change.fields.iter() {
+                let before = field.before.as_deref().unwrap_or("-");
+                let after = field.after.as_deref().unwrap_or("-");
+                match field.delta {
+                    Some(delta) => writeln!(
+                        f,
+                        "      {}: {} -> {} ({:+})",
+                        field.field, before, after, delta
+                    )?,
+                    None => writeln!(f, "      {}: {} -> {}", field.field, before, after)?,
+                }
+            }
+        }
+
+        for line in self.logs.iter() {
+            writeln!(f, "  | {}", line)?;
+        }
+
+        Ok(())
+    }
+}
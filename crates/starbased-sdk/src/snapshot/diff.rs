@@ -0,0 +1,274 @@
+use anchor_lang::Discriminator;
+use serde::{Deserialize, Serialize};
+use solana_sdk::{account::Account, program_pack::Pack, pubkey::Pubkey};
+use spl_token::{state::Account as TokenAccount, ID as TOKEN_PROGRAM_ID};
+use std::{collections::BTreeMap, fmt};
+
+use staratlas_cargo::state::{CargoPod, CargoStatsDefinition, CargoType};
+use staratlas_player_profile::state::{PlayerName, Profile, ProfileRoleMembership, Role};
+use staratlas_profile_faction::state::ProfileFactionAccount;
+use staratlas_sage::state::{
+    CraftingInstance, DisbandedFleet, Fleet, FleetShips, Game, GameState, MineItem, Planet,
+    PlayerCrewRecord, ProgressionConfig, Resource, SageCrewConfig, SagePlayerProfile, Sector, Ship,
+    Star, Starbase, StarbasePlayer, SurveyDataUnitTracker,
+};
+
+use super::{
+    fields::{decode_fields, token_account_fields},
+    hex, Snapshot,
+};
+
+/// Changes between two snapshots, per account type.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SnapshotDiff {
+    pub before_slot: u64,
+    pub after_slot: u64,
+    pub types: Vec<TypeDiff>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TypeDiff {
+    /// Account type name, or the hex discriminator of unknown types.
+    pub name: String,
+    pub created: Vec<String>,
+    pub closed: Vec<String>,
+    pub modified: Vec<AccountDiff>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountDiff {
+    pub pubkey: String,
+    pub fields: Vec<FieldDiff>,
+}
+
+/// A changed field of the decoded account, nested fields are joined with `.` (e.g.
+/// `ship_counts.total`, `sector.0`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldDiff {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    /// `after - before` of numeric fields.
+    pub delta: Option<i128>,
+}
+
+impl SnapshotDiff {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
+/// Diffs the accounts of `before` and `after`.
+pub fn diff(before: &Snapshot, after: &Snapshot) -> SnapshotDiff {
+    let mut discriminators: Vec<&Vec<u8>> = before
+        .accounts
+        .keys()
+        .chain(after.accounts.keys())
+        .collect();
+    discriminators.sort();
+    discriminators.dedup();
+
+    let types = discriminators
+        .into_iter()
+        .filter_map(|discriminator| {
+            let by_pubkey = |snapshot: &Snapshot| -> BTreeMap<Pubkey, Account> {
+                snapshot
+                    .accounts
+                    .get(discriminator)
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect()
+            };
+            let before_accounts = by_pubkey(before);
+            let after_accounts = by_pubkey(after);
+
+            let mut type_diff = TypeDiff {
+                name: account_type_name(discriminator)
+                    .map(String::from)
+                    .unwrap_or_else(|| hex(discriminator)),
+                ..Default::default()
+            };
+
+            for (pubkey, after_account) in after_accounts.iter() {
+                match before_accounts.get(pubkey) {
+                    None => type_diff.created.push(pubkey.to_string()),
+                    Some(before_account) if before_account != after_account => {
+                        type_diff.modified.push(AccountDiff {
+                            pubkey: pubkey.to_string(),
+                            fields: diff_account(before_account, after_account),
+                        })
+                    }
+                    Some(_) => {}
+                }
+            }
+            type_diff.closed = before_accounts
+                .keys()
+                .filter(|pubkey| !after_accounts.contains_key(pubkey))
+                .map(Pubkey::to_string)
+                .collect();
+
+            let changed = !(type_diff.created.is_empty()
+                && type_diff.closed.is_empty()
+                && type_diff.modified.is_empty());
+            changed.then_some(type_diff)
+        })
+        .collect();
+
+    SnapshotDiff {
+        before_slot: before.header.slot,
+        after_slot: after.header.slot,
+        types,
+    }
+}
+
+fn diff_account(before: &Account, after: &Account) -> Vec<FieldDiff> {
+    diff_fields(&account_fields(before), &account_fields(after))
+}
+
+/// Name of the decoded account type, token accounts are `TokenAccount`.
+pub(crate) fn account_name(account: &Account) -> Option<&'static str> {
+    if account.owner == TOKEN_PROGRAM_ID && account.data.len() == TokenAccount::LEN {
+        return Some("TokenAccount");
+    }
+    account_type_name(account.data.get(..8)?)
+}
+
+/// Fields of the decoded account and its `lamports`.
+pub(crate) fn account_fields(account: &Account) -> BTreeMap<String, String> {
+    let token_account = (account.owner == TOKEN_PROGRAM_ID)
+        .then(|| TokenAccount::unpack(&account.data).ok())
+        .flatten();
+    let mut fields = match token_account {
+        Some(token_account) => token_account_fields(&token_account),
+        None => account_type_name(account.data.get(..8).unwrap_or_default())
+            .and_then(|name| decode_fields(name, &account.data[8..]))
+            .unwrap_or_else(|| BTreeMap::from([("data".to_string(), hex(&account.data))])),
+    };
+    fields.insert("lamports".into(), account.lamports.to_string());
+    fields
+}
+
+/// Fields differing between `before` and `after`, with the delta of numeric fields.
+pub(crate) fn diff_fields(
+    before_fields: &BTreeMap<String, String>,
+    after_fields: &BTreeMap<String, String>,
+) -> Vec<FieldDiff> {
+    let mut fields: Vec<&String> = before_fields.keys().chain(after_fields.keys()).collect();
+    fields.sort();
+    fields.dedup();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let before = before_fields.get(field);
+            let after = after_fields.get(field);
+            if before == after {
+                return None;
+            }
+
+            let delta = match (before, after) {
+                (Some(before), Some(after)) => before
+                    .parse::<i128>()
+                    .ok()
+                    .zip(after.parse::<i128>().ok())
+                    .map(|(before, after)| after - before),
+                _ => None,
+            };
+
+            Some(FieldDiff {
+                field: field.clone(),
+                before: before.cloned(),
+                after: after.cloned(),
+                delta,
+            })
+        })
+        .collect()
+}
+
+macro_rules! account_types {
+    ($($account:ident),* $(,)?) => {
+        /// Name of the SAGE, cargo or profile account type with the discriminator.
+        pub fn account_type_name(discriminator: &[u8]) -> Option<&'static str> {
+            $(
+                if discriminator == $account::DISCRIMINATOR {
+                    return Some(stringify!($account));
+                }
+            )*
+            None
+        }
+    };
+}
+
+account_types!(
+    CargoPod,
+    CargoStatsDefinition,
+    CargoType,
+    CraftingInstance,
+    DisbandedFleet,
+    Fleet,
+    FleetShips,
+    Game,
+    GameState,
+    MineItem,
+    Planet,
+    PlayerCrewRecord,
+    PlayerName,
+    Profile,
+    ProfileFactionAccount,
+    ProfileRoleMembership,
+    ProgressionConfig,
+    Resource,
+    Role,
+    SageCrewConfig,
+    SagePlayerProfile,
+    Sector,
+    Ship,
+    Star,
+    Starbase,
+    StarbasePlayer,
+    SurveyDataUnitTracker,
+);
+
+impl fmt::Display for SnapshotDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "slot {} -> {}", self.before_slot, self.after_slot)?;
+        for type_diff in self.types.iter() {
+            writeln!(
+                f,
+                "\n{}: {} created, {} closed, {} modified",
+                type_diff.name,
+                type_diff.created.len(),
+                type_diff.closed.len(),
+                type_diff.modified.len()
+            )?;
+            for pubkey in type_diff.created.iter() {
+                writeln!(f, "  + {}", pubkey)?;
+            }
+            for pubkey in type_diff.closed.iter() {
+                writeln!(f, "  - {}", pubkey)?;
+            }
+            for account_diff in type_diff.modified.iter() {
+                writeln!(f, "  ~ {}", account_diff.pubkey)?;
+                for field in account_diff.fields.iter() {
+                    let before = field.before.as_deref().unwrap_or("-");
+                    let after = field.after.as_deref().unwrap_or("-");
+                    match field.delta {
+                        Some(delta) => writeln!(
+                            f,
+                            "      {}.{}: {} -> {} ({:+})",
+                            type_diff.name, field.field, before, after, delta
+                        )?,
+                        None => writeln!(
+                            f,
+                            "      {}.{}: {} -> {}",
+                            type_diff.name, field.field, before, after
+                        )?,
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
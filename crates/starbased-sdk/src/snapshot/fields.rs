@@ -0,0 +1,264 @@
+//! Account fields walked with the type definitions of the program IDLs.
+//!
+//! Fields are keyed by their snake case path, nested fields joined with `.` and array, vector and
+//! tuple elements indexed (e.g. `ship_counts.total`, `sector.0`). Byte arrays and vectors are a
+//! single field, quoted when they hold printable UTF-8 (e.g. names and labels), hex otherwise.
+use serde_json::Value;
+use solana_sdk::pubkey::Pubkey;
+use spl_token::state::Account as TokenAccount;
+use std::{collections::BTreeMap, sync::OnceLock};
+
+use super::hex;
+
+/// Variants of the state following the `Fleet` account data, in discriminator order.
+const FLEET_STATES: [&str; 6] = [
+    "StarbaseLoadingBay",
+    "Idle",
+    "MineAsteroid",
+    "MoveWarp",
+    "MoveSubwarp",
+    "Respawn",
+];
+
+fn idls() -> &'static [Value] {
+    static IDLS: OnceLock<Vec<Value>> = OnceLock::new();
+    IDLS.get_or_init(|| {
+        [
+            include_str!("../../../../programs/sage/sage.json"),
+            include_str!("../../../../programs/cargo/cargo.json"),
+            include_str!("../../../../programs/player-profile/player_profile.json"),
+            include_str!("../../../../programs/profile-faction/profile_faction.json"),
+        ]
+        .iter()
+        .map(|idl| serde_json::from_str(idl).expect("program IDL"))
+        .collect()
+    })
+}
+
+/// The account or type definition named `name` in `idl`.
+fn type_definition<'a>(idl: &'a Value, name: &str) -> Option<&'a Value> {
+    ["accounts", "types"]
+        .iter()
+        .filter_map(|section| idl[section].as_array())
+        .flatten()
+        .find(|definition| definition["name"] == name)
+        .map(|definition| &definition["type"])
+}
+
+/// Fields of the account `name` after its discriminator, `None` when `data` doesn't decode.
+pub(crate) fn decode_fields(name: &str, data: &[u8]) -> Option<BTreeMap<String, String>> {
+    let (idl, definition) = idls()
+        .iter()
+        .find_map(|idl| Some((idl, type_definition(idl, name)?)))?;
+
+    let mut walker = Walker {
+        idl,
+        data,
+        fields: BTreeMap::new(),
+    };
+    walker.defined(definition, "")?;
+
+    if name == "Fleet" {
+        let variant = *FLEET_STATES.get(walker.take(1)?[0] as usize)?;
+        walker.defined(type_definition(idl, variant)?, "state")?;
+        walker.fields.insert("state".into(), variant.into());
+    }
+
+    Some(walker.fields)
+}
+
+/// Fields of an SPL token account.
+pub(crate) fn token_account_fields(token_account: &TokenAccount) -> BTreeMap<String, String> {
+    let optional = |value: Option<String>| value.unwrap_or_else(|| "None".into());
+    BTreeMap::from([
+        ("mint".into(), token_account.mint.to_string()),
+        ("owner".into(), token_account.owner.to_string()),
+        ("amount".into(), token_account.amount.to_string()),
+        (
+            "delegate".into(),
+            optional(Option::from(token_account.delegate).map(|key: Pubkey| key.to_string())),
+        ),
+        ("state".into(), format!("{:?}", token_account.state)),
+        (
+            "is_native".into(),
+            optional(Option::from(token_account.is_native).map(|amount: u64| amount.to_string())),
+        ),
+        (
+            "delegated_amount".into(),
+            token_account.delegated_amount.to_string(),
+        ),
+        (
+            "close_authority".into(),
+            optional(
+                Option::from(token_account.close_authority).map(|key: Pubkey| key.to_string()),
+            ),
+        ),
+    ])
+}
+
+/// Reads borsh encoded `data` front to back into `fields`.
+struct Walker<'a> {
+    idl: &'a Value,
+    data: &'a [u8],
+    fields: BTreeMap<String, String>,
+}
+
+impl<'a> Walker<'a> {
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        if self.data.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.data.split_at(len);
+        self.data = rest;
+        Some(bytes)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn take_len(&mut self) -> Option<usize> {
+        Some(u32::from_le_bytes(self.take_array()?) as usize)
+    }
+
+    fn insert(&mut self, path: &str, value: String) {
+        self.fields.insert(path.to_string(), value);
+    }
+
+    /// A struct or enum definition (`{"kind": "struct", ..}`).
+    fn defined(&mut self, definition: &Value, path: &str) -> Option<()> {
+        match definition["kind"].as_str()? {
+            "struct" => self.struct_fields(definition["fields"].as_array()?, path),
+            "enum" => {
+                let index = self.take(1)?[0] as usize;
+                let variant = definition["variants"].as_array()?.get(index)?;
+                self.insert(path, variant["name"].as_str()?.to_string());
+                match variant["fields"].as_array() {
+                    Some(fields) => self.struct_fields(fields, path),
+                    None => Some(()),
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Named fields (`{"name": .., "type": ..}`) or tuple fields (bare types).
+    fn struct_fields(&mut self, fields: &[Value], path: &str) -> Option<()> {
+        for (index, field) in fields.iter().enumerate() {
+            let (name, ty) = match field["name"].as_str() {
+                Some(name) => (snake_case(name), &field["type"]),
+                None => (index.to_string(), field),
+            };
+            self.value(ty, &join(path, &name))?;
+        }
+        Some(())
+    }
+
+    fn value(&mut self, ty: &Value, path: &str) -> Option<()> {
+        if let Some(name) = ty.as_str() {
+            let value = match name {
+                "bool" => (self.take(1)?[0] != 0).to_string(),
+                "u8" => self.take(1)?[0].to_string(),
+                "i8" => (self.take(1)?[0] as i8).to_string(),
+                "u16" => u16::from_le_bytes(self.take_array()?).to_string(),
+                "i16" => i16::from_le_bytes(self.take_array()?).to_string(),
+                "u32" => u32::from_le_bytes(self.take_array()?).to_string(),
+                "i32" => i32::from_le_bytes(self.take_array()?).to_string(),
+                "u64" => u64::from_le_bytes(self.take_array()?).to_string(),
+                "i64" => i64::from_le_bytes(self.take_array()?).to_string(),
+                "u128" => u128::from_le_bytes(self.take_array()?).to_string(),
+                "i128" => i128::from_le_bytes(self.take_array()?).to_string(),
+                "f32" => f32::from_le_bytes(self.take_array()?).to_string(),
+                "f64" => f64::from_le_bytes(self.take_array()?).to_string(),
+                "publicKey" | "pubkey" => Pubkey::new_from_array(self.take_array()?).to_string(),
+                "string" => {
+                    let len = self.take_len()?;
+                    format!("{:?}", std::str::from_utf8(self.take(len)?).ok()?)
+                }
+                "bytes" => {
+                    let len = self.take_len()?;
+                    bytes(self.take(len)?)
+                }
+                _ => return None,
+            };
+            self.insert(path, value);
+            return Some(());
+        }
+
+        if let Some(inner) = ty.get("option") {
+            return match self.take(1)?[0] {
+                0 => {
+                    self.insert(path, "None".into());
+                    Some(())
+                }
+                _ => self.value(inner, path),
+            };
+        }
+        if let Some(array) = ty.get("array") {
+            return self.elements(&array[0], array[1].as_u64()? as usize, path);
+        }
+        if let Some(inner) = ty.get("vec") {
+            let len = self.take_len()?;
+            return self.elements(inner, len, path);
+        }
+        if let Some(defined) = ty.get("defined") {
+            let name = defined.as_str().or_else(|| defined["name"].as_str())?;
+            let definition = type_definition(self.idl, name)?;
+            return self.defined(definition, path);
+        }
+
+        None
+    }
+
+    fn elements(&mut self, ty: &Value, len: usize, path: &str) -> Option<()> {
+        if ty == "u8" {
+            let value = bytes(self.take(len)?);
+            self.insert(path, value);
+            return Some(());
+        }
+        for index in 0..len {
+            self.value(ty, &join(path, &index.to_string()))?;
+        }
+        Some(())
+    }
+}
+
+fn join(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+/// Quoted when printable UTF-8 after trailing zero padding, hex otherwise.
+fn bytes(bytes: &[u8]) -> String {
+    let trimmed = &bytes[..bytes
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |i| i + 1)];
+    match std::str::from_utf8(trimmed) {
+        Ok(text) if !text.is_empty() && !text.chars().any(char::is_control) => {
+            format!("{:?}", text)
+        }
+        _ => hex(bytes),
+    }
+}
+
+/// `shipCounts` and `ship_counts` to `ship_counts`.
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() {
+            if previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit()) {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        } else {
+            snake.push(c);
+        }
+        previous = Some(c);
+    }
+    snake
+}
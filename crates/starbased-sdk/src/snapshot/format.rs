@@ -0,0 +1,410 @@
+use bincode::{Decode, Encode};
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::{
+    collections::HashMap,
+    io::{BufReader, BufWriter, Read, Write},
+    str::FromStr,
+};
+
+use super::SnapshotError;
+
+/// Leading bytes of a versioned snapshot, legacy (version 0) snapshots have no magic.
+pub const SNAPSHOT_MAGIC: &[u8; 8] = b"SAGESNAP";
+
+/// Format version written by [`SnapshotWriter`].
+///
+/// - `0`: bincode map of discriminator to base58 pubkey/account pairs, no header.
+/// - `1`: [`SNAPSHOT_MAGIC`], version, header with the IDL hash and sections of accounts, each
+///   led by its discriminator and count, ended by an end marker.
+pub const SNAPSHOT_VERSION: u16 = 1;
+
+fn config() -> bincode::config::Configuration {
+    bincode::config::standard()
+}
+
+/// SHA-256 of the SAGE IDL the SDK decodes accounts with.
+pub fn sage_idl_hash() -> [u8; 32] {
+    Sha256::digest(include_bytes!("../../../../programs/sage/sage.json")).into()
+}
+
+/// Describes the snapshot contents.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    /// Format version the snapshot was written with.
+    pub version: u16,
+    /// Programs the accounts were fetched from.
+    pub program_ids: Vec<Pubkey>,
+    /// Slot the accounts were fetched at.
+    pub slot: u64,
+    /// Unix timestamp of the snapshot creation.
+    pub timestamp: i64,
+    /// SHA-256 of the IDL the accounts decode with, `None` for version `0`.
+    pub idl_hash: Option<[u8; 32]>,
+    /// Number of accounts per discriminator, in the order they are written.
+    ///
+    /// Ignored by [`SnapshotWriter`]. From version `1` on [`SnapshotReader`] adds the sections as
+    /// it reaches them, the counts are complete once the reader is exhausted.
+    pub counts: Vec<(Vec<u8>, u64)>,
+}
+
+impl Default for SnapshotHeader {
+    fn default() -> Self {
+        SnapshotHeader {
+            version: SNAPSHOT_VERSION,
+            program_ids: vec![staratlas_sage::ID],
+            slot: 0,
+            timestamp: 0,
+            idl_hash: Some(sage_idl_hash()),
+            counts: Vec::new(),
+        }
+    }
+}
+
+impl SnapshotHeader {
+    pub fn total_count(&self) -> u64 {
+        self.counts.iter().map(|(_, count)| count).sum()
+    }
+
+    pub fn count(&self, discriminator: &[u8]) -> u64 {
+        self.counts
+            .iter()
+            .filter(|(d, _)| d == discriminator)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+#[derive(Encode, Decode)]
+struct HeaderV1 {
+    program_ids: Vec<[u8; 32]>,
+    slot: u64,
+    timestamp: i64,
+    idl_hash: Option<[u8; 32]>,
+}
+
+/// Leads the accounts of a section, `None` ends the snapshot.
+#[derive(Encode, Decode)]
+struct SectionV1 {
+    discriminator: Vec<u8>,
+    count: u64,
+}
+
+#[derive(Encode, Decode)]
+struct AccountV1 {
+    pubkey: [u8; 32],
+    lamports: u64,
+    data: Vec<u8>,
+    owner: [u8; 32],
+    executable: bool,
+    rent_epoch: u64,
+}
+
+/// Version 0 pubkey, base58 encoded.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, Clone)]
+pub struct SerializablePubkey(pub String);
+
+/// Version 0 account.
+#[derive(Serialize, Deserialize, Encode, Decode, Debug, Clone)]
+pub struct SerializableAccount {
+    pub lamports: u64,
+    pub data: Vec<u8>,
+    pub owner: SerializablePubkey,
+    pub executable: bool,
+    pub rent_epoch: u64,
+}
+
+impl From<Pubkey> for SerializablePubkey {
+    fn from(pubkey: Pubkey) -> Self {
+        SerializablePubkey(pubkey.to_string())
+    }
+}
+
+impl TryFrom<SerializablePubkey> for Pubkey {
+    type Error = SnapshotError;
+
+    fn try_from(serializable: SerializablePubkey) -> Result<Self, Self::Error> {
+        Pubkey::from_str(&serializable.0).map_err(|_| SnapshotError::InvalidPubkey(serializable.0))
+    }
+}
+
+impl From<Account> for SerializableAccount {
+    fn from(account: Account) -> Self {
+        SerializableAccount {
+            lamports: account.lamports,
+            data: account.data,
+            owner: SerializablePubkey::from(account.owner),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+        }
+    }
+}
+
+impl TryFrom<SerializableAccount> for Account {
+    type Error = SnapshotError;
+
+    fn try_from(serializable: SerializableAccount) -> Result<Self, Self::Error> {
+        Ok(Account {
+            lamports: serializable.lamports,
+            data: serializable.data,
+            owner: serializable.owner.try_into()?,
+            executable: serializable.executable,
+            rent_epoch: serializable.rent_epoch,
+        })
+    }
+}
+
+type LegacyCollection = HashMap<Vec<u8>, Vec<(SerializablePubkey, SerializableAccount)>>;
+
+/// An account read from a snapshot.
+#[derive(Debug, Clone)]
+pub struct SnapshotEntry {
+    pub discriminator: Vec<u8>,
+    pub pubkey: Pubkey,
+    pub account: Account,
+}
+
+/// Streams accounts into a gzip compressed snapshot.
+///
+/// Accounts are written a section (the accounts of one type) at a time, so each type can be
+/// fetched, written and dropped in turn:
+///
+/// ```ignore
+/// let mut writer = SnapshotWriter::new(file, &header)?;
+/// for discriminator in discriminators {
+///     writer.write_section(discriminator, &fetch_accounts(discriminator)?)?;
+/// }
+/// writer.finish()?;
+/// ```
+pub struct SnapshotWriter<W: Write> {
+    encoder: BufWriter<GzEncoder<W>>,
+    count: u64,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    /// Writes the header, its `version` and `counts` are ignored.
+    pub fn new(writer: W, header: &SnapshotHeader) -> Result<Self, SnapshotError> {
+        let mut encoder = BufWriter::new(GzEncoder::new(writer, Compression::default()));
+
+        encoder.write_all(SNAPSHOT_MAGIC)?;
+        bincode::encode_into_std_write(SNAPSHOT_VERSION, &mut encoder, config())?;
+        let header_v1 = HeaderV1 {
+            program_ids: header
+                .program_ids
+                .iter()
+                .map(|program_id| program_id.to_bytes())
+                .collect(),
+            slot: header.slot,
+            timestamp: header.timestamp,
+            idl_hash: header.idl_hash,
+        };
+        bincode::encode_into_std_write(&header_v1, &mut encoder, config())?;
+
+        Ok(SnapshotWriter { encoder, count: 0 })
+    }
+
+    /// Writes the accounts of one type, a type may span several sections.
+    pub fn write_section(
+        &mut self,
+        discriminator: &[u8],
+        accounts: &[(Pubkey, Account)],
+    ) -> Result<(), SnapshotError> {
+        let section = Some(SectionV1 {
+            discriminator: discriminator.to_vec(),
+            count: accounts.len() as u64,
+        });
+        bincode::encode_into_std_write(&section, &mut self.encoder, config())?;
+
+        for (pubkey, account) in accounts.iter() {
+            let account_v1 = AccountV1 {
+                pubkey: pubkey.to_bytes(),
+                lamports: account.lamports,
+                data: account.data.clone(),
+                owner: account.owner.to_bytes(),
+                executable: account.executable,
+                rent_epoch: account.rent_epoch,
+            };
+            bincode::encode_into_std_write(&account_v1, &mut self.encoder, config())?;
+        }
+        self.count += accounts.len() as u64;
+
+        Ok(())
+    }
+
+    /// Number of accounts written so far.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Writes the end marker and flushes the snapshot.
+    pub fn finish(mut self) -> Result<W, SnapshotError> {
+        bincode::encode_into_std_write(None::<SectionV1>, &mut self.encoder, config())?;
+
+        let encoder = self
+            .encoder
+            .into_inner()
+            .map_err(|err| SnapshotError::Io(err.into_error()))?;
+        Ok(encoder.finish()?)
+    }
+}
+
+enum Entries<R: Read> {
+    Stream {
+        decoder: BufReader<GzDecoder<R>>,
+        /// Discriminator and remaining accounts of the current section.
+        section: Option<(Vec<u8>, u64)>,
+        /// Whether the end marker or an error was read.
+        done: bool,
+    },
+    Legacy(std::vec::IntoIter<SnapshotEntry>),
+}
+
+/// Streams accounts out of a gzip compressed snapshot of any supported version.
+///
+/// Version 0 snapshots have no header and are decoded at once, `slot` and `timestamp` of their
+/// header are `0`.
+pub struct SnapshotReader<R: Read> {
+    header: SnapshotHeader,
+    entries: Entries<R>,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    pub fn new(reader: R) -> Result<Self, SnapshotError> {
+        let mut decoder = GzDecoder::new(reader);
+
+        let mut magic = Vec::with_capacity(SNAPSHOT_MAGIC.len());
+        (&mut decoder)
+            .take(SNAPSHOT_MAGIC.len() as u64)
+            .read_to_end(&mut magic)?;
+
+        if magic != SNAPSHOT_MAGIC {
+            let mut decoded_data = magic;
+            decoder.read_to_end(&mut decoded_data)?;
+            return Self::legacy(&decoded_data);
+        }
+
+        let mut decoder = BufReader::new(decoder);
+        let version: u16 = bincode::decode_from_std_read(&mut decoder, config())?;
+        if version > SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+
+        let header_v1: HeaderV1 = bincode::decode_from_std_read(&mut decoder, config())?;
+        let header = SnapshotHeader {
+            version,
+            program_ids: header_v1
+                .program_ids
+                .into_iter()
+                .map(Pubkey::new_from_array)
+                .collect(),
+            slot: header_v1.slot,
+            timestamp: header_v1.timestamp,
+            idl_hash: header_v1.idl_hash,
+            counts: Vec::new(),
+        };
+
+        Ok(SnapshotReader {
+            entries: Entries::Stream {
+                decoder,
+                section: None,
+                done: false,
+            },
+            header,
+        })
+    }
+
+    fn legacy(decoded_data: &[u8]) -> Result<Self, SnapshotError> {
+        let (collection, _): (LegacyCollection, _) =
+            bincode::decode_from_slice(decoded_data, config())?;
+
+        let mut header = SnapshotHeader {
+            version: 0,
+            idl_hash: None,
+            ..Default::default()
+        };
+        let mut entries = Vec::new();
+        for (discriminator, accounts) in collection {
+            header
+                .counts
+                .push((discriminator.clone(), accounts.len() as u64));
+            for (pubkey, account) in accounts {
+                entries.push(SnapshotEntry {
+                    discriminator: discriminator.clone(),
+                    pubkey: pubkey.try_into()?,
+                    account: account.try_into()?,
+                });
+            }
+        }
+
+        Ok(SnapshotReader {
+            header,
+            entries: Entries::Legacy(entries.into_iter()),
+        })
+    }
+
+    pub fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+}
+
+impl<R: Read> Iterator for SnapshotReader<R> {
+    type Item = Result<SnapshotEntry, SnapshotError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.entries {
+            Entries::Legacy(entries) => entries.next().map(Ok),
+            Entries::Stream {
+                decoder,
+                section,
+                done,
+            } => {
+                while !*done && section.as_ref().is_none_or(|(_, count)| *count == 0) {
+                    match bincode::decode_from_std_read::<Option<SectionV1>, _, _>(
+                        decoder,
+                        config(),
+                    ) {
+                        Ok(Some(next)) => {
+                            let counts = &mut self.header.counts;
+                            counts.push((next.discriminator.clone(), next.count));
+                            *section = Some((next.discriminator, next.count));
+                        }
+                        Ok(None) => {
+                            *done = true;
+                            *section = None;
+                        }
+                        Err(err) => {
+                            *done = true;
+                            *section = None;
+                            return Some(Err(err.into()));
+                        }
+                    }
+                }
+                let (discriminator, count) = section.as_mut()?;
+                *count -= 1;
+                let discriminator = discriminator.clone();
+
+                let entry = bincode::decode_from_std_read::<AccountV1, _, _>(decoder, config())
+                    .map(|account_v1| SnapshotEntry {
+                        discriminator,
+                        pubkey: Pubkey::new_from_array(account_v1.pubkey),
+                        account: Account {
+                            lamports: account_v1.lamports,
+                            data: account_v1.data,
+                            owner: Pubkey::new_from_array(account_v1.owner),
+                            executable: account_v1.executable,
+                            rent_epoch: account_v1.rent_epoch,
+                        },
+                    })
+                    .map_err(SnapshotError::from);
+                if entry.is_err() {
+                    *done = true;
+                    *section = None;
+                }
+
+                Some(entry)
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+use anchor_lang::{prelude::borsh::BorshDeserialize, AccountDeserialize, Discriminator};
+use litesvm::LiteSVM;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::{collections::HashMap, path::Path};
+
+use staratlas_sage::{
+    state::{
+        DisbandedFleet, Fleet, FleetShips, Game, GameState, MineItem, Planet, PlayerCrewRecord,
+        ProgressionConfig, Resource, SageCrewConfig, SagePlayerProfile, Sector, Ship, Star,
+        Starbase, StarbasePlayer, SurveyDataUnitTracker,
+    },
+    state_with_data::{FleetState, FleetWithState},
+};
+
+use super::{Snapshot, SnapshotError};
+
+/// Restricts which snapshot accounts are loaded, `None` matches everything.
+///
+/// A filter only applies to account types that carry the attribute, e.g. `sector` keeps all
+/// `Ship` and `MineItem` accounts. Accounts without the attribute of their own inherit it from
+/// the account they belong to (a `Resource` from its planet, a `StarbasePlayer` from its
+/// starbase, `FleetShips` from its fleet).
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotFilter {
+    pub game_id: Option<Pubkey>,
+    /// Sector coordinates, a moving fleet matches both its origin and destination sector.
+    pub sector: Option<[i64; 2]>,
+    pub owner_profile: Option<Pubkey>,
+}
+
+impl SnapshotFilter {
+    pub fn set_game_id(mut self, game_id: Pubkey) -> Self {
+        self.game_id = Some(game_id);
+        self
+    }
+
+    pub fn set_sector(mut self, sector: [i64; 2]) -> Self {
+        self.sector = Some(sector);
+        self
+    }
+
+    pub fn set_owner_profile(mut self, owner_profile: Pubkey) -> Self {
+        self.owner_profile = Some(owner_profile);
+        self
+    }
+}
+
+/// The filterable attributes of an account, `None` when the account type has no such attribute.
+#[derive(Debug, Default)]
+struct AccountAttributes {
+    game_id: Option<Pubkey>,
+    sectors: Option<Vec<[i64; 2]>>,
+    owner_profile: Option<Pubkey>,
+}
+
+impl AccountAttributes {
+    fn matches(&self, filter: &SnapshotFilter) -> bool {
+        let game_id = match (filter.game_id, self.game_id) {
+            (Some(expected), Some(game_id)) => expected == game_id,
+            _ => true,
+        };
+        let sector = match (filter.sector, &self.sectors) {
+            (Some(expected), Some(sectors)) => sectors.contains(&expected),
+            _ => true,
+        };
+        let owner_profile = match (filter.owner_profile, self.owner_profile) {
+            (Some(expected), Some(owner_profile)) => expected == owner_profile,
+            _ => true,
+        };
+
+        game_id && sector && owner_profile
+    }
+}
+
+/// Reads the snapshot at `path` and loads it into the svm, see [`load_into_svm`].
+pub fn load(
+    svm: &mut LiteSVM,
+    path: impl AsRef<Path>,
+    filter: &SnapshotFilter,
+) -> Result<usize, SnapshotError> {
+    let snapshot = Snapshot::read(path)?;
+    load_into_svm(svm, &snapshot, filter)
+}
+
+/// Sets every snapshot account matching `filter` in the svm, returns the number of accounts set.
+///
+/// Expects the SAGE program to be loaded at its mainnet address, accounts keep their owner.
+pub fn load_into_svm(
+    svm: &mut LiteSVM,
+    snapshot: &Snapshot,
+    filter: &SnapshotFilter,
+) -> Result<usize, SnapshotError> {
+    let mut count = 0;
+    for (pubkey, account) in snapshot.filter(filter) {
+        svm.set_account(*pubkey, account.clone())
+            .map_err(|err| SnapshotError::SetAccount(*pubkey, format!("{:?}", err)))?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+impl Snapshot {
+    /// Accounts matching `filter`.
+    pub fn filter(&self, filter: &SnapshotFilter) -> Vec<(&Pubkey, &Account)> {
+        // fleets docked at a starbase or mining a planet take its sector, so they are resolved
+        // after the planets and starbases
+        let mut parents = HashMap::new();
+        for accounts in [self.accounts_of::<Planet>(), self.accounts_of::<Starbase>()] {
+            for (pubkey, account) in accounts {
+                parents.insert(*pubkey, attributes(pubkey, &account.data, &HashMap::new()));
+            }
+        }
+        let fleets: Vec<_> = self
+            .accounts_of::<Fleet>()
+            .iter()
+            .map(|(pubkey, account)| (*pubkey, attributes(pubkey, &account.data, &parents)))
+            .collect();
+        parents.extend(fleets);
+
+        self.accounts
+            .values()
+            .flatten()
+            .filter(|(pubkey, account)| attributes(pubkey, &account.data, &parents).matches(filter))
+            .map(|(pubkey, account)| (pubkey, account))
+            .collect()
+    }
+
+    fn accounts_of<T: Discriminator>(&self) -> &[(Pubkey, Account)] {
+        self.accounts
+            .get(T::DISCRIMINATOR)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+fn deserialize<T: AccountDeserialize>(data: &[u8]) -> Option<T> {
+    T::try_deserialize(&mut &data[..]).ok()
+}
+
+fn to_pubkey(key: impl AsRef<[u8]>) -> Pubkey {
+    Pubkey::try_from(key.as_ref()).expect("32 byte pubkey")
+}
+
+fn game_attributes(game_id: impl AsRef<[u8]>) -> AccountAttributes {
+    AccountAttributes {
+        game_id: Some(to_pubkey(game_id)),
+        ..Default::default()
+    }
+}
+
+/// Attributes of the account, `parents` resolves attributes inherited from planets, starbases
+/// and fleets.
+fn attributes(
+    pubkey: &Pubkey,
+    data: &[u8],
+    parents: &HashMap<Pubkey, AccountAttributes>,
+) -> AccountAttributes {
+    let parent = |parent: Pubkey| parents.get(&parent);
+    let parent_sectors = |key: Pubkey| parent(key).and_then(|parent| parent.sectors.clone());
+
+    if data.starts_with(Fleet::DISCRIMINATOR) {
+        let Ok(FleetWithState(fleet, state)) = FleetWithState::deserialize(&mut &data[..]) else {
+            return AccountAttributes::default();
+        };
+        let sectors = match state {
+            FleetState::StarbaseLoadingBay(state) => parent_sectors(to_pubkey(state.starbase)),
+            FleetState::Idle(state) => Some(vec![state.sector]),
+            FleetState::MineAsteroid(state) => parent_sectors(to_pubkey(state.asteroid)),
+            FleetState::MoveWarp(state) => Some(vec![state.from_sector, state.to_sector]),
+            FleetState::MoveSubwarp(state) => Some(vec![
+                state.from_sector,
+                state.to_sector,
+                state.current_sector,
+            ]),
+            FleetState::Respawn(state) => Some(vec![state.sector]),
+        };
+        AccountAttributes {
+            game_id: Some(to_pubkey(fleet.game_id)),
+            sectors,
+            owner_profile: Some(to_pubkey(fleet.owner_profile)),
+        }
+    } else if let Some(fleet_ships) = deserialize::<FleetShips>(data) {
+        let fleet = to_pubkey(fleet_ships.fleet);
+        AccountAttributes {
+            game_id: parent(fleet).and_then(|fleet| fleet.game_id),
+            sectors: parent_sectors(fleet),
+            owner_profile: parent(fleet).and_then(|fleet| fleet.owner_profile),
+        }
+    } else if let Some(disbanded_fleet) = deserialize::<DisbandedFleet>(data) {
+        AccountAttributes {
+            game_id: Some(to_pubkey(disbanded_fleet.game_id)),
+            sectors: parent_sectors(to_pubkey(disbanded_fleet.starbase)),
+            owner_profile: Some(to_pubkey(disbanded_fleet.owner_profile)),
+        }
+    } else if let Some(starbase_player) = deserialize::<StarbasePlayer>(data) {
+        AccountAttributes {
+            game_id: Some(to_pubkey(starbase_player.game_id)),
+            sectors: parent_sectors(to_pubkey(starbase_player.starbase)),
+            owner_profile: Some(to_pubkey(starbase_player.player_profile)),
+        }
+    } else if let Some(sage_player_profile) = deserialize::<SagePlayerProfile>(data) {
+        AccountAttributes {
+            owner_profile: Some(to_pubkey(sage_player_profile.player_profile)),
+            ..game_attributes(sage_player_profile.game_id)
+        }
+    } else if let Some(player_crew_record) = deserialize::<PlayerCrewRecord>(data) {
+        AccountAttributes {
+            owner_profile: Some(to_pubkey(player_crew_record.player_profile)),
+            ..game_attributes(player_crew_record.game_id)
+        }
+    } else if let Some(resource) = deserialize::<Resource>(data) {
+        AccountAttributes {
+            sectors: parent_sectors(to_pubkey(resource.location)),
+            ..game_attributes(resource.game_id)
+        }
+    } else if let Some(sector) = deserialize::<Sector>(data) {
+        AccountAttributes {
+            sectors: Some(vec![sector.coordinates]),
+            ..game_attributes(sector.game_id)
+        }
+    } else if let Some(starbase) = deserialize::<Starbase>(data) {
+        AccountAttributes {
+            sectors: Some(vec![starbase.sector]),
+            ..game_attributes(starbase.game_id)
+        }
+    } else if let Some(planet) = deserialize::<Planet>(data) {
+        AccountAttributes {
+            sectors: Some(vec![planet.sector]),
+            ..game_attributes(planet.game_id)
+        }
+    } else if let Some(star) = deserialize::<Star>(data) {
+        AccountAttributes {
+            sectors: Some(vec![star.sector]),
+            ..game_attributes(star.game_id)
+        }
+    } else if data.starts_with(Game::DISCRIMINATOR) {
+        game_attributes(pubkey)
+    } else if let Some(game_state) = deserialize::<GameState>(data) {
+        game_attributes(game_state.game_id)
+    } else if let Some(mine_item) = deserialize::<MineItem>(data) {
+        game_attributes(mine_item.game_id)
+    } else if let Some(progression_config) = deserialize::<ProgressionConfig>(data) {
+        game_attributes(progression_config.game_id)
+    } else if let Some(sage_crew_config) = deserialize::<SageCrewConfig>(data) {
+        game_attributes(sage_crew_config.game_id)
+    } else if let Some(ship) = deserialize::<Ship>(data) {
+        game_attributes(ship.game_id)
+    } else if let Some(tracker) = deserialize::<SurveyDataUnitTracker>(data) {
+        game_attributes(tracker.game_id)
+    } else {
+        AccountAttributes::default()
+    }
+}
@@ -0,0 +1,152 @@
+//! SAGE account snapshots, as written by `examples/snapshot.rs` (`data/sage.bin.gz`).
+//!
+//! A snapshot is a gzip compressed stream of [`SNAPSHOT_MAGIC`], the format version, a
+//! [`SnapshotHeader`] and bincode encoded sections of accounts, each led by its account
+//! discriminator and count. Use
+//! [`SnapshotWriter`] and [`SnapshotReader`] to stream large snapshots, [`Snapshot`] to hold one
+//! in memory. Snapshots written before the header was introduced are read as version `0`.
+mod diff;
+mod fields;
+mod format;
+mod load;
+
+pub use diff::*;
+pub use format::*;
+pub use load::*;
+
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::File,
+    io::{BufReader, Read, Write},
+    path::Path,
+};
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io(std::io::Error),
+    Encode(bincode::error::EncodeError),
+    Decode(bincode::error::DecodeError),
+    InvalidPubkey(String),
+    /// The snapshot was written by a newer version of the format.
+    UnsupportedVersion(u16),
+    /// The accounts do not match the snapshot header.
+    Invalid(String),
+    SetAccount(Pubkey, String),
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::Io(err) => write!(f, "snapshot io error: {}", err),
+            SnapshotError::Encode(err) => write!(f, "failed to encode snapshot: {}", err),
+            SnapshotError::Decode(err) => write!(f, "failed to decode snapshot: {}", err),
+            SnapshotError::InvalidPubkey(pubkey) => write!(f, "invalid pubkey `{}`", pubkey),
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "unsupported snapshot version {}", version)
+            }
+            SnapshotError::Invalid(err) => write!(f, "invalid snapshot: {}", err),
+            SnapshotError::SetAccount(pubkey, err) => {
+                write!(f, "failed to set account {}: {}", pubkey, err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+impl From<std::io::Error> for SnapshotError {
+    fn from(err: std::io::Error) -> Self {
+        SnapshotError::Io(err)
+    }
+}
+
+impl From<bincode::error::EncodeError> for SnapshotError {
+    fn from(err: bincode::error::EncodeError) -> Self {
+        SnapshotError::Encode(err)
+    }
+}
+
+impl From<bincode::error::DecodeError> for SnapshotError {
+    fn from(err: bincode::error::DecodeError) -> Self {
+        SnapshotError::Decode(err)
+    }
+}
+
+/// An in-memory snapshot, accounts by 8-byte account discriminator.
+#[derive(Debug, Clone, Default)]
+pub struct Snapshot {
+    /// Header of the snapshot, `counts` is ignored when written.
+    pub header: SnapshotHeader,
+    pub accounts: HashMap<Vec<u8>, Vec<(Pubkey, Account)>>,
+}
+
+impl Snapshot {
+    pub fn insert(&mut self, discriminator: &[u8], accounts: Vec<(Pubkey, Account)>) {
+        self.accounts
+            .entry(discriminator.to_vec())
+            .or_default()
+            .extend(accounts);
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.values().map(Vec::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Writes the snapshot in the current format version.
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<W, SnapshotError> {
+        let mut discriminators: Vec<&Vec<u8>> = self.accounts.keys().collect();
+        discriminators.sort();
+
+        let mut snapshot_writer = SnapshotWriter::new(writer, &self.header)?;
+        for discriminator in discriminators {
+            snapshot_writer.write_section(discriminator, &self.accounts[discriminator])?;
+        }
+        snapshot_writer.finish()
+    }
+
+    pub fn read_from<R: Read>(reader: R) -> Result<Self, SnapshotError> {
+        let mut snapshot_reader = SnapshotReader::new(reader)?;
+
+        let mut accounts: HashMap<Vec<u8>, Vec<(Pubkey, Account)>> = HashMap::new();
+        for entry in snapshot_reader.by_ref() {
+            let entry = entry?;
+            accounts
+                .entry(entry.discriminator)
+                .or_default()
+                .push((entry.pubkey, entry.account));
+        }
+
+        Ok(Snapshot {
+            header: snapshot_reader.header().clone(),
+            accounts,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        self.write_to(Vec::new())
+    }
+
+    pub fn from_bytes(compressed_data: &[u8]) -> Result<Self, SnapshotError> {
+        Self::read_from(compressed_data)
+    }
+
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        let mut file = self.write_to(File::create(path)?)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+}
@@ -0,0 +1,520 @@
+//! LiteSVM test harness, enabled with the `testing` feature.
+//!
+//! ```ignore
+//! let mut test_world = TestWorld::new();
+//! let player = test_world.new_player(Faction::MUD);
+//! let fleet = test_world.new_fleet(&player, &[("Test Ship", 2)]);
+//! ```
+use anchor_lang::AccountDeserialize;
+use litesvm::LiteSVM;
+use solana_sdk::{feature_set::FeatureSet, pubkey::Pubkey, signature::Keypair, signer::Signer};
+
+use staratlas_profile_faction::typedefs::Faction;
+use staratlas_sage::state::{Fleet, Starbase};
+
+use crate::{
+    fleet, player, profile, token,
+    tx::InstructionBuilder,
+    world::{
+        CargoStatsConfig, GameConfig, MineItemConfig, MiscStatsConfig, MovementStatsConfig,
+        SectorConfig, ShipConfig, SizeClassConfig, StarbaseConfig, SurveyConfig, World,
+        WorldConfig,
+    },
+};
+
+pub const CARGO_PROGRAM_BYTES: &[u8] =
+    include_bytes!("../../../programs/cargo/Cargo2VNTPPTi9c1vq1Jw5d3BWUNr18MjRtSupAghKEk.so");
+
+pub const CREW_PROGRAM_BYTES: &[u8] =
+    include_bytes!("../../../programs/crew/CREWiq8qbxvo4SKkAFpVnc6t7CRQC4tAAscsNAENXgrJ.so");
+
+pub const PLAYER_PROFILE_PROGRAM_BYTES: &[u8] = include_bytes!(
+    "../../../programs/player-profile/pprofELXjL5Kck7Jn5hCpwAL82DpTkSYBENzahVtbc9.so"
+);
+
+pub const PROFILE_FACTION_PROGRAM_BYTES: &[u8] = include_bytes!(
+    "../../../programs/profile-faction/pFACSRuobDmvfMKq1bAzwj27t6d2GJhSCHb1VcfnRmq.so"
+);
+
+pub const SAGE_PROGRAM_BYTES: &[u8] =
+    include_bytes!("../../../programs/sage/SAGE2HAwep459SNq61LHvjxPk4pLPEJLoMETef7f7EE.so");
+
+/// Name of the ship registered by [`TestWorld::new`].
+pub const TEST_SHIP: &str = "Test Ship";
+
+/// Name of the mine item scans spend in [`TestWorld::new`], the game's food mint.
+pub const TEST_FOOD: &str = "Food";
+
+/// Name of the mine item moves burn in [`TestWorld::new`], the game's fuel mint.
+pub const TEST_FUEL: &str = "Fuel";
+
+/// A LiteSVM with the SPL and Star Atlas programs loaded.
+pub fn new_svm() -> LiteSVM {
+    let mut svm = LiteSVM::default()
+        .with_feature_set(FeatureSet::all_enabled())
+        .with_builtins()
+        .with_lamports(1_000_000_000_000_000)
+        .with_sysvars()
+        .with_spl_programs();
+
+    svm.add_program(staratlas_cargo::ID, CARGO_PROGRAM_BYTES);
+    svm.add_program(staratlas_crew::ID, CREW_PROGRAM_BYTES);
+    svm.add_program(staratlas_player_profile::ID, PLAYER_PROFILE_PROGRAM_BYTES);
+    svm.add_program(staratlas_profile_faction::ID, PROFILE_FACTION_PROGRAM_BYTES);
+    svm.add_program(staratlas_sage::ID, SAGE_PROGRAM_BYTES);
+
+    svm
+}
+
+/// The default test world, a sector with a level 1 starbase per faction, [`TEST_SHIP`],
+/// [`TEST_FUEL`] and a survey data unit tracker spending [`TEST_FOOD`].
+pub fn default_config() -> WorldConfig {
+    let factions = [
+        (Faction::MUD, [0, 10]),
+        (Faction::ONI, [10, -10]),
+        (Faction::Ustur, [-10, -10]),
+    ];
+
+    WorldConfig {
+        game: GameConfig {
+            fuel: Some(TEST_FUEL.into()),
+            food: Some(TEST_FOOD.into()),
+            ..Default::default()
+        },
+        sectors: factions
+            .iter()
+            .map(|(faction, coordinates)| SectorConfig {
+                name: format!("{} Sector", faction),
+                coordinates: *coordinates,
+                connections: Vec::new(),
+            })
+            .collect(),
+        starbases: factions
+            .iter()
+            .map(|(faction, _)| StarbaseConfig {
+                name: format!("{} Starbase", faction),
+                sector: format!("{} Sector", faction),
+                sub_coordinates: [0, 0],
+                level: 1,
+                faction: faction.clone(),
+            })
+            .collect(),
+        ships: vec![ShipConfig {
+            name: TEST_SHIP.into(),
+            size_class: SizeClassConfig::Small,
+            mint: None,
+            movement_stats: MovementStatsConfig {
+                subwarp_speed: 1000,
+                warp_speed: 50000,
+                max_warp_distance: 300,
+                warp_cool_down: 30,
+                subwarp_fuel_consumption_rate: 10,
+                warp_fuel_consumption_rate: 100,
+                planet_exit_fuel_amount: 50,
+            },
+            cargo_stats: CargoStatsConfig {
+                cargo_capacity: 1000,
+                fuel_capacity: 1000,
+                ammo_capacity: 1000,
+                mining_rate: 10,
+                ..Default::default()
+            },
+            misc_stats: MiscStatsConfig {
+                scan_cool_down: 60,
+                sdu_per_scan: 10,
+                scan_cost: 1,
+                ..Default::default()
+            },
+        }],
+        mine_items: [TEST_FOOD, TEST_FUEL]
+            .iter()
+            .map(|name| MineItemConfig {
+                name: name.to_string(),
+                resource_hardness: 0,
+                mint: None,
+                decimals: 0,
+            })
+            .collect(),
+        survey: Some(SurveyConfig {
+            resource: TEST_FOOD.into(),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// A player created with [`TestWorld::new_player`].
+#[derive(Debug)]
+pub struct TestPlayer {
+    pub keypair: Keypair,
+    pub faction: Faction,
+    pub profile: Pubkey,
+    pub profile_faction: Pubkey,
+    pub sage_player_profile: Pubkey,
+    /// Starbase of the player's faction, if the world has one.
+    pub starbase: Option<Pubkey>,
+    pub starbase_player: Option<Pubkey>,
+}
+
+/// A fleet created with [`TestWorld::new_fleet`].
+#[derive(Debug, Clone, Copy)]
+pub struct TestFleet {
+    pub fleet: Pubkey,
+    pub fleet_ships: Pubkey,
+}
+
+pub struct TestWorld {
+    pub svm: LiteSVM,
+    /// Funded payer for all accounts created by the harness.
+    pub funder: Keypair,
+    pub world: World,
+    fleet_count: usize,
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestWorld {
+    pub fn new() -> Self {
+        Self::from_config(&default_config())
+    }
+
+    /// Panics if the world can not be materialized.
+    pub fn from_config(config: &WorldConfig) -> Self {
+        let mut svm = new_svm();
+
+        let funder = Keypair::new();
+        svm.airdrop(&funder.pubkey(), 1_000_000_000_000)
+            .expect("airdrop funder");
+
+        let world = config.build(&mut svm, &funder).expect("build test world");
+
+        TestWorld {
+            svm,
+            funder,
+            world,
+            fleet_count: 0,
+        }
+    }
+
+    /// Airdrops `lamports` to a new keypair.
+    pub fn new_payer(&mut self, lamports: u64) -> Keypair {
+        let payer = Keypair::new();
+        self.svm
+            .airdrop(&payer.pubkey(), lamports)
+            .expect("airdrop payer");
+        payer
+    }
+
+    /// Creates a funded player of `faction`, registered at the first starbase of that faction.
+    pub fn new_player(&mut self, faction: Faction) -> TestPlayer {
+        let keypair = self.new_payer(10_000_000_000);
+        let profile = profile::CreateProfile::new(&keypair, &keypair)
+            .send(&mut self.svm)
+            .expect("create player profile");
+
+        let profile_faction =
+            profile::ChooseFaction::new(&keypair, &profile, faction.clone(), &keypair)
+                .send(&mut self.svm)
+                .expect("choose faction");
+
+        let sage_player_profile = player::RegisterSagePlayerProfile::new(
+            &profile,
+            &self.world.game,
+            &self.world.game_state,
+            &keypair,
+        )
+        .send(&mut self.svm)
+        .expect("register sage player profile");
+
+        let starbase = self.faction_starbase(&faction);
+        let starbase_player = starbase.map(|starbase| {
+            player::RegisterStarbasePlayer::new(
+                &self.world.game,
+                &self.world.game_state,
+                &sage_player_profile,
+                &profile_faction,
+                &starbase,
+                &keypair,
+            )
+            .send(&mut self.svm)
+            .expect("register starbase player")
+        });
+
+        TestPlayer {
+            keypair,
+            faction,
+            profile,
+            profile_faction,
+            sage_player_profile,
+            starbase,
+            starbase_player,
+        }
+    }
+
+    /// Mints `ships` (ship name, amount) to the player, escrows them at the player's starbase
+    /// and creates a fleet from them.
+    pub fn new_fleet(&mut self, player: &TestPlayer, ships: &[(&str, u8)]) -> TestFleet {
+        let starbase = player.starbase.expect("player has no starbase");
+        let starbase_player = player
+            .starbase_player
+            .expect("player has no starbase player");
+
+        self.fleet_count += 1;
+        let fleet_label = format!("Test Fleet {}", self.fleet_count);
+
+        let mut fleet: Option<TestFleet> = None;
+        for (ship_name, amount) in ships.iter() {
+            let ship = self.world.ships[*ship_name];
+            let ship_mint = self.world.mints[*ship_name];
+
+            let origin_token_account = token::create_associated_token_account(
+                &mut self.svm,
+                &self.funder,
+                &player.keypair.pubkey(),
+                &ship_mint,
+            )
+            .expect("create ship token account");
+            token::mint_tokens(
+                &mut self.svm,
+                &self.funder,
+                &ship_mint,
+                &self.world.authority,
+                &origin_token_account,
+                *amount as u64,
+            )
+            .expect("mint ships");
+            token::create_associated_token_account(
+                &mut self.svm,
+                &self.funder,
+                &starbase_player,
+                &ship_mint,
+            )
+            .expect("create ship escrow token account");
+
+            let existing_escrow_index = self.ship_escrow_index(&starbase_player, &ship);
+            player::AddShipEscrow::new(
+                &player.keypair,
+                &player.profile,
+                &player.profile_faction,
+                &self.world.game,
+                &self.world.game_state,
+                &player.sage_player_profile,
+                &starbase,
+                &starbase_player,
+                &ship,
+                &ship_mint,
+                &origin_token_account,
+                &self.funder,
+            )
+            .set_ship_amount(*amount as u64)
+            .set_index(existing_escrow_index)
+            .send(&mut self.svm)
+            .expect("add ship escrow");
+
+            let ship_escrow_index = self
+                .ship_escrow_index(&starbase_player, &ship)
+                .expect("ship escrow");
+
+            match fleet {
+                None => {
+                    let fleet_pk = fleet::CreateFleet::new(
+                        &player.keypair,
+                        &player.profile,
+                        &player.profile_faction,
+                        &self.world.game,
+                        &self.world.game_state,
+                        &starbase,
+                        &starbase_player,
+                        &ship,
+                        &self.world.cargo_stats_definition,
+                        &self.funder,
+                    )
+                    .set_fleet_label(fleet_label.clone())
+                    .set_ship_amount(*amount)
+                    .set_ship_escrow_index(ship_escrow_index)
+                    .send(&mut self.svm)
+                    .expect("create fleet");
+
+                    fleet = Some(TestFleet {
+                        fleet: fleet_pk,
+                        fleet_ships: fleet::find_fleet_ships_pda(&fleet_pk).0,
+                    });
+                }
+                Some(TestFleet {
+                    fleet: fleet_pk, ..
+                }) => {
+                    fleet::AddShipToFleet::new(
+                        &player.keypair,
+                        &player.profile,
+                        &player.profile_faction,
+                        &fleet_pk,
+                        &self.world.game,
+                        &self.world.game_state,
+                        &ship,
+                        &starbase,
+                        &starbase_player,
+                        &self.funder,
+                    )
+                    .set_ship_amount(*amount)
+                    .set_ship_escrow_index(ship_escrow_index)
+                    .send(&mut self.svm)
+                    .expect("add ship to fleet");
+                }
+            }
+        }
+
+        fleet.expect("no ships for fleet")
+    }
+
+    /// Mints `amount` of the mine item `name` to the player, deposits it into a new cargo pod at
+    /// the player's starbase and from there into `cargo_pod` of the docked fleet, e.g. its fuel
+    /// tank.
+    pub fn deposit_cargo(
+        &mut self,
+        player: &TestPlayer,
+        fleet: &TestFleet,
+        cargo_pod: &Pubkey,
+        name: &str,
+        amount: u64,
+    ) {
+        let starbase = player.starbase.expect("player has no starbase");
+        let starbase_player = player
+            .starbase_player
+            .expect("player has no starbase player");
+        let mint = self.world.mints[name];
+        let cargo_type = self.world.cargo_types[name];
+
+        let token_account = token::create_associated_token_account(
+            &mut self.svm,
+            &self.funder,
+            &player.keypair.pubkey(),
+            &mint,
+        )
+        .expect("create cargo token account");
+        token::mint_tokens(
+            &mut self.svm,
+            &self.funder,
+            &mint,
+            &self.world.authority,
+            &token_account,
+            amount,
+        )
+        .expect("mint cargo");
+
+        let starbase_cargo_pod = player::CreateCargoPod::new(
+            &player.keypair,
+            &player.profile,
+            &player.profile_faction,
+            &self.world.game,
+            &self.world.game_state,
+            &starbase,
+            &starbase_player,
+            &self.world.cargo_stats_definition,
+            &self.funder,
+        )
+        .send(&mut self.svm)
+        .expect("create starbase cargo pod");
+        for owner in [&starbase_cargo_pod, cargo_pod] {
+            token::create_associated_token_account(&mut self.svm, &self.funder, owner, &mint)
+                .expect("create cargo pod token account");
+        }
+
+        player::DepositCargoToGame::new(
+            &player.keypair,
+            &player.profile,
+            &player.profile_faction,
+            &self.world.game,
+            &self.world.game_state,
+            &starbase,
+            &starbase_player,
+            &starbase_cargo_pod,
+            &cargo_type,
+            &self.world.cargo_stats_definition,
+            &mint,
+            &self.funder,
+        )
+        .set_amount(amount)
+        .send(&mut self.svm)
+        .expect("deposit cargo to game");
+
+        fleet::DepositCargoToFleet::new(
+            &player.keypair,
+            &player.profile,
+            &player.profile_faction,
+            &fleet.fleet,
+            &self.world.game,
+            &self.world.game_state,
+            &starbase,
+            &starbase_player,
+            &starbase_cargo_pod,
+            cargo_pod,
+            &cargo_type,
+            &self.world.cargo_stats_definition,
+            &mint,
+            &self.funder,
+        )
+        .set_amount(amount)
+        .send(&mut self.svm)
+        .expect("deposit cargo to fleet");
+    }
+
+    /// Undocks the fleet from the player's starbase, it becomes idle in the starbase's sector.
+    pub fn undock(&mut self, player: &TestPlayer, fleet: &TestFleet) {
+        let starbase = player.starbase.expect("player has no starbase");
+        let starbase_player = player
+            .starbase_player
+            .expect("player has no starbase player");
+
+        fleet::LoadingBayToIdle::new(
+            &player.keypair,
+            &player.profile,
+            &player.profile_faction,
+            &fleet.fleet,
+            &self.world.game,
+            &self.world.game_state,
+            &starbase,
+            &starbase_player,
+            &self.funder,
+        )
+        .send(&mut self.svm)
+        .expect("undock fleet");
+    }
+
+    /// The fleet account.
+    pub fn fleet(&self, fleet: &TestFleet) -> Fleet {
+        self.svm
+            .get_account(&fleet.fleet)
+            .and_then(|acc| Fleet::try_deserialize(&mut &acc.data[..]).ok())
+            .expect("fleet account")
+    }
+
+    /// First starbase (by address) of the faction.
+    fn faction_starbase(&self, faction: &Faction) -> Option<Pubkey> {
+        let faction_id = u8::from(faction.clone());
+        let mut starbases: Vec<Pubkey> = self
+            .world
+            .starbases
+            .values()
+            .filter(|starbase_pk| {
+                self.svm
+                    .get_account(starbase_pk)
+                    .and_then(|acc| Starbase::try_deserialize(&mut &acc.data[..]).ok())
+                    .is_some_and(|starbase| starbase.faction == faction_id)
+            })
+            .copied()
+            .collect();
+        starbases.sort();
+        starbases.into_iter().next()
+    }
+
+    fn ship_escrow_index(&self, starbase_player: &Pubkey, ship: &Pubkey) -> Option<u32> {
+        player::get_ship_escrows(&self.svm, starbase_player)
+            .iter()
+            .position(|escrow| escrow.ship.as_ref() == ship.as_ref())
+            .map(|index| index as u32)
+    }
+}
@@ -0,0 +1,99 @@
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::{
+    address::get_associated_token_address, instruction::create_associated_token_account_idempotent,
+};
+use spl_token::{
+    instruction::{initialize_mint2, mint_to},
+    state::Mint,
+    ID as TOKEN_PROGRAM_ID,
+};
+
+// https://github.com/LiteSVM/litesvm/blob/master/crates/token/src/create_mint.rs
+pub fn create_mint(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    mint_kp: &Keypair,
+    decimals: u8,
+    authority_pk: &Pubkey,
+) -> Result<Pubkey, FailedTransactionMetadata> {
+    let mint_size = Mint::LEN;
+    let mint_pk = mint_kp.pubkey();
+    let payer_pk = payer_kp.pubkey();
+
+    let ix1 = system_instruction::create_account(
+        &payer_pk,
+        &mint_pk,
+        svm.minimum_balance_for_rent_exemption(mint_size),
+        mint_size as u64,
+        &TOKEN_PROGRAM_ID,
+    );
+
+    let ix2 = initialize_mint2(&TOKEN_PROGRAM_ID, &mint_pk, authority_pk, None, decimals)?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix1, ix2],
+        Some(&payer_pk),
+        &[payer_kp, mint_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(mint_pk)
+}
+
+/// Creates the associated token account of `owner_pk` for `mint_pk` unless it already exists.
+pub fn create_associated_token_account(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    owner_pk: &Pubkey,
+    mint_pk: &Pubkey,
+) -> Result<Pubkey, FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+
+    let ix =
+        create_associated_token_account_idempotent(&payer_pk, owner_pk, mint_pk, &TOKEN_PROGRAM_ID);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer_pk),
+        &[payer_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(get_associated_token_address(owner_pk, mint_pk))
+}
+
+pub fn mint_tokens(
+    svm: &mut LiteSVM,
+    payer_kp: &Keypair,
+    mint_pk: &Pubkey,
+    authority_kp: &Keypair,
+    destination_pk: &Pubkey,
+    amount: u64,
+) -> Result<(), FailedTransactionMetadata> {
+    let payer_pk = payer_kp.pubkey();
+
+    let ix = mint_to(
+        &TOKEN_PROGRAM_ID,
+        mint_pk,
+        destination_pk,
+        &authority_kp.pubkey(),
+        &[],
+        amount,
+    )?;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer_pk),
+        &[payer_kp, authority_kp],
+        svm.latest_blockhash(),
+    );
+    svm.send_transaction(tx)?;
+
+    Ok(())
+}
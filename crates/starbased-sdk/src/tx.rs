@@ -0,0 +1,346 @@
+//! Several builders in one transaction.
+//!
+//! ```ignore
+//! let pubkeys = TxBuilder::new(&funder_kp)
+//!     .add(RegisterSector::new(&authority_kp, &profile_pk, &discoverer_pk, &game_pk, &funder_kp))
+//!     .add(RegisterStarbase::new(/* .. */))
+//!     .add(RegisterStarbasePlayer::new(/* .. */))
+//!     .send(&mut svm)?;
+//! ```
+//!
+//! The builders' compute budgets are merged, see [`crate::compute_budget`]. A single builder is
+//! sent in its own transaction with [`InstructionBuilder::send`].
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    message::{v0, AddressLookupTableAccount, CompileError, Message, VersionedMessage},
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::{Signer, SignerError},
+    transaction::{Transaction, VersionedTransaction},
+};
+use std::{fmt, ops::Deref};
+
+use crate::{
+    batch::MAX_COMPUTE_UNITS,
+    compute_budget::{ComputeBudget, ComputeUnitLimit},
+    sender::Sender,
+    simulation::SimulationReport,
+};
+
+/// A keypair signing a builder's instructions, builders generating a keypair own it.
+pub enum TxSigner<'a> {
+    Borrowed(&'a Keypair),
+    Owned(Keypair),
+}
+
+impl<'a> From<&'a Keypair> for TxSigner<'a> {
+    fn from(keypair: &'a Keypair) -> Self {
+        TxSigner::Borrowed(keypair)
+    }
+}
+
+impl From<Keypair> for TxSigner<'_> {
+    fn from(keypair: Keypair) -> Self {
+        TxSigner::Owned(keypair)
+    }
+}
+
+impl Deref for TxSigner<'_> {
+    type Target = Keypair;
+
+    fn deref(&self) -> &Keypair {
+        match self {
+            TxSigner::Borrowed(keypair) => keypair,
+            TxSigner::Owned(keypair) => keypair,
+        }
+    }
+}
+
+/// A transaction that can't be built from the added builders.
+#[derive(Debug, PartialEq, Eq)]
+pub enum TxError {
+    /// A signer the instructions require was not added.
+    Signer(SignerError),
+    /// The v0 message doesn't compile, e.g. an account index overflows.
+    Compile(CompileError),
+}
+
+impl fmt::Display for TxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TxError::Signer(err) => write!(f, "signing transaction: {}", err),
+            TxError::Compile(err) => write!(f, "compiling v0 message: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for TxError {}
+
+impl From<SignerError> for TxError {
+    fn from(err: SignerError) -> Self {
+        TxError::Signer(err)
+    }
+}
+
+impl From<CompileError> for TxError {
+    fn from(err: CompileError) -> Self {
+        TxError::Compile(err)
+    }
+}
+
+/// What a builder's `send` returns, built from its derived pubkeys.
+pub trait FromPubkeys {
+    fn from_pubkeys(pubkeys: Vec<Pubkey>) -> Self;
+}
+
+/// Builders that don't create an account.
+impl FromPubkeys for () {
+    fn from_pubkeys(_pubkeys: Vec<Pubkey>) -> Self {}
+}
+
+/// The first derived pubkey, the account the builder creates.
+impl FromPubkeys for Pubkey {
+    fn from_pubkeys(pubkeys: Vec<Pubkey>) -> Self {
+        pubkeys[0]
+    }
+}
+
+pub trait InstructionBuilder<'a> {
+    type Output: FromPubkeys;
+
+    /// Pays the fees when the builder is sent in its own transaction.
+    fn payer(&self) -> &'a Keypair;
+
+    /// Merged into the compute budget of the transaction.
+    fn compute_budget(&self) -> ComputeBudget {
+        ComputeBudget::default()
+    }
+
+    /// The instructions, the keypairs signing them (fee payer included) and the pubkeys of the
+    /// accounts they derive, in the order `send` returns them.
+    fn instructions(self) -> (Vec<Instruction>, Vec<TxSigner<'a>>, Vec<Pubkey>);
+
+    /// Sends the builder in its own transaction, paid by [`InstructionBuilder::payer`].
+    fn send<S: Sender>(self, sender: &mut S) -> Result<Self::Output, S::Error>
+    where
+        Self: Sized,
+    {
+        let pubkeys = TxBuilder::new(self.payer()).add(self).send(sender)?;
+        Ok(Self::Output::from_pubkeys(pubkeys))
+    }
+
+    /// Dry run of [`InstructionBuilder::send`].
+    fn simulate<S: Sender>(self, sender: &mut S) -> Result<SimulationReport, S::Error>
+    where
+        Self: Sized,
+    {
+        TxBuilder::new(self.payer()).add(self).simulate(sender)
+    }
+}
+
+pub struct TxBuilder<'a> {
+    payer_kp: &'a Keypair,
+    instructions: Vec<Instruction>,
+    signers: Vec<TxSigner<'a>>,
+    pubkeys: Vec<Pubkey>,
+    compute_budget: ComputeBudget,
+}
+
+impl<'a> TxBuilder<'a> {
+    pub fn new(payer_kp: &'a Keypair) -> Self {
+        TxBuilder {
+            payer_kp,
+            instructions: vec![],
+            signers: vec![],
+            pubkeys: vec![],
+            compute_budget: ComputeBudget::default(),
+        }
+    }
+
+    /// Replaces the compute budget merged from the builders added so far.
+    pub fn set_compute_budget(mut self, compute_budget: ComputeBudget) -> Self {
+        self.compute_budget = compute_budget;
+        self
+    }
+
+    /// Appends the builder's instructions, signers already added are skipped.
+    pub fn add(mut self, builder: impl InstructionBuilder<'a>) -> Self {
+        let compute_budget = builder.compute_budget();
+        let (instructions, signers, pubkeys) = builder.instructions();
+        self.compute_budget = self.compute_budget.merge_counting(
+            self.instructions.len(),
+            compute_budget,
+            instructions.len(),
+        );
+        self.add_instructions(instructions, signers, pubkeys)
+    }
+
+    pub fn add_instructions(
+        mut self,
+        instructions: Vec<Instruction>,
+        signers: Vec<TxSigner<'a>>,
+        pubkeys: Vec<Pubkey>,
+    ) -> Self {
+        self.instructions.extend(instructions);
+        for signer in signers {
+            let signer_pk = signer.pubkey();
+            if !self.signers.iter().any(|kp| kp.pubkey() == signer_pk) {
+                self.signers.push(signer);
+            }
+        }
+        self.pubkeys.extend(pubkeys);
+        self
+    }
+
+    /// The builders' instructions, without the compute budget ones.
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    pub fn compute_budget(&self) -> ComputeBudget {
+        self.compute_budget
+    }
+
+    /// Simulates the transaction to replace an auto compute unit limit. A failing simulation sets
+    /// [`MAX_COMPUTE_UNITS`], sending then reports the error.
+    pub fn resolve_compute_budget<S: Sender>(
+        &mut self,
+        sender: &mut S,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<(), S::Error> {
+        if !self.compute_budget.is_auto() {
+            return Ok(());
+        }
+
+        let block_hash = sender.latest_blockhash()?;
+        let simulation = if lookup_tables.is_empty() {
+            sender.simulate(self.transaction(block_hash)?)?
+        } else {
+            sender.simulate(self.versioned_transaction(block_hash, lookup_tables)?)?
+        };
+        self.compute_budget = match simulation.err {
+            None => self.compute_budget.resolve(simulation.units_consumed),
+            Some(_) => ComputeBudget {
+                unit_limit: Some(ComputeUnitLimit::Fixed(MAX_COMPUTE_UNITS)),
+                ..self.compute_budget
+            },
+        };
+
+        Ok(())
+    }
+
+    /// The compute budget instructions followed by the builders' instructions.
+    fn all_instructions(&self) -> Vec<Instruction> {
+        let mut instructions = self.compute_budget.instructions();
+        instructions.extend(self.instructions.iter().cloned());
+        instructions
+    }
+
+    /// Derived pubkeys of the added builders, in order.
+    pub fn pubkeys(&self) -> &[Pubkey] {
+        &self.pubkeys
+    }
+
+    /// Signs with the payer and the signers the instructions require, builders' fee payers that
+    /// sign nothing else are left out.
+    ///
+    /// Fails when a required signer was not added.
+    pub fn transaction(&self, block_hash: Hash) -> Result<Transaction, TxError> {
+        let payer_pk = self.payer_kp.pubkey();
+        let message = Message::new(&self.all_instructions(), Some(&payer_pk));
+        let signer_pks = &message.account_keys[..message.header.num_required_signatures as usize];
+        let signers = self.signers_for(signer_pks);
+
+        let mut tx = Transaction::new_unsigned(message);
+        tx.try_sign(&signers, block_hash)?;
+        Ok(tx)
+    }
+
+    /// A v0 transaction loading accounts from `lookup_tables`, signed like
+    /// [`TxBuilder::transaction`].
+    ///
+    /// Fails when a required signer was not added or the message can't be compiled.
+    pub fn versioned_transaction(
+        &self,
+        block_hash: Hash,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<VersionedTransaction, TxError> {
+        let payer_pk = self.payer_kp.pubkey();
+        let message = v0::Message::try_compile(
+            &payer_pk,
+            &self.all_instructions(),
+            lookup_tables,
+            block_hash,
+        )?;
+        let signer_pks =
+            message.account_keys[..message.header.num_required_signatures as usize].to_vec();
+
+        VersionedTransaction::try_new(
+            VersionedMessage::V0(message),
+            &self.signers_for(&signer_pks),
+        )
+        .map_err(TxError::Signer)
+    }
+
+    fn signers_for(&self, signer_pks: &[Pubkey]) -> Vec<&Keypair> {
+        let payer_pk = self.payer_kp.pubkey();
+
+        let mut signers: Vec<&Keypair> = vec![self.payer_kp];
+        for signer in &self.signers {
+            let signer_pk = signer.pubkey();
+            if signer_pk != payer_pk && signer_pks.contains(&signer_pk) {
+                signers.push(signer);
+            }
+        }
+        signers
+    }
+
+    /// Dry run of the transaction, reporting the changes of its writable accounts.
+    pub fn simulate<S: Sender>(&self, sender: &mut S) -> Result<SimulationReport, S::Error> {
+        let writable_pks = self.writable_accounts();
+        let mut before = Vec::with_capacity(writable_pks.len());
+        for pubkey in writable_pks.iter() {
+            before.push((*pubkey, sender.get_account(pubkey)?));
+        }
+
+        let block_hash = sender.latest_blockhash()?;
+        let simulation =
+            sender.simulate_with_accounts(self.transaction(block_hash)?, &writable_pks)?;
+
+        Ok(SimulationReport::new(simulation, &before))
+    }
+
+    /// The payer and the accounts written by the instructions, in order.
+    fn writable_accounts(&self) -> Vec<Pubkey> {
+        let mut pubkeys = vec![self.payer_kp.pubkey()];
+        for account in self.instructions.iter().flat_map(|ix| &ix.accounts) {
+            if account.is_writable && !pubkeys.contains(&account.pubkey) {
+                pubkeys.push(account.pubkey);
+            }
+        }
+        pubkeys
+    }
+
+    /// Returns the derived pubkeys of the added builders.
+    pub fn send<S: Sender>(mut self, sender: &mut S) -> Result<Vec<Pubkey>, S::Error> {
+        self.resolve_compute_budget(sender, &[])?;
+        let block_hash = sender.latest_blockhash()?;
+        sender.send(self.transaction(block_hash)?)?;
+
+        Ok(self.pubkeys)
+    }
+
+    /// Sends a v0 transaction, see [`TxBuilder::versioned_transaction`].
+    pub fn send_with_lookup_tables<S: Sender>(
+        mut self,
+        sender: &mut S,
+        lookup_tables: &[AddressLookupTableAccount],
+    ) -> Result<Vec<Pubkey>, S::Error> {
+        self.resolve_compute_budget(sender, lookup_tables)?;
+        let block_hash = sender.latest_blockhash()?;
+        sender.send(self.versioned_transaction(block_hash, lookup_tables)?)?;
+
+        Ok(self.pubkeys)
+    }
+}
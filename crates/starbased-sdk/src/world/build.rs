@@ -0,0 +1,616 @@
+use anchor_lang::AccountDeserialize;
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{pubkey::Pubkey, signature::Keypair, signer::Signer};
+use std::{collections::HashMap, path::Path, str::FromStr};
+
+use staratlas_cargo::ID as CARGO_PROGRAM_ID;
+use staratlas_sage::{
+    state::Game,
+    typedefs::{CargoStats, MiscStats, MovementStats, StarbaseLevelInfoArrayInput},
+    ID as SAGE_PROGRAM_ID,
+};
+
+use super::{ShipConfig, StarbaseLevelConfig, World, WorldConfig, WorldError, WorldPlayer, SDU};
+use crate::{
+    admin::{self, GameMint},
+    cargo,
+    fleet::find_survey_data_unit_tracker_signer_pda,
+    player,
+    profile::{
+        self,
+        permissions::{
+            CARGO_ALL_PERMISSIONS, CARGO_MANAGER_KEY_INDEX, PROFILE_AUTH_PERMISSIONS,
+            SAGE_ALL_PERMISSIONS, SAGE_MANAGER_KEY_INDEX,
+        },
+    },
+    token,
+    tx::InstructionBuilder,
+};
+use staratlas_player_profile::ID as PLAYER_PROFILE_PROGRAM_ID;
+
+/// Reads a world file and materializes it, see [`WorldConfig::build`].
+pub fn load(
+    svm: &mut LiteSVM,
+    path: impl AsRef<Path>,
+    funder_kp: &Keypair,
+) -> Result<World, WorldError> {
+    WorldConfig::from_path(path)?.build(svm, funder_kp)
+}
+
+fn step(step: impl Into<String>) -> impl FnOnce(FailedTransactionMetadata) -> WorldError {
+    let step = step.into();
+    move |meta| WorldError::Transaction {
+        step,
+        meta: Box::new(meta),
+    }
+}
+
+fn lookup(
+    entities: &HashMap<String, Pubkey>,
+    kind: &str,
+    name: &str,
+) -> Result<Pubkey, WorldError> {
+    entities
+        .get(name)
+        .copied()
+        .ok_or_else(|| WorldError::Invalid(format!("unknown {} `{}`", kind, name)))
+}
+
+fn parse_pubkey(pubkey: &str) -> Result<Pubkey, WorldError> {
+    Pubkey::from_str(pubkey)
+        .map_err(|err| WorldError::Invalid(format!("invalid pubkey `{}`: {}", pubkey, err)))
+}
+
+fn ship_stats(ship: &ShipConfig) -> (MovementStats, CargoStats, MiscStats) {
+    let movement = &ship.movement_stats;
+    let cargo = &ship.cargo_stats;
+    let misc = &ship.misc_stats;
+
+    (
+        MovementStats {
+            subwarp_speed: movement.subwarp_speed,
+            warp_speed: movement.warp_speed,
+            max_warp_distance: movement.max_warp_distance,
+            warp_cool_down: movement.warp_cool_down,
+            subwarp_fuel_consumption_rate: movement.subwarp_fuel_consumption_rate,
+            warp_fuel_consumption_rate: movement.warp_fuel_consumption_rate,
+            planet_exit_fuel_amount: movement.planet_exit_fuel_amount,
+        },
+        CargoStats {
+            cargo_capacity: cargo.cargo_capacity,
+            fuel_capacity: cargo.fuel_capacity,
+            ammo_capacity: cargo.ammo_capacity,
+            ammo_consumption_rate: cargo.ammo_consumption_rate,
+            food_consumption_rate: cargo.food_consumption_rate,
+            mining_rate: cargo.mining_rate,
+            upgrade_rate: cargo.upgrade_rate,
+            cargo_transfer_rate: cargo.cargo_transfer_rate,
+            tractor_beam_gather_rate: cargo.tractor_beam_gather_rate,
+        },
+        MiscStats {
+            required_crew: misc.required_crew,
+            passenger_capacity: misc.passenger_capacity,
+            crew_count: 0,
+            rented_crew: 0,
+            respawn_time: misc.respawn_time,
+            scan_cool_down: misc.scan_cool_down,
+            sdu_per_scan: misc.sdu_per_scan,
+            scan_cost: misc.scan_cost,
+            placeholder: 0,
+            placeholder2: 0,
+            placeholder3: 0,
+        },
+    )
+}
+
+impl WorldConfig {
+    /// Creates every entity of the world, `funder_kp` pays for all accounts and must be funded.
+    pub fn build(&self, svm: &mut LiteSVM, funder_kp: &Keypair) -> Result<World, WorldError> {
+        self.validate()?;
+
+        let authority_kp = Keypair::new();
+        let authority_pk = authority_kp.pubkey();
+
+        // superuser profile: auth (0), cargo manager (1), sage manager (2), fleet manager (3)
+        let profile_kp = Keypair::new();
+        let profile_pk = profile::CreateProfile::new(&profile_kp, funder_kp)
+            .add_key(
+                &authority_kp,
+                PLAYER_PROFILE_PROGRAM_ID,
+                PROFILE_AUTH_PERMISSIONS,
+            )
+            .add_key(&authority_kp, CARGO_PROGRAM_ID, CARGO_ALL_PERMISSIONS)
+            .add_key(&authority_kp, SAGE_PROGRAM_ID, SAGE_ALL_PERMISSIONS)
+            .add_key(&authority_kp, SAGE_PROGRAM_ID, SAGE_ALL_PERMISSIONS) // fleet manager
+            .send(svm)
+            .map_err(step("create superuser profile"))?;
+
+        let cargo_stats_definition_kp = Keypair::new();
+        let cargo_stats_definition_pk =
+            cargo::InitDefinition::new(&profile_pk, &cargo_stats_definition_kp, funder_kp)
+                .send(svm)
+                .map_err(step("init cargo stats definition"))?;
+
+        let game_pk = admin::CreateGame::new(&authority_kp, &profile_pk, funder_kp)
+            .send(svm)
+            .map_err(step("create game"))?;
+
+        let game_update_id = svm
+            .get_account(&game_pk)
+            .and_then(|acc| Game::try_deserialize(&mut &acc.data[..]).ok())
+            .map(|game| game.update_id)
+            .ok_or_else(|| WorldError::Invalid("game account not found".into()))?;
+
+        let game_state_pk =
+            admin::CreateGameState::new(&authority_kp, &profile_pk, &game_pk, funder_kp)
+                .set_game_update_id(game_update_id)
+                .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+                .send(svm)
+                .map_err(step("create game state"))?;
+
+        let mut update_game_state = admin::UpdateGameState::new(
+            &authority_kp,
+            &profile_pk,
+            &game_pk,
+            &game_state_pk,
+            funder_kp,
+        )
+        .set_max_fleet_size(self.game.max_fleet_size)
+        .set_profile_key_index(SAGE_MANAGER_KEY_INDEX);
+
+        for level_info in self.starbase_levels() {
+            update_game_state =
+                update_game_state.add_starbase_level_info(StarbaseLevelInfoArrayInput {
+                    level: level_info.level,
+                    faction: level_info.faction.into(),
+                    hp: level_info.hp,
+                    sp: level_info.sp,
+                    sector_ring_available: level_info.sector_ring.into(),
+                    warp_lane_movement_fee: level_info.warp_lane_movement_fee,
+                });
+        }
+
+        update_game_state
+            .send(svm)
+            .map_err(step("update game state"))?;
+
+        // mine item mints, created before the game is updated with its fuel and food mints
+        let mut mints = HashMap::new();
+        for mine_item in self.mine_items.iter() {
+            let mint_pk = match &mine_item.mint {
+                Some(mint) => parse_pubkey(mint)?,
+                None => token::create_mint(
+                    svm,
+                    funder_kp,
+                    &Keypair::new(),
+                    mine_item.decimals,
+                    &authority_pk,
+                )
+                .map_err(step(format!("create mint `{}`", mine_item.name)))?,
+            };
+
+            mints.insert(mine_item.name.clone(), mint_pk);
+        }
+
+        let mut update_game =
+            admin::UpdateGame::new(&authority_kp, &profile_pk, &game_pk, funder_kp)
+                .set_cargo_stats_definition(cargo_stats_definition_pk)
+                .set_profile_key_index(SAGE_MANAGER_KEY_INDEX);
+        if let Some(fuel) = &self.game.fuel {
+            update_game = update_game.set_mint(GameMint::Fuel, lookup(&mints, "mine item", fuel)?);
+        }
+        if let Some(food) = &self.game.food {
+            update_game = update_game.set_mint(GameMint::Food, lookup(&mints, "mine item", food)?);
+        }
+        update_game.send(svm).map_err(step("update game"))?;
+
+        admin::ActivateGameState::new(
+            &authority_kp,
+            &profile_pk,
+            &game_pk,
+            &game_state_pk,
+            funder_kp,
+        )
+        .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+        .send(svm)
+        .map_err(step("activate game state"))?;
+
+        // sectors
+        let mut sectors = HashMap::new();
+        let mut sectors_by_coordinates = HashMap::new();
+        for sector in self.sectors.iter() {
+            let sector_pk = admin::RegisterSector::new(
+                &authority_kp,
+                &profile_pk,
+                &profile_pk, // discoverer
+                &game_pk,
+                funder_kp,
+            )
+            .set_coordinates(sector.coordinates)
+            .set_name(sector.name.clone())
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register sector `{}`", sector.name)))?;
+
+            sectors.insert(sector.name.clone(), sector_pk);
+            sectors_by_coordinates.insert(sector.coordinates, sector_pk);
+        }
+
+        // connections, each pair once
+        let mut connections: Vec<([i64; 2], [i64; 2])> = Vec::new();
+        for sector in self.sectors.iter() {
+            for to in sector.connections.iter() {
+                let from = sector.coordinates;
+                if !connections.contains(&(from, *to)) && !connections.contains(&(*to, from)) {
+                    connections.push((from, *to));
+                }
+            }
+        }
+
+        for (from, to) in connections {
+            let sector1_pk = sectors_by_coordinates.get(&from).copied().ok_or_else(|| {
+                WorldError::Invalid(format!("no sector at coordinates {:?}", from))
+            })?;
+            let sector2_pk = sectors_by_coordinates
+                .get(&to)
+                .copied()
+                .ok_or_else(|| WorldError::Invalid(format!("no sector at coordinates {:?}", to)))?;
+
+            admin::AddConnection::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &sector1_pk,
+                &sector2_pk,
+                funder_kp,
+            )
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("add connection {:?} -> {:?}", from, to)))?;
+        }
+
+        // stars
+        let mut stars = HashMap::new();
+        for star in self.stars.iter() {
+            let sector_pk = lookup(&sectors, "sector", &star.sector)?;
+            let star_kp = Keypair::new();
+            let star_pk = admin::RegisterStar::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &star_kp,
+                &sector_pk,
+                funder_kp,
+            )
+            .set_name(star.name.clone())
+            .set_size(star.size)
+            .set_sub_coordinates(star.sub_coordinates)
+            .set_star_type(star.star_type.into())
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register star `{}`", star.name)))?;
+
+            stars.insert(star.name.clone(), star_pk);
+        }
+
+        // planets
+        let mut planets = HashMap::new();
+        for planet in self.planets.iter() {
+            let sector_pk = lookup(&sectors, "sector", &planet.sector)?;
+            let planet_kp = Keypair::new();
+            let planet_pk = admin::RegisterPlanet::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &planet_kp,
+                &sector_pk,
+                funder_kp,
+            )
+            .set_name(planet.name.clone())
+            .set_size(planet.size)
+            .set_max_hp(planet.max_hp)
+            .set_sub_coordinates(planet.sub_coordinates)
+            .set_planet_type(planet.planet_type.into())
+            .set_position(planet.position)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register planet `{}`", planet.name)))?;
+
+            planets.insert(planet.name.clone(), planet_pk);
+        }
+
+        // mine items
+        let mut mine_items = HashMap::new();
+        for mine_item in self.mine_items.iter() {
+            let mint_pk = lookup(&mints, "mint", &mine_item.name)?;
+            let mine_item_pk = admin::RegisterMineItem::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &mint_pk,
+                funder_kp,
+            )
+            .set_name(mine_item.name.clone())
+            .set_resource_hardness(mine_item.resource_hardness)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register mine item `{}`", mine_item.name)))?;
+
+            mine_items.insert(mine_item.name.clone(), mine_item_pk);
+        }
+
+        // survey data unit tracker, the SDU mint and its bank
+        let mut survey_data_unit_tracker = None;
+        if let Some(survey) = &self.survey {
+            let resource_mint_pk = lookup(&mints, "mine item", &survey.resource)?;
+            let sdu_mint_pk = token::create_mint(
+                svm,
+                funder_kp,
+                &Keypair::new(),
+                survey.sdu_decimals,
+                &authority_pk,
+            )
+            .map_err(step("create SDU mint"))?;
+
+            let tracker_kp = Keypair::new();
+            let tracker_pk = admin::RegisterSurveyDataUnitTracker::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &tracker_kp,
+                &sdu_mint_pk,
+                &resource_mint_pk,
+                funder_kp,
+            )
+            .set_coordinates_range(survey.coordinates_range)
+            .set_css_coordinates(survey.css_coordinates)
+            .set_origin_coordinates(survey.origin_coordinates)
+            .set_css_max_distance(survey.css_max_distance)
+            .set_origin_max_distance(survey.origin_max_distance)
+            .set_distance_weighting(survey.distance_weighting)
+            .set_t_max(survey.t_max)
+            .set_noise_multipliers(survey.x_mul, survey.y_mul, survey.z_mul)
+            .set_sdu_max_per_sector(survey.sdu_max_per_sector)
+            .set_scan_chance_regen_period(survey.scan_chance_regen_period)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step("register survey data unit tracker"))?;
+
+            let (tracker_signer_pda, _bump) = find_survey_data_unit_tracker_signer_pda(&tracker_pk);
+            let sdu_bank_pk = token::create_associated_token_account(
+                svm,
+                funder_kp,
+                &tracker_signer_pda,
+                &sdu_mint_pk,
+            )
+            .map_err(step("create SDU bank"))?;
+            token::mint_tokens(
+                svm,
+                funder_kp,
+                &sdu_mint_pk,
+                &authority_kp,
+                &sdu_bank_pk,
+                survey.sdu_supply,
+            )
+            .map_err(step("mint SDUs to the bank"))?;
+
+            mints.insert(SDU.to_string(), sdu_mint_pk);
+            survey_data_unit_tracker = Some(tracker_pk);
+        }
+
+        // cargo types, a unit of every mine item and SDUs takes up one cargo space
+        let mut cargo_types = HashMap::new();
+        let cargo_mints = self
+            .mine_items
+            .iter()
+            .map(|mine_item| mine_item.name.as_str())
+            .chain(self.survey.iter().map(|_| SDU));
+        for name in cargo_mints {
+            let mint_pk = lookup(&mints, "mint", name)?;
+            let cargo_type_pk = cargo::InitCargoType::new(
+                &authority_kp,
+                &profile_pk,
+                &cargo_stats_definition_pk,
+                &mint_pk,
+                funder_kp,
+            )
+            .set_profile_key_index(CARGO_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("init cargo type `{}`", name)))?;
+
+            cargo_types.insert(name.to_string(), cargo_type_pk);
+        }
+
+        // resources
+        let mut resources = HashMap::new();
+        for resource in self.resources.iter() {
+            let planet_pk = lookup(&planets, "planet", &resource.planet)?;
+            let mine_item_pk = lookup(&mine_items, "mine item", &resource.mine_item)?;
+            let resource_pk = admin::RegisterResource::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &planet_pk,
+                &mine_item_pk,
+                funder_kp,
+            )
+            .set_system_richness(resource.system_richness)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register resource `{}`", resource.name)))?;
+
+            resources.insert(resource.name.clone(), resource_pk);
+        }
+
+        // starbases
+        let mut starbases = HashMap::new();
+        for starbase in self.starbases.iter() {
+            let sector = self
+                .sectors
+                .iter()
+                .find(|sector| sector.name == starbase.sector)
+                .ok_or_else(|| {
+                    WorldError::Invalid(format!("unknown sector `{}`", starbase.sector))
+                })?;
+            let sector_pk = lookup(&sectors, "sector", &sector.name)?;
+
+            let starbase_pk = admin::RegisterStarbase::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &game_state_pk,
+                &sector_pk,
+                funder_kp,
+            )
+            .set_coordinates(sector.coordinates)
+            .set_name(starbase.name.clone())
+            .set_sub_coordinates(starbase.sub_coordinates)
+            .set_starbase_level_index(starbase.level)
+            .set_faction(starbase.faction.clone())
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register starbase `{}`", starbase.name)))?;
+
+            starbases.insert(starbase.name.clone(), starbase_pk);
+        }
+
+        // ships
+        let mut ships = HashMap::new();
+        for ship in self.ships.iter() {
+            let mint_pk = match &ship.mint {
+                Some(mint) => parse_pubkey(mint)?,
+                None => token::create_mint(svm, funder_kp, &Keypair::new(), 0, &authority_pk)
+                    .map_err(step(format!("create mint `{}`", ship.name)))?,
+            };
+
+            let (movement_stats, cargo_stats, misc_stats) = ship_stats(ship);
+            let ship_kp = Keypair::new();
+            let ship_pk = admin::RegisterShip::new(
+                &authority_kp,
+                &profile_pk,
+                &game_pk,
+                &ship_kp,
+                &mint_pk,
+                funder_kp,
+            )
+            .set_name(ship.name.clone())
+            .set_size_class(ship.size_class.into())
+            .set_movement_stats(movement_stats)
+            .set_cargo_stats(cargo_stats)
+            .set_misc_stats(misc_stats)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .send(svm)
+            .map_err(step(format!("register ship `{}`", ship.name)))?;
+
+            mints.insert(ship.name.clone(), mint_pk);
+            ships.insert(ship.name.clone(), ship_pk);
+        }
+
+        // players
+        let mut players = HashMap::new();
+        for player in self.players.iter() {
+            let player_kp = Keypair::new();
+            let player_pk = player_kp.pubkey();
+            svm.airdrop(&player_pk, player.lamports)
+                .map_err(step(format!("airdrop player `{}`", player.name)))?;
+
+            let player_profile_pk = profile::CreateProfile::new(&player_kp, &player_kp)
+                .send(svm)
+                .map_err(step(format!("create profile `{}`", player.name)))?;
+
+            let profile_faction_pk = profile::ChooseFaction::new(
+                &player_kp,
+                &player_profile_pk,
+                player.faction.clone(),
+                &player_kp,
+            )
+            .send(svm)
+            .map_err(step(format!("choose faction `{}`", player.name)))?;
+
+            let sage_player_profile_pk = player::RegisterSagePlayerProfile::new(
+                &player_profile_pk,
+                &game_pk,
+                &game_state_pk,
+                &player_kp,
+            )
+            .send(svm)
+            .map_err(step(format!(
+                "register sage player profile `{}`",
+                player.name
+            )))?;
+
+            let mut starbase_players = HashMap::new();
+            for starbase in player.starbases.iter() {
+                let starbase_pk = lookup(&starbases, "starbase", starbase)?;
+                let starbase_player_pk = player::RegisterStarbasePlayer::new(
+                    &game_pk,
+                    &game_state_pk,
+                    &sage_player_profile_pk,
+                    &profile_faction_pk,
+                    &starbase_pk,
+                    &player_kp,
+                )
+                .send(svm)
+                .map_err(step(format!(
+                    "register starbase player `{}` at `{}`",
+                    player.name, starbase
+                )))?;
+
+                starbase_players.insert(starbase.clone(), starbase_player_pk);
+            }
+
+            players.insert(
+                player.name.clone(),
+                WorldPlayer {
+                    keypair: player_kp,
+                    profile: player_profile_pk,
+                    profile_faction: profile_faction_pk,
+                    sage_player_profile: sage_player_profile_pk,
+                    starbase_players,
+                },
+            );
+        }
+
+        Ok(World {
+            authority: authority_kp,
+            profile: profile_pk,
+            game: game_pk,
+            game_state: game_state_pk,
+            cargo_stats_definition: cargo_stats_definition_pk,
+            sectors,
+            stars,
+            planets,
+            mine_items,
+            mints,
+            cargo_types,
+            resources,
+            starbases,
+            ships,
+            players,
+            survey_data_unit_tracker,
+        })
+    }
+
+    /// `game.starbase_levels`, when empty a default level for every `(faction, level)` used by
+    /// `starbases`.
+    fn starbase_levels(&self) -> Vec<StarbaseLevelConfig> {
+        if !self.game.starbase_levels.is_empty() {
+            return self.game.starbase_levels.clone();
+        }
+
+        let mut levels: Vec<StarbaseLevelConfig> = Vec::new();
+        for starbase in self.starbases.iter() {
+            let exists = levels.iter().any(|level_info| {
+                level_info.level == starbase.level
+                    && level_info.faction.name() == starbase.faction.name()
+            });
+            if !exists {
+                levels.push(StarbaseLevelConfig::new(
+                    starbase.level,
+                    starbase.faction.clone(),
+                ));
+            }
+        }
+        levels
+    }
+}
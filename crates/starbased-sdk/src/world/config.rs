@@ -0,0 +1,466 @@
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fs, path::Path};
+
+use staratlas_profile_faction::typedefs::Faction;
+use staratlas_sage::typedefs::{PlanetType, SectorRing, SizeClass, StarType};
+
+use super::{WorldError, SDU};
+
+/// A declarative description of a SAGE world.
+///
+/// Entities reference each other by `name` (e.g. a planet's `sector`), names must be unique
+/// per entity type. Factions are parsed with [`Faction`]'s `FromStr`, e.g. `"MUD"`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct WorldConfig {
+    pub game: GameConfig,
+    pub sectors: Vec<SectorConfig>,
+    pub stars: Vec<StarConfig>,
+    pub planets: Vec<PlanetConfig>,
+    pub mine_items: Vec<MineItemConfig>,
+    pub resources: Vec<ResourceConfig>,
+    pub starbases: Vec<StarbaseConfig>,
+    pub ships: Vec<ShipConfig>,
+    pub players: Vec<PlayerConfig>,
+    /// The survey data unit tracker, none is registered when omitted.
+    pub survey: Option<SurveyConfig>,
+}
+
+impl WorldConfig {
+    pub fn from_toml_str(s: &str) -> Result<Self, WorldError> {
+        toml::from_str(s).map_err(|err| WorldError::Parse(err.to_string()))
+    }
+
+    pub fn from_json_str(s: &str) -> Result<Self, WorldError> {
+        serde_json::from_str(s).map_err(|err| WorldError::Parse(err.to_string()))
+    }
+
+    /// Reads a world file, `.json` files are parsed as JSON everything else as TOML.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, WorldError> {
+        let path = path.as_ref();
+        let contents = fs::read_to_string(path)
+            .map_err(|err| WorldError::Parse(format!("{}: {}", path.display(), err)))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json_str(&contents),
+            _ => Self::from_toml_str(&contents),
+        }
+    }
+
+    /// Checks that names are unique per entity type (mine items, ships and the [`SDU`] mint share
+    /// their mint names) and that no two sectors share coordinates.
+    pub fn validate(&self) -> Result<(), WorldError> {
+        unique(
+            "sector",
+            self.sectors.iter().map(|sector| sector.name.as_str()),
+        )?;
+        unique("star", self.stars.iter().map(|star| star.name.as_str()))?;
+        unique(
+            "planet",
+            self.planets.iter().map(|planet| planet.name.as_str()),
+        )?;
+        unique(
+            "resource",
+            self.resources.iter().map(|resource| resource.name.as_str()),
+        )?;
+        unique(
+            "starbase",
+            self.starbases.iter().map(|starbase| starbase.name.as_str()),
+        )?;
+        unique(
+            "player",
+            self.players.iter().map(|player| player.name.as_str()),
+        )?;
+        unique(
+            "mine item or ship",
+            self.mine_items
+                .iter()
+                .map(|mine_item| mine_item.name.as_str())
+                .chain(self.ships.iter().map(|ship| ship.name.as_str()))
+                .chain(self.survey.iter().map(|_| SDU)),
+        )?;
+
+        let mut coordinates = HashSet::new();
+        for sector in self.sectors.iter() {
+            if !coordinates.insert(sector.coordinates) {
+                return Err(WorldError::Invalid(format!(
+                    "duplicate sector coordinates {:?}",
+                    sector.coordinates
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn unique<'a>(kind: &str, names: impl Iterator<Item = &'a str>) -> Result<(), WorldError> {
+    let mut seen = HashSet::new();
+    for name in names {
+        if !seen.insert(name) {
+            return Err(WorldError::Invalid(format!(
+                "duplicate {} `{}`",
+                kind, name
+            )));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GameConfig {
+    pub max_fleet_size: u32,
+    /// Starbase levels, when empty one is derived for every `(faction, level)` used by `starbases`.
+    pub starbase_levels: Vec<StarbaseLevelConfig>,
+    /// Mine item set as the game's fuel mint, moves burn it.
+    pub fuel: Option<String>,
+    /// Mine item set as the game's food mint.
+    pub food: Option<String>,
+}
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig {
+            max_fleet_size: 64,
+            starbase_levels: Vec::new(),
+            fuel: None,
+            food: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StarbaseLevelConfig {
+    pub level: u8,
+    #[serde(with = "faction_name")]
+    pub faction: Faction,
+    #[serde(default = "default_starbase_hp")]
+    pub hp: u64,
+    #[serde(default = "default_starbase_hp")]
+    pub sp: u64,
+    #[serde(default)]
+    pub sector_ring: SectorRingConfig,
+    #[serde(default)]
+    pub warp_lane_movement_fee: u64,
+}
+
+impl StarbaseLevelConfig {
+    /// A level with the default hp, sp, sector ring and warp lane fee.
+    pub fn new(level: u8, faction: Faction) -> Self {
+        StarbaseLevelConfig {
+            level,
+            faction,
+            hp: default_starbase_hp(),
+            sp: default_starbase_hp(),
+            sector_ring: SectorRingConfig::default(),
+            warp_lane_movement_fee: 0,
+        }
+    }
+}
+
+fn default_starbase_hp() -> u64 {
+    6000
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SectorConfig {
+    pub name: String,
+    pub coordinates: [i64; 2],
+    /// Coordinates of the sectors this sector has a warp lane to.
+    #[serde(default)]
+    pub connections: Vec<[i64; 2]>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StarConfig {
+    pub name: String,
+    pub sector: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub sub_coordinates: [i64; 2],
+    #[serde(default)]
+    pub star_type: StarTypeConfig,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlanetConfig {
+    pub name: String,
+    pub sector: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub max_hp: u64,
+    #[serde(default)]
+    pub sub_coordinates: [i64; 2],
+    #[serde(default)]
+    pub planet_type: PlanetTypeConfig,
+    #[serde(default)]
+    pub position: u8,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MineItemConfig {
+    pub name: String,
+    #[serde(default)]
+    pub resource_hardness: u16,
+    /// Existing mint address, a new mint is created when omitted.
+    #[serde(default)]
+    pub mint: Option<String>,
+    #[serde(default)]
+    pub decimals: u8,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SurveyConfig {
+    /// Mine item spent per scan, e.g. food.
+    pub resource: String,
+    /// Decimals of the SDU mint.
+    pub sdu_decimals: u8,
+    /// SDUs minted to the tracker's bank, paid out by scans.
+    pub sdu_supply: u64,
+    pub coordinates_range: [i64; 2],
+    pub css_coordinates: [[i64; 2]; 3],
+    pub origin_coordinates: [i64; 2],
+    pub css_max_distance: u32,
+    pub origin_max_distance: u32,
+    pub distance_weighting: u32,
+    pub t_max: i64,
+    pub x_mul: u32,
+    pub y_mul: u32,
+    pub z_mul: u32,
+    pub sdu_max_per_sector: u32,
+    pub scan_chance_regen_period: i16,
+}
+
+impl Default for SurveyConfig {
+    fn default() -> Self {
+        SurveyConfig {
+            resource: "Food".into(),
+            sdu_decimals: 0,
+            sdu_supply: 1_000_000,
+            coordinates_range: [-50, 50],
+            css_coordinates: [[0, 0]; 3],
+            origin_coordinates: [0, 0],
+            css_max_distance: 50,
+            origin_max_distance: 100,
+            distance_weighting: 1,
+            t_max: 3600,
+            x_mul: 1,
+            y_mul: 1,
+            z_mul: 1,
+            sdu_max_per_sector: 100,
+            scan_chance_regen_period: 3600,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ResourceConfig {
+    pub name: String,
+    pub planet: String,
+    pub mine_item: String,
+    #[serde(default)]
+    pub system_richness: u16,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct StarbaseConfig {
+    pub name: String,
+    pub sector: String,
+    #[serde(default)]
+    pub sub_coordinates: [i64; 2],
+    #[serde(default)]
+    pub level: u8,
+    #[serde(default = "default_faction", with = "faction_name")]
+    pub faction: Faction,
+}
+
+fn default_faction() -> Faction {
+    Faction::Unaligned
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ShipConfig {
+    pub name: String,
+    #[serde(default)]
+    pub size_class: SizeClassConfig,
+    /// Existing mint address, a new mint is created when omitted.
+    #[serde(default)]
+    pub mint: Option<String>,
+    #[serde(default)]
+    pub movement_stats: MovementStatsConfig,
+    #[serde(default)]
+    pub cargo_stats: CargoStatsConfig,
+    #[serde(default)]
+    pub misc_stats: MiscStatsConfig,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MovementStatsConfig {
+    pub subwarp_speed: u32,
+    pub warp_speed: u32,
+    pub max_warp_distance: u16,
+    pub warp_cool_down: u16,
+    pub subwarp_fuel_consumption_rate: u32,
+    pub warp_fuel_consumption_rate: u32,
+    pub planet_exit_fuel_amount: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CargoStatsConfig {
+    pub cargo_capacity: u32,
+    pub fuel_capacity: u32,
+    pub ammo_capacity: u32,
+    pub ammo_consumption_rate: u32,
+    pub food_consumption_rate: u32,
+    pub mining_rate: u32,
+    pub upgrade_rate: u32,
+    pub cargo_transfer_rate: u32,
+    pub tractor_beam_gather_rate: u32,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct MiscStatsConfig {
+    pub required_crew: u16,
+    pub passenger_capacity: u16,
+    pub respawn_time: u16,
+    pub scan_cool_down: u16,
+    pub sdu_per_scan: u32,
+    pub scan_cost: u32,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PlayerConfig {
+    pub name: String,
+    #[serde(default = "default_faction", with = "faction_name")]
+    pub faction: Faction,
+    /// Starbases to register the player at.
+    #[serde(default)]
+    pub starbases: Vec<String>,
+    #[serde(default = "default_player_lamports")]
+    pub lamports: u64,
+}
+
+fn default_player_lamports() -> u64 {
+    10_000_000_000
+}
+
+/// (De)serializes a [`Faction`] by name.
+mod faction_name {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use staratlas_profile_faction::typedefs::Faction;
+
+    pub fn serialize<S: Serializer>(faction: &Faction, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(faction.name())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Faction, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(D::Error::custom)
+    }
+}
+
+/// [`SectorRing`] by name, e.g. `"Inner"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SectorRingConfig {
+    #[default]
+    Inner,
+    Mid,
+    Outer,
+}
+
+impl From<SectorRingConfig> for SectorRing {
+    fn from(sector_ring: SectorRingConfig) -> Self {
+        match sector_ring {
+            SectorRingConfig::Inner => SectorRing::Inner,
+            SectorRingConfig::Mid => SectorRing::Mid,
+            SectorRingConfig::Outer => SectorRing::Outer,
+        }
+    }
+}
+
+/// [`StarType`] by name, e.g. `"Solar"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum StarTypeConfig {
+    WhiteDwarf,
+    RedDwarf,
+    #[default]
+    Solar,
+    HotBlue,
+    RedGiant,
+}
+
+impl From<StarTypeConfig> for StarType {
+    fn from(star_type: StarTypeConfig) -> Self {
+        match star_type {
+            StarTypeConfig::WhiteDwarf => StarType::WhiteDwarf,
+            StarTypeConfig::RedDwarf => StarType::RedDwarf,
+            StarTypeConfig::Solar => StarType::Solar,
+            StarTypeConfig::HotBlue => StarType::HotBlue,
+            StarTypeConfig::RedGiant => StarType::RedGiant,
+        }
+    }
+}
+
+/// [`PlanetType`] by name, e.g. `"AsteroidBelt"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum PlanetTypeConfig {
+    Terrestrial,
+    Volcanic,
+    Barren,
+    #[default]
+    AsteroidBelt,
+    GasGiant,
+    IceGiant,
+    Dark,
+}
+
+impl From<PlanetTypeConfig> for PlanetType {
+    fn from(planet_type: PlanetTypeConfig) -> Self {
+        match planet_type {
+            PlanetTypeConfig::Terrestrial => PlanetType::Terrestrial,
+            PlanetTypeConfig::Volcanic => PlanetType::Volcanic,
+            PlanetTypeConfig::Barren => PlanetType::Barren,
+            PlanetTypeConfig::AsteroidBelt => PlanetType::AsteroidBelt,
+            PlanetTypeConfig::GasGiant => PlanetType::GasGiant,
+            PlanetTypeConfig::IceGiant => PlanetType::IceGiant,
+            PlanetTypeConfig::Dark => PlanetType::Dark,
+        }
+    }
+}
+
+/// [`SizeClass`] by name, e.g. `"Small"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+pub enum SizeClassConfig {
+    XxSmall,
+    XSmall,
+    #[default]
+    Small,
+    Medium,
+    Large,
+    Capital,
+    Commander,
+    Titan,
+}
+
+impl From<SizeClassConfig> for SizeClass {
+    fn from(size_class: SizeClassConfig) -> Self {
+        match size_class {
+            SizeClassConfig::XxSmall => SizeClass::XxSmall,
+            SizeClassConfig::XSmall => SizeClass::XSmall,
+            SizeClassConfig::Small => SizeClass::Small,
+            SizeClassConfig::Medium => SizeClass::Medium,
+            SizeClassConfig::Large => SizeClass::Large,
+            SizeClassConfig::Capital => SizeClass::Capital,
+            SizeClassConfig::Commander => SizeClass::Commander,
+            SizeClassConfig::Titan => SizeClass::Titan,
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Declarative world bootstrap.
+//!
+//! Reads a TOML/JSON [`WorldConfig`] and materializes it in a [`LiteSVM`](litesvm::LiteSVM),
+//! expects the SAGE, cargo, player-profile, profile-faction and SPL token programs to be loaded.
+//!
+//! ```toml
+//! [game]
+//! max_fleet_size = 64
+//! fuel = "Hydrogen"
+//!
+//! [[sectors]]
+//! name = "Super Sector"
+//! coordinates = [1, 1]
+//! connections = [[2, 2]]
+//!
+//! [[sectors]]
+//! name = "Outer Sector"
+//! coordinates = [2, 2]
+//!
+//! [[planets]]
+//! name = "Belt A"
+//! sector = "Super Sector"
+//! planet_type = "AsteroidBelt"
+//!
+//! [[mine_items]]
+//! name = "Hydrogen"
+//! resource_hardness = 100
+//!
+//! [[resources]]
+//! name = "Belt A Hydrogen"
+//! planet = "Belt A"
+//! mine_item = "Hydrogen"
+//! system_richness = 100
+//!
+//! [[starbases]]
+//! name = "Starbase Alpha"
+//! sector = "Super Sector"
+//! level = 6
+//! faction = "Ustur"
+//!
+//! [[players]]
+//! name = "alice"
+//! faction = "Ustur"
+//! starbases = ["Starbase Alpha"]
+//!
+//! [survey]
+//! resource = "Hydrogen"
+//! ```
+mod build;
+mod config;
+
+pub use build::*;
+pub use config::*;
+
+use litesvm::types::FailedTransactionMetadata;
+use solana_sdk::{pubkey::Pubkey, signature::Keypair};
+use std::{collections::HashMap, fmt};
+
+/// Name of the SDU mint and cargo type in [`World`].
+pub const SDU: &str = "SDU";
+
+#[derive(Debug)]
+pub enum WorldError {
+    /// The world file could not be read or parsed.
+    Parse(String),
+    /// An entity references a name or value that does not exist.
+    Invalid(String),
+    /// A transaction failed while materializing the world.
+    Transaction {
+        step: String,
+        meta: Box<FailedTransactionMetadata>,
+    },
+}
+
+impl fmt::Display for WorldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WorldError::Parse(err) => write!(f, "failed to parse world: {}", err),
+            WorldError::Invalid(err) => write!(f, "invalid world: {}", err),
+            WorldError::Transaction { step, meta } => {
+                write!(f, "{} failed: {:?}", step, meta.err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for WorldError {}
+
+/// A player created by the world bootstrap.
+#[derive(Debug)]
+pub struct WorldPlayer {
+    /// Keypair of the player, it is both the profile and its auth key.
+    pub keypair: Keypair,
+    pub profile: Pubkey,
+    pub profile_faction: Pubkey,
+    pub sage_player_profile: Pubkey,
+    /// Starbase player accounts by starbase name.
+    pub starbase_players: HashMap<String, Pubkey>,
+}
+
+/// Handle to a materialized world with every created pubkey by name.
+#[derive(Debug)]
+pub struct World {
+    /// Key of the superuser profile (auth, cargo manager, SAGE manager and fleet manager key).
+    pub authority: Keypair,
+    /// The superuser profile.
+    pub profile: Pubkey,
+    pub game: Pubkey,
+    pub game_state: Pubkey,
+    pub cargo_stats_definition: Pubkey,
+    pub sectors: HashMap<String, Pubkey>,
+    pub stars: HashMap<String, Pubkey>,
+    pub planets: HashMap<String, Pubkey>,
+    pub mine_items: HashMap<String, Pubkey>,
+    /// Mints by mine item and ship name, the SDU mint as [`SDU`].
+    pub mints: HashMap<String, Pubkey>,
+    /// Cargo types by mine item name and [`SDU`].
+    pub cargo_types: HashMap<String, Pubkey>,
+    pub resources: HashMap<String, Pubkey>,
+    pub starbases: HashMap<String, Pubkey>,
+    pub ships: HashMap<String, Pubkey>,
+    pub players: HashMap<String, WorldPlayer>,
+    /// The survey data unit tracker, if the world has one.
+    pub survey_data_unit_tracker: Option<Pubkey>,
+}
+
+impl World {
+    /// Looks up an entity by name across all entity types (mints excluded).
+    pub fn pubkey(&self, name: &str) -> Option<Pubkey> {
+        [
+            &self.sectors,
+            &self.stars,
+            &self.planets,
+            &self.mine_items,
+            &self.resources,
+            &self.starbases,
+            &self.ships,
+        ]
+        .iter()
+        .find_map(|entities| entities.get(name).copied())
+        .or_else(|| self.players.get(name).map(|player| player.profile))
+    }
+}
@@ -7,7 +7,7 @@ use sqlx::{
 
 use staratlas_fleet_rentals::{seeds, state::ContractState};
 use staratlas_sage::{
-    state::{Fleet, FleetShips, Ship},
+    state::{Fleet, FleetShips, MineItem, Planet, Resource, Ship},
     typedefs::FleetShipsInfo,
 };
 
@@ -82,6 +82,7 @@ async fn table_upsert_sage_fleet(
 
     let mut data = &data[8..]; // Skip the first 8 bytes
     let fleet = Fleet::deserialize_reader(&mut data)?;
+    let faction = fleet.faction()?;
     let fleet_label = String::from_utf8_lossy(&fleet.fleet_label)
         .trim_end_matches('\0')
         .to_string();
@@ -103,7 +104,7 @@ async fn table_upsert_sage_fleet(
         .bind(fleet.game_id.to_string())
         .bind(fleet.owner_profile.to_string())
         .bind(fleet.fleet_ships.to_string())
-        .bind(fleet.faction)
+        .bind(u8::from(faction))
         .bind(fleet_label)
         .bind(ship_counts)
         .execute(pool)
@@ -173,14 +174,40 @@ async fn table_upsert_sage_ships(
             game_id,
             mint,
             name,
-            size_class
+            size_class,
+            warp_range,
+            warp_speed,
+            subwarp_speed,
+            warp_fuel_per_au,
+            subwarp_fuel_per_au,
+            warp_cool_down,
+            planet_exit_fuel_amount,
+            cargo_capacity,
+            fuel_capacity,
+            ammo_capacity,
+            mining_rate,
+            required_crew,
+            passenger_capacity
         ) VALUES (
-            $1, $2, $3, $4, $5
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18
         ) ON CONFLICT (pubkey) DO UPDATE SET
             game_id = $2,
             mint = $3,
             name = $4,
-            size_class = $5
+            size_class = $5,
+            warp_range = $6,
+            warp_speed = $7,
+            subwarp_speed = $8,
+            warp_fuel_per_au = $9,
+            subwarp_fuel_per_au = $10,
+            warp_cool_down = $11,
+            planet_exit_fuel_amount = $12,
+            cargo_capacity = $13,
+            fuel_capacity = $14,
+            ammo_capacity = $15,
+            mining_rate = $16,
+            required_crew = $17,
+            passenger_capacity = $18
     "#;
 
     let mut data = &data[8..]; // Skip the first 8 bytes
@@ -188,6 +215,7 @@ async fn table_upsert_sage_ships(
     let name = String::from_utf8_lossy(&ship.name)
         .trim_end_matches('\0')
         .to_string();
+    let stats = ship.derived_stats();
 
     let res = sqlx::query(UPSERT_SAGE_SHIPS_SQL)
         .bind(pubkey)
@@ -195,6 +223,160 @@ async fn table_upsert_sage_ships(
         .bind(ship.mint.to_string())
         .bind(name)
         .bind(ship.size_class)
+        .bind(stats.warp_range)
+        .bind(stats.warp_speed)
+        .bind(stats.subwarp_speed)
+        .bind(stats.warp_fuel_per_au)
+        .bind(stats.subwarp_fuel_per_au)
+        .bind(stats.warp_cool_down)
+        .bind(stats.planet_exit_fuel_amount)
+        .bind(stats.cargo_capacity)
+        .bind(stats.fuel_capacity)
+        .bind(stats.ammo_capacity)
+        .bind(stats.mining_rate)
+        .bind(stats.required_crew)
+        .bind(stats.passenger_capacity)
+        .execute(pool)
+        .await?;
+    dbg!(res);
+
+    Ok(())
+}
+
+async fn table_upsert_sage_planets(
+    pool: &SqlitePool,
+    pubkey: &str,
+    data: &[u8],
+) -> anyhow::Result<()> {
+    const UPSERT_SAGE_PLANETS_SQL: &str = r#"
+        INSERT INTO sage_planets (
+            pubkey,
+            game_id,
+            name,
+            sector_x,
+            sector_y,
+            sub_coordinates_x,
+            sub_coordinates_y,
+            planet_type,
+            num_resources,
+            num_miners,
+            amount_mined
+        ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+        ) ON CONFLICT (pubkey) DO UPDATE SET
+            game_id = $2,
+            name = $3,
+            sector_x = $4,
+            sector_y = $5,
+            sub_coordinates_x = $6,
+            sub_coordinates_y = $7,
+            planet_type = $8,
+            num_resources = $9,
+            num_miners = $10,
+            amount_mined = $11
+    "#;
+
+    let mut data = &data[8..]; // Skip the first 8 bytes
+    let planet = Planet::deserialize_reader(&mut data)?;
+    let name = String::from_utf8_lossy(&planet.name)
+        .trim_end_matches('\0')
+        .to_string();
+
+    let res = sqlx::query(UPSERT_SAGE_PLANETS_SQL)
+        .bind(pubkey)
+        .bind(planet.game_id.to_string())
+        .bind(name)
+        .bind(planet.sector[0])
+        .bind(planet.sector[1])
+        .bind(planet.sub_coordinates[0])
+        .bind(planet.sub_coordinates[1])
+        .bind(planet.planet_type)
+        .bind(planet.num_resources)
+        .bind(planet.num_miners as i64)
+        .bind(planet.amount_mined as i64)
+        .execute(pool)
+        .await?;
+    dbg!(res);
+
+    Ok(())
+}
+
+async fn table_upsert_sage_resources(
+    pool: &SqlitePool,
+    pubkey: &str,
+    data: &[u8],
+) -> anyhow::Result<()> {
+    const UPSERT_SAGE_RESOURCES_SQL: &str = r#"
+        INSERT INTO sage_resources (
+            pubkey,
+            game_id,
+            location,
+            mine_item,
+            system_richness,
+            amount_mined,
+            num_miners
+        ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7
+        ) ON CONFLICT (pubkey) DO UPDATE SET
+            game_id = $2,
+            location = $3,
+            mine_item = $4,
+            system_richness = $5,
+            amount_mined = $6,
+            num_miners = $7
+    "#;
+
+    let mut data = &data[8..]; // Skip the first 8 bytes
+    let resource = Resource::deserialize_reader(&mut data)?;
+
+    let res = sqlx::query(UPSERT_SAGE_RESOURCES_SQL)
+        .bind(pubkey)
+        .bind(resource.game_id.to_string())
+        .bind(resource.location.to_string())
+        .bind(resource.mine_item.to_string())
+        .bind(resource.system_richness)
+        .bind(resource.amount_mined as i64)
+        .bind(resource.num_miners as i64)
+        .execute(pool)
+        .await?;
+    dbg!(res);
+
+    Ok(())
+}
+
+async fn table_upsert_sage_mine_items(
+    pool: &SqlitePool,
+    pubkey: &str,
+    data: &[u8],
+) -> anyhow::Result<()> {
+    const UPSERT_SAGE_MINE_ITEMS_SQL: &str = r#"
+        INSERT INTO sage_mine_items (
+            pubkey,
+            game_id,
+            name,
+            mint,
+            resource_hardness
+        ) VALUES (
+            $1, $2, $3, $4, $5
+        ) ON CONFLICT (pubkey) DO UPDATE SET
+            game_id = $2,
+            name = $3,
+            mint = $4,
+            resource_hardness = $5
+    "#;
+
+    let mut data = &data[8..]; // Skip the first 8 bytes
+    let mine_item = MineItem::deserialize_reader(&mut data)?;
+    let name = String::from_utf8_lossy(&mine_item.name)
+        .trim_end_matches('\0')
+        .to_string();
+
+    let res = sqlx::query(UPSERT_SAGE_MINE_ITEMS_SQL)
+        .bind(pubkey)
+        .bind(mine_item.game_id.to_string())
+        .bind(name)
+        .bind(mine_item.mint.to_string())
+        .bind(mine_item.resource_hardness)
         .execute(pool)
         .await?;
     dbg!(res);
@@ -215,6 +397,9 @@ async fn upsert_sage_tables(pool: &SqlitePool) -> anyhow::Result<()> {
             Fleet::DISCRIMINATOR => table_upsert_sage_fleet(pool, &pubkey, data).await?,
             FleetShips::DISCRIMINATOR => table_upsert_sage_fleet_ships(pool, &pubkey, data).await?,
             Ship::DISCRIMINATOR => table_upsert_sage_ships(pool, &pubkey, data).await?,
+            Planet::DISCRIMINATOR => table_upsert_sage_planets(pool, &pubkey, data).await?,
+            Resource::DISCRIMINATOR => table_upsert_sage_resources(pool, &pubkey, data).await?,
+            MineItem::DISCRIMINATOR => table_upsert_sage_mine_items(pool, &pubkey, data).await?,
             _ => continue,
         }
     }
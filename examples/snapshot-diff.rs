@@ -0,0 +1,38 @@
+//! Reports the accounts created, closed and modified between two snapshots.
+//!
+//! ```text
+//! cargo run --example snapshot-diff -- data/sage-before.bin.gz data/sage.bin.gz [--json]
+//! ```
+use std::{env, process};
+
+use staratlas_starbased_sdk::snapshot::{diff, Snapshot};
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let json = args.iter().any(|arg| arg == "--json");
+    let paths: Vec<&String> = args.iter().filter(|arg| !arg.starts_with("--")).collect();
+
+    let [before_path, after_path] = paths[..] else {
+        eprintln!("usage: snapshot-diff <before> <after> [--json]");
+        process::exit(2);
+    };
+
+    let read = |path: &str| {
+        Snapshot::read(path).unwrap_or_else(|err| {
+            eprintln!("{}: {}", path, err);
+            process::exit(1);
+        })
+    };
+    let before = read(before_path);
+    let after = read(after_path);
+
+    let snapshot_diff = diff(&before, &after);
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&snapshot_diff).expect("serialize diff")
+        );
+    } else {
+        print!("{}", snapshot_diff);
+    }
+}
@@ -1,17 +1,14 @@
 use anchor_lang::Discriminator;
-use bincode::{Decode, Encode};
 use dotenv::dotenv;
-use flate2::{read::GzDecoder, write::GzEncoder, Compression};
-use serde::{Deserialize, Serialize};
 use solana_client::{
     rpc_client::RpcClient,
     rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
     rpc_filter::{Memcmp, MemcmpEncodedBytes, RpcFilterType},
 };
 use solana_sdk::{account::Account, commitment_config::CommitmentConfig, pubkey::Pubkey};
-use std::collections::HashMap;
 use std::fs::File;
-use std::io::prelude::*;
+use std::io::{prelude::*, BufReader, BufWriter};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use staratlas_sage::{
     state::{
@@ -22,58 +19,7 @@ use staratlas_sage::{
     ID as SAGE_ID,
 };
 
-// Create serializable wrapper types for Solana types
-#[derive(Serialize, Deserialize, Encode, Decode, Debug)]
-struct SerializablePubkey(String);
-
-#[derive(Serialize, Deserialize, Encode, Decode, Debug)]
-struct SerializableAccount {
-    lamports: u64,
-    data: Vec<u8>,
-    owner: SerializablePubkey,
-    executable: bool,
-    rent_epoch: u64,
-}
-
-impl From<Pubkey> for SerializablePubkey {
-    fn from(pubkey: Pubkey) -> Self {
-        SerializablePubkey(pubkey.to_string())
-    }
-}
-
-impl TryFrom<SerializablePubkey> for Pubkey {
-    type Error = anyhow::Error;
-
-    fn try_from(serializable: SerializablePubkey) -> Result<Self, Self::Error> {
-        Ok(Pubkey::from_str_const(&serializable.0))
-    }
-}
-
-impl From<Account> for SerializableAccount {
-    fn from(account: Account) -> Self {
-        SerializableAccount {
-            lamports: account.lamports,
-            data: account.data,
-            owner: SerializablePubkey::from(account.owner),
-            executable: account.executable,
-            rent_epoch: account.rent_epoch,
-        }
-    }
-}
-
-impl TryInto<Account> for SerializableAccount {
-    type Error = anyhow::Error;
-
-    fn try_into(self) -> Result<Account, Self::Error> {
-        Ok(Account {
-            lamports: self.lamports,
-            data: self.data,
-            owner: self.owner.try_into()?,
-            executable: self.executable,
-            rent_epoch: self.rent_epoch,
-        })
-    }
-}
+use staratlas_starbased_sdk::snapshot::{SnapshotHeader, SnapshotReader, SnapshotWriter};
 
 fn get_program_accounts<T: Discriminator>(
     client: &RpcClient,
@@ -104,7 +50,7 @@ fn main() -> anyhow::Result<()> {
     let rpc_url = dotenv::var("RPC")?;
     let client = RpcClient::new_with_commitment(rpc_url, CommitmentConfig::confirmed());
 
-    let mut collection = HashMap::new();
+    let slot = client.get_slot()?;
 
     let account_types = [
         get_program_accounts::<CraftingInstance>,
@@ -128,65 +74,41 @@ fn main() -> anyhow::Result<()> {
         get_program_accounts::<SurveyDataUnitTracker>,
     ];
 
+    let header = SnapshotHeader {
+        program_ids: vec![SAGE_ID],
+        slot,
+        timestamp: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() as i64,
+        ..Default::default()
+    };
+
+    // Stream the accounts to the gzip compressed file, one account type at a time
+    let file = BufWriter::new(File::create("data/sage.bin.gz")?);
+    let mut writer = SnapshotWriter::new(file, &header)?;
     for get_accounts in account_types.iter() {
         let (discrim, accounts) = get_accounts(&client)?;
-        collection.insert(discrim, accounts);
+        writer.write_section(discrim, &accounts)?;
     }
+    let total = writer.count();
+    writer.finish()?.flush()?;
 
-    // Convert to serializable format
-    let mut serializable_collection: HashMap<
-        Vec<u8>,
-        Vec<(SerializablePubkey, SerializableAccount)>,
-    > = HashMap::new();
-
-    for (discrim, accounts) in collection {
-        let serializable_accounts = accounts
-            .into_iter()
-            .map(|(pubkey, account)| {
-                (
-                    SerializablePubkey::from(pubkey),
-                    SerializableAccount::from(account),
-                )
-            })
-            .collect();
+    println!(
+        "Compressed snapshot of {} accounts at slot {} saved to data/sage.bin.gz",
+        total, slot
+    );
 
-        serializable_collection.insert(discrim.to_vec(), serializable_accounts);
+    // To read back later, see also `snapshot::load` to load it into a LiteSVM
+    let reader = SnapshotReader::new(BufReader::new(File::open("data/sage.bin.gz")?))?;
+    let version = reader.header().version;
+    let mut count = 0;
+    for entry in reader {
+        entry?;
+        count += 1;
     }
 
-    let encoded: Vec<u8> =
-        bincode::encode_to_vec(&serializable_collection, bincode::config::standard())?;
-
-    // Compress the encoded data with gzip
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
-    encoder.write_all(&encoded)?;
-    let compressed_data = encoder.finish()?;
-
-    // Write the compressed data to file
-    let mut file = File::create("data/sage.bin.gz")?;
-    file.write_all(&compressed_data)?;
-
-    println!("Compressed snapshot saved to data/sage.bin.gz");
     println!(
-        "Original size: {} bytes, Compressed size: {} bytes",
-        encoded.len(),
-        compressed_data.len()
+        "Successfully decoded the snapshot file (version {}), {} accounts",
+        version, count
     );
 
-    // To decompress and read back later:
-    let mut file = File::open("data/sage.bin.gz")?;
-    let mut compressed_data = Vec::new();
-    file.read_to_end(&mut compressed_data)?;
-
-    let mut decoder = GzDecoder::new(&compressed_data[..]);
-    let mut decoded_data = Vec::new();
-    decoder.read_to_end(&mut decoded_data)?;
-
-    let (_decoded_collection, _): (
-        HashMap<Vec<u8>, Vec<(SerializablePubkey, SerializableAccount)>>,
-        _,
-    ) = bincode::decode_from_slice(&decoded_data, bincode::config::standard())?;
-
-    println!("Successfully decoded the snapshot file");
-
     Ok(())
 }
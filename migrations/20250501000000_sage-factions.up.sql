@@ -0,0 +1,12 @@
+-- Lookup for the `faction` column of `sage_fleets` (profile-faction `Faction` discriminants)
+CREATE TABLE IF NOT EXISTS sage_factions (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(16) UNIQUE NOT NULL
+);
+
+INSERT INTO sage_factions (id, name) VALUES
+    (0, 'Unaligned'),
+    (1, 'MUD'),
+    (2, 'ONI'),
+    (3, 'Ustur')
+ON CONFLICT (id) DO NOTHING;
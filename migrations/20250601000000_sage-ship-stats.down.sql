@@ -0,0 +1,13 @@
+ALTER TABLE sage_ships DROP COLUMN warp_range;
+ALTER TABLE sage_ships DROP COLUMN warp_speed;
+ALTER TABLE sage_ships DROP COLUMN subwarp_speed;
+ALTER TABLE sage_ships DROP COLUMN warp_fuel_per_au;
+ALTER TABLE sage_ships DROP COLUMN subwarp_fuel_per_au;
+ALTER TABLE sage_ships DROP COLUMN warp_cool_down;
+ALTER TABLE sage_ships DROP COLUMN planet_exit_fuel_amount;
+ALTER TABLE sage_ships DROP COLUMN cargo_capacity;
+ALTER TABLE sage_ships DROP COLUMN fuel_capacity;
+ALTER TABLE sage_ships DROP COLUMN ammo_capacity;
+ALTER TABLE sage_ships DROP COLUMN mining_rate;
+ALTER TABLE sage_ships DROP COLUMN required_crew;
+ALTER TABLE sage_ships DROP COLUMN passenger_capacity;
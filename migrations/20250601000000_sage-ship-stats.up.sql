@@ -0,0 +1,14 @@
+-- Derived `Ship.stats`, see `staratlas_sage::stats::DerivedStats`
+ALTER TABLE sage_ships ADD COLUMN warp_range REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN warp_speed REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN subwarp_speed REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN warp_fuel_per_au REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN subwarp_fuel_per_au REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN warp_cool_down INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN planet_exit_fuel_amount INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN cargo_capacity INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN fuel_capacity INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN ammo_capacity INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN mining_rate REAL NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN required_crew INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE sage_ships ADD COLUMN passenger_capacity INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS sage_resources_mine_item;
+
+DROP INDEX IF EXISTS sage_planets_sector;
+
+DROP TABLE IF EXISTS sage_resources;
+
+DROP TABLE IF EXISTS sage_mine_items;
+
+DROP TABLE IF EXISTS sage_planets;
@@ -0,0 +1,38 @@
+-- `Planet`, `Resource` and `MineItem` accounts, see `staratlas_sage::galaxy_map`
+CREATE TABLE IF NOT EXISTS sage_planets (
+    id INTEGER PRIMARY KEY,
+    pubkey VARCHAR(32) UNIQUE NOT NULL,
+    game_id VARCHAR(32) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    sector_x BIGINT NOT NULL,
+    sector_y BIGINT NOT NULL,
+    sub_coordinates_x BIGINT NOT NULL,
+    sub_coordinates_y BIGINT NOT NULL,
+    planet_type INTEGER NOT NULL,
+    num_resources INTEGER NOT NULL,
+    num_miners BIGINT NOT NULL,
+    amount_mined BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sage_mine_items (
+    id INTEGER PRIMARY KEY,
+    pubkey VARCHAR(32) UNIQUE NOT NULL,
+    game_id VARCHAR(32) NOT NULL,
+    name VARCHAR(64) NOT NULL,
+    mint VARCHAR(32) NOT NULL,
+    resource_hardness INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS sage_resources (
+    id INTEGER PRIMARY KEY,
+    pubkey VARCHAR(32) UNIQUE NOT NULL,
+    game_id VARCHAR(32) NOT NULL,
+    location VARCHAR(32) NOT NULL,
+    mine_item VARCHAR(32) NOT NULL,
+    system_richness INTEGER NOT NULL,
+    amount_mined BIGINT NOT NULL,
+    num_miners BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS sage_planets_sector ON sage_planets (sector_x, sector_y);
+CREATE INDEX IF NOT EXISTS sage_resources_mine_item ON sage_resources (mine_item);
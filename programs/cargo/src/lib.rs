@@ -1,2 +1,4 @@
 anchor_gen::generate_cpi_crate!("cargo.json");
 anchor_lang::declare_id!("Cargo2VNTPPTi9c1vq1Jw5d3BWUNr18MjRtSupAghKEk");
+staratlas_ix_decode_gen::generate_ix_decode!("cargo.json");
+staratlas_ix_decode_gen::generate_errors!("cargo.json");
@@ -1,2 +1,17 @@
 anchor_gen::generate_cpi_crate!("crew.json");
 anchor_lang::declare_id!("CREWiq8qbxvo4SKkAFpVnc6t7CRQC4tAAscsNAENXgrJ");
+staratlas_ix_decode_gen::generate_ix_decode!("crew.json");
+staratlas_ix_decode_gen::generate_errors!("crew.json");
+
+pub use staratlas_profile_faction::{typedefs::Faction, InvalidFaction};
+
+pub mod faction {
+    use crate::state;
+    use staratlas_profile_faction::{typedefs::Faction, InvalidFaction};
+
+    impl state::PackType {
+        pub fn faction(&self) -> Result<Faction, InvalidFaction> {
+            Faction::try_from(self.faction)
+        }
+    }
+}
@@ -1,5 +1,7 @@
 // anchor_gen::generate_cpi_crate!("fleet_rentals.json");
 anchor_lang::declare_id!("SRSLY1fq9TJqCk1gNSE7VZL2bztvTn9wm4VR8u8jMKT");
+staratlas_ix_decode_gen::generate_ix_decode!("fleet_rentals.json");
+staratlas_ix_decode_gen::generate_errors!("fleet_rentals.json");
 
 pub mod state {
     use anchor_lang::prelude::{AnchorDeserialize, Pubkey, borsh};
@@ -1,2 +1,4 @@
 anchor_gen::generate_cpi_crate!("player_profile.json");
 anchor_lang::declare_id!("pprofELXjL5Kck7Jn5hCpwAL82DpTkSYBENzahVtbc9");
+staratlas_ix_decode_gen::generate_ix_decode!("player_profile.json");
+staratlas_ix_decode_gen::generate_errors!("player_profile.json");
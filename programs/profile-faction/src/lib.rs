@@ -1,2 +1,107 @@
 anchor_gen::generate_cpi_crate!("profile_faction.json");
 anchor_lang::declare_id!("pFACSRuobDmvfMKq1bAzwj27t6d2GJhSCHb1VcfnRmq");
+staratlas_ix_decode_gen::generate_ix_decode!("profile_faction.json");
+staratlas_ix_decode_gen::generate_errors!("profile_faction.json");
+
+pub mod seeds {
+    pub const PROFILE_FACTION_SEED: &[u8] = b"player_faction"; // ["player_faction", profile.publicKey]
+}
+
+pub mod faction {
+    use crate::{state::ProfileFactionAccount, typedefs::Faction};
+    use anchor_lang::prelude::Pubkey;
+    use std::{fmt, str::FromStr};
+
+    /// A raw faction value or name that does not map to any known [`Faction`].
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum InvalidFaction {
+        Value(u8),
+        Name(String),
+    }
+
+    impl fmt::Display for InvalidFaction {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                InvalidFaction::Value(value) => write!(f, "invalid faction: {}", value),
+                InvalidFaction::Name(name) => write!(f, "invalid faction: {}", name),
+            }
+        }
+    }
+
+    impl std::error::Error for InvalidFaction {}
+
+    impl Faction {
+        /// Every faction, in on-chain discriminant order.
+        pub const ALL: [Faction; 4] = [
+            Faction::Unaligned,
+            Faction::MUD,
+            Faction::ONI,
+            Faction::Ustur,
+        ];
+
+        pub fn name(&self) -> &'static str {
+            match self {
+                Faction::Unaligned => "Unaligned",
+                Faction::MUD => "MUD",
+                Faction::ONI => "ONI",
+                Faction::Ustur => "Ustur",
+            }
+        }
+    }
+
+    impl From<Faction> for u8 {
+        fn from(faction: Faction) -> Self {
+            faction as u8
+        }
+    }
+
+    impl TryFrom<u8> for Faction {
+        type Error = InvalidFaction;
+
+        fn try_from(value: u8) -> Result<Self, Self::Error> {
+            match value {
+                0 => Ok(Faction::Unaligned),
+                1 => Ok(Faction::MUD),
+                2 => Ok(Faction::ONI),
+                3 => Ok(Faction::Ustur),
+                _ => Err(InvalidFaction::Value(value)),
+            }
+        }
+    }
+
+    impl fmt::Display for Faction {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(self.name())
+        }
+    }
+
+    impl FromStr for Faction {
+        type Err = InvalidFaction;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s.to_ascii_lowercase().as_str() {
+                "unaligned" => Ok(Faction::Unaligned),
+                "mud" => Ok(Faction::MUD),
+                "oni" => Ok(Faction::ONI),
+                "ustur" => Ok(Faction::Ustur),
+                _ => Err(InvalidFaction::Name(s.to_string())),
+            }
+        }
+    }
+
+    impl ProfileFactionAccount {
+        /// ["player_faction", profile.publicKey]
+        pub fn find_address(profile: &Pubkey) -> (Pubkey, u8) {
+            Pubkey::find_program_address(
+                &[crate::seeds::PROFILE_FACTION_SEED, profile.as_ref()],
+                &crate::ID,
+            )
+        }
+
+        pub fn faction(&self) -> Result<Faction, InvalidFaction> {
+            Faction::try_from(self.faction)
+        }
+    }
+}
+
+pub use faction::InvalidFaction;
@@ -0,0 +1,324 @@
+//! Recomputes a fleet's aggregate `ship_counts` and `stats` from its ships.
+//!
+//! The aggregation mirrors SAGE: speeds and the warp distance are those of the slowest ship,
+//! cool downs and respawn time those of the slowest to recover, every other stat is summed per
+//! ship. `ship_counts.updated`, `crew_count`, `rented_crew` and the placeholders are not derived
+//! from ships and are taken from the fleet.
+use anchor_lang::prelude::{borsh::BorshDeserialize, Pubkey};
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    state::{Fleet, FleetShips, Ship},
+    typedefs::{CargoStats, FleetShipsInfo, MiscStats, MovementStats, ShipCounts, ShipStats},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregateError {
+    /// A `FleetShipsInfo` references a ship that was not provided.
+    MissingShip(Pubkey),
+    /// The ship has a size class outside of `SizeClass`.
+    InvalidSizeClass(Pubkey, u8),
+}
+
+impl fmt::Display for AggregateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AggregateError::MissingShip(ship) => write!(f, "missing ship {}", ship),
+            AggregateError::InvalidSizeClass(ship, size_class) => {
+                write!(f, "ship {} has invalid size class {}", ship, size_class)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AggregateError {}
+
+/// Decodes a `FleetShips` account and its `FleetShipsInfo` tail.
+pub fn fleet_ships_infos(data: &[u8]) -> std::io::Result<(FleetShips, Vec<FleetShipsInfo>)> {
+    let mut data = data.get(8..).unwrap_or_default(); // Skip the first 8 bytes
+    let fleet_ships = FleetShips::deserialize_reader(&mut data)?;
+    let infos = (0..fleet_ships.fleet_ships_info_count)
+        .map(|_| FleetShipsInfo::deserialize_reader(&mut data))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    Ok((fleet_ships, infos))
+}
+
+/// A `FleetShipsInfo` whose ship has been updated, fixed with `updateShipInFleet`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShipUpdate {
+    /// Index of the `FleetShipsInfo` (`UpdateShipFleetInput::fleet_ship_info_index`).
+    pub fleet_ship_info_index: u32,
+    /// The `old_ship` account.
+    pub ship: Pubkey,
+    /// The `next` account of the old ship, `None` when only the update id is behind.
+    pub next: Option<Pubkey>,
+    pub ship_amount: u64,
+    /// Update id recorded in the `FleetShipsInfo`.
+    pub update_id: u64,
+    /// Update id of the ship.
+    pub ship_update_id: u64,
+}
+
+/// A fleet field that differs from the recomputed value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub field: &'static str,
+    pub fleet: u64,
+    pub expected: u64,
+}
+
+/// The recomputed aggregates of a fleet.
+#[derive(Debug)]
+pub struct FleetAggregate {
+    pub ship_counts: ShipCounts,
+    pub stats: ShipStats,
+    /// Fields where the fleet differs from `ship_counts` and `stats`.
+    pub mismatches: Vec<Mismatch>,
+    pub updates: Vec<ShipUpdate>,
+}
+
+impl FleetAggregate {
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    pub fn needs_update(&self) -> bool {
+        !self.updates.is_empty()
+    }
+}
+
+/// A typedef field in `u64`, saturated when converted back.
+trait FromTotal {
+    fn from_total(total: u64) -> Self;
+}
+
+impl FromTotal for u16 {
+    fn from_total(total: u64) -> Self {
+        u16::try_from(total).unwrap_or(u16::MAX)
+    }
+}
+
+impl FromTotal for u32 {
+    fn from_total(total: u64) -> Self {
+        u32::try_from(total).unwrap_or(u32::MAX)
+    }
+}
+
+/// Declares a `u64` mirror of a typedef with how each of its fields aggregates over the ships:
+/// summed per ship, the minimum or the maximum of the ships, or kept from the fleet. Every field
+/// of the typedef has to be listed, `of` destructures it exhaustively.
+macro_rules! totals {
+    (
+        $(#[$meta:meta])*
+        struct $name:ident of $typedef:ident as $prefix:literal {
+            sum: [$($sum:ident),* $(,)?],
+            min: [$($min:ident),* $(,)?],
+            max: [$($max:ident),* $(,)?],
+            kept: [$($kept:ident),* $(,)?],
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+        struct $name {
+            $($sum: u64,)*
+            $($min: u64,)*
+            $($max: u64,)*
+        }
+
+        impl $name {
+            fn of(value: &$typedef) -> Self {
+                let $typedef {
+                    $($sum,)*
+                    $($min,)*
+                    $($max,)*
+                    $($kept: _,)*
+                } = value;
+                $name {
+                    $($sum: *$sum as u64,)*
+                    $($min: *$min as u64,)*
+                    $($max: *$max as u64,)*
+                }
+            }
+
+            /// Adds `amount` of a ship, `first` when it is the fleet's first ship.
+            #[allow(unused_variables)]
+            fn add(&mut self, ship: &Self, amount: u64, first: bool) {
+                $(self.$sum = self.$sum.saturating_add(ship.$sum.saturating_mul(amount));)*
+                $(self.$min = if first { ship.$min } else { self.$min.min(ship.$min) };)*
+                $(self.$max = self.$max.max(ship.$max);)*
+            }
+
+            fn mismatches(&self, fleet: &Self, mismatches: &mut Vec<Mismatch>) {
+                $(
+                    if fleet.$sum != self.$sum {
+                        mismatches.push(Mismatch {
+                            field: concat!($prefix, stringify!($sum)),
+                            fleet: fleet.$sum,
+                            expected: self.$sum,
+                        });
+                    }
+                )*
+                $(
+                    if fleet.$min != self.$min {
+                        mismatches.push(Mismatch {
+                            field: concat!($prefix, stringify!($min)),
+                            fleet: fleet.$min,
+                            expected: self.$min,
+                        });
+                    }
+                )*
+                $(
+                    if fleet.$max != self.$max {
+                        mismatches.push(Mismatch {
+                            field: concat!($prefix, stringify!($max)),
+                            fleet: fleet.$max,
+                            expected: self.$max,
+                        });
+                    }
+                )*
+            }
+
+            /// The saturated typedef, `kept` fields taken from `fleet`.
+            #[allow(unused_variables)]
+            fn saturated(&self, fleet: &$typedef) -> $typedef {
+                $typedef {
+                    $($sum: FromTotal::from_total(self.$sum),)*
+                    $($min: FromTotal::from_total(self.$min),)*
+                    $($max: FromTotal::from_total(self.$max),)*
+                    $($kept: fleet.$kept,)*
+                }
+            }
+        }
+    };
+}
+
+totals! {
+    struct ShipCountTotals of ShipCounts as "ship_counts." {
+        sum: [total, xx_small, x_small, small, medium, large, capital, commander, titan],
+        min: [],
+        max: [],
+        kept: [updated],
+    }
+}
+
+totals! {
+    struct MovementTotals of MovementStats as "stats.movement_stats." {
+        sum: [subwarp_fuel_consumption_rate, warp_fuel_consumption_rate, planet_exit_fuel_amount],
+        min: [subwarp_speed, warp_speed, max_warp_distance],
+        max: [warp_cool_down],
+        kept: [],
+    }
+}
+
+totals! {
+    struct CargoTotals of CargoStats as "stats.cargo_stats." {
+        sum: [
+            cargo_capacity,
+            fuel_capacity,
+            ammo_capacity,
+            ammo_consumption_rate,
+            food_consumption_rate,
+            mining_rate,
+            upgrade_rate,
+            cargo_transfer_rate,
+            tractor_beam_gather_rate,
+        ],
+        min: [],
+        max: [],
+        kept: [],
+    }
+}
+
+totals! {
+    struct MiscTotals of MiscStats as "stats.misc_stats." {
+        sum: [required_crew, passenger_capacity, sdu_per_scan, scan_cost],
+        min: [],
+        max: [respawn_time, scan_cool_down],
+        kept: [crew_count, rented_crew, placeholder, placeholder2, placeholder3],
+    }
+}
+
+/// Recomputes the fleet aggregates from its `FleetShipsInfo` entries and their ships.
+///
+/// Aggregates are computed in `u64`, one that overflows its fleet field is a mismatch and is
+/// saturated in `ship_counts` and `stats`.
+pub fn recompute(
+    fleet: &Fleet,
+    infos: &[FleetShipsInfo],
+    ships: &HashMap<Pubkey, Ship>,
+) -> Result<FleetAggregate, AggregateError> {
+    let mut counts = ShipCountTotals::default();
+    let mut movement = MovementTotals::default();
+    let mut cargo = CargoTotals::default();
+    let mut misc = MiscTotals::default();
+    let mut updates = Vec::new();
+
+    for (index, info) in infos.iter().enumerate() {
+        let ship = ships
+            .get(&info.ship)
+            .ok_or(AggregateError::MissingShip(info.ship))?;
+        let amount = info.amount;
+
+        let mut ship_counts = ShipCountTotals {
+            total: 1,
+            ..Default::default()
+        };
+        match ship.size_class {
+            0 => ship_counts.xx_small = 1,
+            1 => ship_counts.x_small = 1,
+            2 => ship_counts.small = 1,
+            3 => ship_counts.medium = 1,
+            4 => ship_counts.large = 1,
+            5 => ship_counts.capital = 1,
+            6 => ship_counts.commander = 1,
+            7 => ship_counts.titan = 1,
+            size_class => return Err(AggregateError::InvalidSizeClass(info.ship, size_class)),
+        }
+
+        let ShipStats {
+            movement_stats,
+            cargo_stats,
+            misc_stats,
+        } = &ship.stats;
+        let first = index == 0;
+        counts.add(&ship_counts, amount, first);
+        movement.add(&MovementTotals::of(movement_stats), amount, first);
+        cargo.add(&CargoTotals::of(cargo_stats), amount, first);
+        misc.add(&MiscTotals::of(misc_stats), amount, first);
+
+        let next = Some(ship.next.key).filter(|next| *next != Pubkey::default());
+        if next.is_some() || info.update_id < ship.update_id {
+            updates.push(ShipUpdate {
+                fleet_ship_info_index: index as u32,
+                ship: info.ship,
+                next,
+                ship_amount: amount,
+                update_id: info.update_id,
+                ship_update_id: ship.update_id,
+            });
+        }
+    }
+
+    let ShipStats {
+        movement_stats: fleet_movement,
+        cargo_stats: fleet_cargo,
+        misc_stats: fleet_misc,
+    } = &fleet.stats;
+    let mut mismatches = Vec::new();
+    counts.mismatches(&ShipCountTotals::of(&fleet.ship_counts), &mut mismatches);
+    movement.mismatches(&MovementTotals::of(fleet_movement), &mut mismatches);
+    cargo.mismatches(&CargoTotals::of(fleet_cargo), &mut mismatches);
+    misc.mismatches(&MiscTotals::of(fleet_misc), &mut mismatches);
+
+    Ok(FleetAggregate {
+        ship_counts: counts.saturated(&fleet.ship_counts),
+        stats: ShipStats {
+            movement_stats: movement.saturated(fleet_movement),
+            cargo_stats: cargo.saturated(fleet_cargo),
+            misc_stats: misc.saturated(fleet_misc),
+        },
+        mismatches,
+        updates,
+    })
+}
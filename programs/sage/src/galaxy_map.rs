@@ -0,0 +1,248 @@
+//! In-memory index of sectors, planets, resources and mine items.
+//!
+//! ```ignore
+//! let map = GalaxyMap::new(sectors, planets, resources, mine_items, starbases);
+//! let hydrogen = map.sectors_with_resource("Hydrogen", 150);
+//! ```
+use anchor_lang::prelude::Pubkey;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    movement::distance,
+    state::{MineItem, Planet, Resource, Sector, Starbase},
+};
+
+/// `PlanetType::AsteroidBelt` discriminant of `Planet.planet_type`.
+pub const ASTEROID_BELT: u8 = 3;
+
+fn name(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .trim_end_matches('\0')
+        .to_string()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SectorEntry {
+    pub key: Pubkey,
+    pub name: String,
+    pub coordinates: [i64; 2],
+    pub num_stars: u16,
+    pub num_planets: u16,
+    pub num_asteroid_belts: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlanetEntry {
+    pub key: Pubkey,
+    pub name: String,
+    pub sector: [i64; 2],
+    pub sub_coordinates: [i64; 2],
+    pub planet_type: u8,
+    pub num_miners: u64,
+}
+
+impl PlanetEntry {
+    pub fn is_asteroid_belt(&self) -> bool {
+        self.planet_type == ASTEROID_BELT
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceEntry {
+    pub key: Pubkey,
+    /// The planet the resource is found on.
+    pub location: Pubkey,
+    pub mine_item: Pubkey,
+    pub system_richness: u16,
+    pub amount_mined: u64,
+    pub num_miners: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MineItemEntry {
+    pub key: Pubkey,
+    pub name: String,
+    pub mint: Pubkey,
+    pub resource_hardness: u16,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarbaseEntry {
+    pub key: Pubkey,
+    pub name: String,
+    pub sector: [i64; 2],
+    pub sub_coordinates: [i64; 2],
+    pub faction: u8,
+}
+
+/// A minable resource with its planet and mine item.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Deposit<'a> {
+    pub resource: &'a ResourceEntry,
+    pub planet: &'a PlanetEntry,
+    pub mine_item: &'a MineItemEntry,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GalaxyMap {
+    pub sectors: HashMap<[i64; 2], SectorEntry>,
+    pub planets: HashMap<Pubkey, PlanetEntry>,
+    pub resources: HashMap<Pubkey, ResourceEntry>,
+    pub mine_items: HashMap<Pubkey, MineItemEntry>,
+    pub starbases: HashMap<Pubkey, StarbaseEntry>,
+}
+
+impl GalaxyMap {
+    pub fn new<'a>(
+        sectors: impl IntoIterator<Item = (Pubkey, &'a Sector)>,
+        planets: impl IntoIterator<Item = (Pubkey, &'a Planet)>,
+        resources: impl IntoIterator<Item = (Pubkey, &'a Resource)>,
+        mine_items: impl IntoIterator<Item = (Pubkey, &'a MineItem)>,
+        starbases: impl IntoIterator<Item = (Pubkey, &'a Starbase)>,
+    ) -> Self {
+        let mut map = GalaxyMap::default();
+        for (key, sector) in sectors {
+            map.insert_sector(key, sector);
+        }
+        for (key, planet) in planets {
+            map.insert_planet(key, planet);
+        }
+        for (key, resource) in resources {
+            map.insert_resource(key, resource);
+        }
+        for (key, mine_item) in mine_items {
+            map.insert_mine_item(key, mine_item);
+        }
+        for (key, starbase) in starbases {
+            map.insert_starbase(key, starbase);
+        }
+        map
+    }
+
+    pub fn insert_sector(&mut self, key: Pubkey, sector: &Sector) {
+        self.sectors.insert(
+            sector.coordinates,
+            SectorEntry {
+                key,
+                name: name(&sector.name),
+                coordinates: sector.coordinates,
+                num_stars: sector.num_stars,
+                num_planets: sector.num_planets,
+                num_asteroid_belts: sector.num_asteroid_belts,
+            },
+        );
+    }
+
+    pub fn insert_planet(&mut self, key: Pubkey, planet: &Planet) {
+        self.planets.insert(
+            key,
+            PlanetEntry {
+                key,
+                name: name(&planet.name),
+                sector: planet.sector,
+                sub_coordinates: planet.sub_coordinates,
+                planet_type: planet.planet_type,
+                num_miners: planet.num_miners,
+            },
+        );
+    }
+
+    pub fn insert_resource(&mut self, key: Pubkey, resource: &Resource) {
+        self.resources.insert(
+            key,
+            ResourceEntry {
+                key,
+                location: resource.location,
+                mine_item: resource.mine_item,
+                system_richness: resource.system_richness,
+                amount_mined: resource.amount_mined,
+                num_miners: resource.num_miners,
+            },
+        );
+    }
+
+    pub fn insert_mine_item(&mut self, key: Pubkey, mine_item: &MineItem) {
+        self.mine_items.insert(
+            key,
+            MineItemEntry {
+                key,
+                name: name(&mine_item.name),
+                mint: mine_item.mint,
+                resource_hardness: mine_item.resource_hardness,
+            },
+        );
+    }
+
+    pub fn insert_starbase(&mut self, key: Pubkey, starbase: &Starbase) {
+        self.starbases.insert(
+            key,
+            StarbaseEntry {
+                key,
+                name: name(&starbase.name),
+                sector: starbase.sector,
+                sub_coordinates: starbase.sub_coordinates,
+                faction: starbase.faction,
+            },
+        );
+    }
+
+    /// Resources with their planet and mine item, resources of unknown planets or mine items are
+    /// skipped.
+    pub fn deposits(&self) -> impl Iterator<Item = Deposit<'_>> {
+        self.resources.values().filter_map(|resource| {
+            Some(Deposit {
+                resource,
+                planet: self.planets.get(&resource.location)?,
+                mine_item: self.mine_items.get(&resource.mine_item)?,
+            })
+        })
+    }
+
+    /// Sectors with a resource of the mine item named `mine_item_name` at `system_richness` or
+    /// above, sorted.
+    pub fn sectors_with_resource(&self, mine_item_name: &str, min_richness: u16) -> Vec<[i64; 2]> {
+        let mut sectors: Vec<[i64; 2]> = self
+            .deposits()
+            .filter(|deposit| {
+                deposit.mine_item.name == mine_item_name
+                    && deposit.resource.system_richness >= min_richness
+            })
+            .map(|deposit| deposit.planet.sector)
+            .collect();
+        sectors.sort();
+        sectors.dedup();
+        sectors
+    }
+
+    /// Nearest asteroid belt to the starbase and its distance in sectors, asteroid belts of the
+    /// same sector are ordered by their sub coordinates.
+    pub fn nearest_asteroid_belt(&self, starbase: &Pubkey) -> Option<(&PlanetEntry, f64)> {
+        let starbase = self.starbases.get(starbase)?;
+        self.planets
+            .values()
+            .filter(|planet| planet.is_asteroid_belt())
+            .map(|planet| {
+                let sub_distance = distance(starbase.sub_coordinates, planet.sub_coordinates);
+                (
+                    planet,
+                    distance(starbase.sector, planet.sector),
+                    sub_distance,
+                )
+            })
+            .min_by(|(_, a, sub_a), (_, b, sub_b)| a.total_cmp(b).then(sub_a.total_cmp(sub_b)))
+            .map(|(planet, distance, _)| (planet, distance))
+    }
+
+    /// `num_miners` summed per mine item name.
+    pub fn miners_per_resource(&self) -> BTreeMap<String, u64> {
+        let mut miners = BTreeMap::new();
+        for resource in self.resources.values() {
+            let name = match self.mine_items.get(&resource.mine_item) {
+                Some(mine_item) => mine_item.name.clone(),
+                None => resource.mine_item.to_string(),
+            };
+            *miners.entry(name).or_default() += resource.num_miners;
+        }
+        miners
+    }
+}
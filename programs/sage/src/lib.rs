@@ -1,5 +1,42 @@
 anchor_gen::generate_cpi_crate!("sage.json");
 anchor_lang::declare_id!("SAGE2HAwep459SNq61LHvjxPk4pLPEJLoMETef7f7EE");
+staratlas_ix_decode_gen::generate_ix_decode!("sage.json");
+staratlas_ix_decode_gen::generate_errors!("sage.json");
+
+pub use staratlas_profile_faction::{typedefs::Faction, InvalidFaction};
+
+pub mod aggregate;
+
+pub mod faction {
+    use crate::state;
+    use staratlas_profile_faction::{typedefs::Faction, InvalidFaction};
+
+    impl state::Fleet {
+        pub fn faction(&self) -> Result<Faction, InvalidFaction> {
+            Faction::try_from(self.faction)
+        }
+    }
+
+    impl state::Starbase {
+        pub fn faction(&self) -> Result<Faction, InvalidFaction> {
+            Faction::try_from(self.faction)
+        }
+    }
+}
+
+pub mod galaxy_map;
+
+pub mod location;
+
+pub mod mining;
+
+pub mod movement;
+
+pub mod route;
+
+pub mod stats;
+
+pub mod survey_heuristic;
 
 pub mod state_with_data {
     use crate::{state, typedefs};
@@ -0,0 +1,118 @@
+//! Where a fleet is, derived from its [`FleetState`].
+//!
+//! ```ignore
+//! let FleetWithState(fleet, state) = FleetWithState::deserialize(&mut &data[..])?;
+//! let location = location::resolve(&state, now);
+//! ```
+use anchor_lang::prelude::Pubkey;
+
+use crate::state_with_data::FleetState;
+
+/// Position of a fleet, docked and mining fleets are at their starbase or asteroid.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Position {
+    /// Sector coordinates, fractional while moving.
+    Coordinates([f64; 2]),
+    Starbase(Pubkey),
+    Asteroid(Pubkey),
+}
+
+impl Position {
+    /// Coordinates of the position, `lookup` resolves the sector of a starbase or asteroid.
+    pub fn coordinates(&self, lookup: impl Fn(&Pubkey) -> Option<[i64; 2]>) -> Option<[f64; 2]> {
+        match self {
+            Position::Coordinates(coordinates) => Some(*coordinates),
+            Position::Starbase(key) | Position::Asteroid(key) => {
+                lookup(key).map(|[x, y]| [x as f64, y as f64])
+            }
+        }
+    }
+}
+
+/// The resolved location of a fleet at a point in time.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FleetLocation {
+    pub position: Position,
+    /// Sector the fleet is moving to.
+    pub destination: Option<[i64; 2]>,
+    /// Unix timestamp of the arrival, or of the end of mining. `None` while mining, `end` is only
+    /// set once mining stops; see [`crate::mining::estimate`] for when it will.
+    pub eta: Option<i64>,
+    /// The move or mining has completed on chain time but the fleet state was not updated yet,
+    /// a `fleetStateHandler` call is needed before the next action.
+    pub needs_state_handler: bool,
+}
+
+impl FleetLocation {
+    pub fn is_moving(&self) -> bool {
+        self.destination.is_some() && !self.needs_state_handler
+    }
+
+    /// Seconds until `eta`, `0` once passed.
+    pub fn remaining(&self, now: i64) -> Option<i64> {
+        self.eta.map(|eta| (eta - now).max(0))
+    }
+}
+
+/// Resolves the location of a fleet in `fleet_state` at the unix timestamp `now`.
+pub fn resolve(fleet_state: &FleetState, now: i64) -> FleetLocation {
+    match fleet_state {
+        FleetState::StarbaseLoadingBay(state) => FleetLocation {
+            position: Position::Starbase(state.starbase),
+            destination: None,
+            eta: None,
+            needs_state_handler: false,
+        },
+        FleetState::Idle(state) => FleetLocation {
+            position: at(state.sector),
+            destination: None,
+            eta: None,
+            needs_state_handler: false,
+        },
+        FleetState::MineAsteroid(state) => FleetLocation {
+            position: Position::Asteroid(state.asteroid),
+            destination: None,
+            eta: (state.end != 0).then_some(state.end),
+            needs_state_handler: state.end != 0 && now >= state.end,
+        },
+        FleetState::MoveWarp(state) => moving(
+            state.from_sector,
+            state.to_sector,
+            state.warp_start,
+            state.warp_finish,
+            now,
+        ),
+        FleetState::MoveSubwarp(state) => moving(
+            state.from_sector,
+            state.to_sector,
+            state.departure_time,
+            state.arrival_time,
+            now,
+        ),
+        FleetState::Respawn(state) => FleetLocation {
+            position: at(state.sector),
+            destination: None,
+            eta: None,
+            needs_state_handler: false,
+        },
+    }
+}
+
+fn at([x, y]: [i64; 2]) -> Position {
+    Position::Coordinates([x as f64, y as f64])
+}
+
+fn moving(from: [i64; 2], to: [i64; 2], start: i64, finish: i64, now: i64) -> FleetLocation {
+    let progress = match finish > start {
+        true => ((now - start) as f64 / (finish - start) as f64).clamp(0.0, 1.0),
+        false => 1.0,
+    };
+    let interpolate = |from: i64, to: i64| from as f64 + (to - from) as f64 * progress;
+
+    FleetLocation {
+        position: Position::Coordinates([interpolate(from[0], to[0]), interpolate(from[1], to[1])]),
+        destination: Some(to),
+        eta: Some(finish),
+        needs_state_handler: now >= finish,
+    }
+}
@@ -0,0 +1,115 @@
+//! Asteroid mining yield, duration and consumption.
+//!
+//! A fleet mines `mining_rate * system_richness / resource_hardness` per second, richness and
+//! hardness are stored in hundredths. Food and ammo are consumed per second of mining at the
+//! fleet's consumption rates, in ten thousandths. Mining stops when the cargo hold is full or the
+//! fleet runs out of food or ammo, whichever comes first.
+use crate::{
+    state::{MineItem, Resource},
+    stats::MINING_RATE_SCALE,
+    typedefs::{CargoStats, MineAsteroid},
+};
+
+/// Scale of `system_richness`.
+pub const RICHNESS_SCALE: f64 = 100.0;
+/// Scale of `resource_hardness`.
+pub const HARDNESS_SCALE: f64 = 100.0;
+/// Scale of `food_consumption_rate` and `ammo_consumption_rate`.
+pub const CONSUMPTION_RATE_SCALE: f64 = 10_000.0;
+
+/// What ends a mining session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MiningLimit {
+    CargoHold,
+    Food,
+    Ammo,
+}
+
+/// Resources the fleet holds at the mining state's `last_update`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MiningSupplies {
+    /// Free cargo hold space.
+    pub cargo_space: u64,
+    pub food: u64,
+    pub ammo: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MiningEstimate {
+    /// Resource mined per second.
+    pub rate: f64,
+    /// Amount mined at `now`, `amount_mined` of the state included.
+    pub mined: u64,
+    /// Food consumed since `last_update` at `now`.
+    pub food_consumed: u64,
+    /// Ammo consumed since `last_update` at `now`.
+    pub ammo_consumed: u64,
+    /// Seconds from `now` until the cargo hold is full, `None` when the fleet does not mine.
+    pub time_to_full: Option<f64>,
+    /// Unix timestamp to stop mining at, `None` when nothing limits mining.
+    pub stop_at: Option<i64>,
+    pub limit: Option<MiningLimit>,
+}
+
+/// Resource mined per second by a fleet with `cargo_stats`.
+pub fn mining_rate(cargo_stats: &CargoStats, resource: &Resource, mine_item: &MineItem) -> f64 {
+    if mine_item.resource_hardness == 0 {
+        return 0.0;
+    }
+
+    let mining_rate = cargo_stats.mining_rate as f64 / MINING_RATE_SCALE;
+    let richness = resource.system_richness as f64 / RICHNESS_SCALE;
+    let hardness = mine_item.resource_hardness as f64 / HARDNESS_SCALE;
+    mining_rate * richness / hardness
+}
+
+/// Estimates the mining session in `state` at the unix timestamp `now`.
+///
+/// The state holds the amount mined until its `last_update`, mining continues from there with
+/// `supplies`.
+pub fn estimate(
+    state: &MineAsteroid,
+    resource: &Resource,
+    mine_item: &MineItem,
+    cargo_stats: &CargoStats,
+    supplies: &MiningSupplies,
+    now: i64,
+) -> MiningEstimate {
+    let rate = mining_rate(cargo_stats, resource, mine_item);
+    let food_rate = cargo_stats.food_consumption_rate as f64 / CONSUMPTION_RATE_SCALE;
+    let ammo_rate = cargo_stats.ammo_consumption_rate as f64 / CONSUMPTION_RATE_SCALE;
+
+    // seconds of mining each limit allows
+    let limits = [
+        (MiningLimit::CargoHold, supplies.cargo_space as f64, rate),
+        (MiningLimit::Food, supplies.food as f64, food_rate),
+        (MiningLimit::Ammo, supplies.ammo as f64, ammo_rate),
+    ];
+    let (limit, duration) = limits
+        .iter()
+        .filter(|(_, _, rate)| *rate > 0.0)
+        .map(|(limit, amount, rate)| (*limit, amount / rate))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map_or((None, None), |(limit, duration)| {
+            (Some(limit), Some(duration))
+        });
+
+    let elapsed = (now - state.last_update).max(0) as f64;
+    let elapsed = duration.map_or(elapsed, |duration| elapsed.min(duration));
+    let amount = |rate: f64, max: u64| ((rate * elapsed).floor() as u64).min(max);
+
+    let time_to_full = (rate > 0.0).then(|| {
+        let full_at = state.last_update as f64 + supplies.cargo_space as f64 / rate;
+        (full_at - now as f64).max(0.0)
+    });
+
+    MiningEstimate {
+        rate,
+        mined: state.amount_mined + amount(rate, supplies.cargo_space),
+        food_consumed: amount(food_rate, supplies.food),
+        ammo_consumed: amount(ammo_rate, supplies.ammo),
+        time_to_full,
+        stop_at: duration.map(|duration| state.last_update + duration.ceil() as i64),
+        limit,
+    }
+}
@@ -0,0 +1,206 @@
+//! Fuel, time and cool down costs of warp and subwarp moves.
+//!
+//! Mirrors the SAGE movement checks, a move that SAGE would reject fails with the SAGE error
+//! name. Planet exit fuel is not included.
+use std::fmt;
+
+use crate::{
+    state,
+    stats::{DISTANCE_SCALE, FUEL_RATE_SCALE, SPEED_SCALE},
+    typedefs::MovementStats,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MovementError {
+    /// The fuel tank holds less fuel than the move burns.
+    InsufficientFuel { required: u64, available: u64 },
+    /// The warp is longer than the fleet's max warp distance.
+    DistanceGreaterThanMax { distance: f64, max: f64 },
+    /// The move does not leave the sector, or the fleet can not warp.
+    InvalidWarpRange,
+    /// The fleet warped too recently.
+    WarpIsOnCooldown { expires_at: i64 },
+    /// The fleet can not subwarp, its subwarp speed is zero.
+    FaultyMovement,
+}
+
+impl fmt::Display for MovementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MovementError::InsufficientFuel {
+                required,
+                available,
+            } => write!(
+                f,
+                "InsufficientFuel: requires {} fuel, has {}",
+                required, available
+            ),
+            MovementError::DistanceGreaterThanMax { distance, max } => write!(
+                f,
+                "DistanceGreaterThanMax: distance {} is greater than max {}",
+                distance, max
+            ),
+            MovementError::InvalidWarpRange => write!(f, "InvalidWarpRange"),
+            MovementError::WarpIsOnCooldown { expires_at } => {
+                write!(f, "WarpIsOnCooldown: until {}", expires_at)
+            }
+            MovementError::FaultyMovement => write!(f, "FaultyMovement: subwarp speed is zero"),
+        }
+    }
+}
+
+impl std::error::Error for MovementError {}
+
+/// Cost of a single move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveCost {
+    /// Distance in AU.
+    pub distance: f64,
+    pub fuel: u64,
+    /// Seconds.
+    pub duration: f64,
+    /// Unix timestamp of the arrival.
+    pub arrival: i64,
+    /// Unix timestamp the next warp is possible, `None` for subwarp.
+    pub warp_cooldown_expires_at: Option<i64>,
+}
+
+/// Warp and subwarp costs of the same move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementEstimate {
+    pub warp: Result<MoveCost, MovementError>,
+    pub subwarp: Result<MoveCost, MovementError>,
+}
+
+impl MovementEstimate {
+    /// The faster possible move.
+    pub fn fastest(&self) -> Option<&MoveCost> {
+        match (&self.warp, &self.subwarp) {
+            (Ok(warp), Ok(subwarp)) if subwarp.duration < warp.duration => Some(subwarp),
+            (Ok(warp), _) => Some(warp),
+            (_, Ok(subwarp)) => Some(subwarp),
+            _ => None,
+        }
+    }
+}
+
+pub fn distance(from: [i64; 2], to: [i64; 2]) -> f64 {
+    let dx = (to[0] - from[0]) as f64;
+    let dy = (to[1] - from[1]) as f64;
+    (dx * dx + dy * dy).sqrt()
+}
+
+/// Fuel burned over `distance` AU at a consumption `rate` (per hundredth AU), rounded up.
+pub fn fuel_burn(distance: f64, rate: u32) -> u64 {
+    (distance * rate as f64 / FUEL_RATE_SCALE).ceil() as u64
+}
+
+/// Estimates a warp and a subwarp from `from` to `to` with `fuel` in the fuel tank at `now`.
+pub fn estimate(
+    stats: &MovementStats,
+    from: [i64; 2],
+    to: [i64; 2],
+    fuel: u64,
+    warp_cooldown_expires_at: i64,
+    now: i64,
+) -> MovementEstimate {
+    MovementEstimate {
+        warp: estimate_warp(stats, from, to, fuel, warp_cooldown_expires_at, now),
+        subwarp: estimate_subwarp(stats, from, to, fuel, now),
+    }
+}
+
+pub fn estimate_warp(
+    stats: &MovementStats,
+    from: [i64; 2],
+    to: [i64; 2],
+    fuel: u64,
+    warp_cooldown_expires_at: i64,
+    now: i64,
+) -> Result<MoveCost, MovementError> {
+    if now < warp_cooldown_expires_at {
+        return Err(MovementError::WarpIsOnCooldown {
+            expires_at: warp_cooldown_expires_at,
+        });
+    }
+
+    let distance = distance(from, to);
+    if distance == 0.0 || stats.warp_speed == 0 {
+        return Err(MovementError::InvalidWarpRange);
+    }
+
+    let max = stats.max_warp_distance as f64 / DISTANCE_SCALE;
+    if distance > max {
+        return Err(MovementError::DistanceGreaterThanMax { distance, max });
+    }
+
+    let required = fuel_burn(distance, stats.warp_fuel_consumption_rate);
+    if required > fuel {
+        return Err(MovementError::InsufficientFuel {
+            required,
+            available: fuel,
+        });
+    }
+
+    let duration = distance / (stats.warp_speed as f64 / SPEED_SCALE);
+    Ok(MoveCost {
+        distance,
+        fuel: required,
+        duration,
+        arrival: now + duration.ceil() as i64,
+        warp_cooldown_expires_at: Some(now + stats.warp_cool_down as i64),
+    })
+}
+
+pub fn estimate_subwarp(
+    stats: &MovementStats,
+    from: [i64; 2],
+    to: [i64; 2],
+    fuel: u64,
+    now: i64,
+) -> Result<MoveCost, MovementError> {
+    let distance = distance(from, to);
+    if distance == 0.0 {
+        return Err(MovementError::InvalidWarpRange);
+    }
+    if stats.subwarp_speed == 0 {
+        return Err(MovementError::FaultyMovement);
+    }
+
+    let required = fuel_burn(distance, stats.subwarp_fuel_consumption_rate);
+    if required > fuel {
+        return Err(MovementError::InsufficientFuel {
+            required,
+            available: fuel,
+        });
+    }
+
+    let duration = distance / (stats.subwarp_speed as f64 / SPEED_SCALE);
+    Ok(MoveCost {
+        distance,
+        fuel: required,
+        duration,
+        arrival: now + duration.ceil() as i64,
+        warp_cooldown_expires_at: None,
+    })
+}
+
+impl state::Fleet {
+    /// Estimates a move of the fleet with `fuel` in its fuel tank, see [`estimate`].
+    pub fn estimate_move(
+        &self,
+        from: [i64; 2],
+        to: [i64; 2],
+        fuel: u64,
+        now: i64,
+    ) -> MovementEstimate {
+        estimate(
+            &self.stats.movement_stats,
+            from,
+            to,
+            fuel,
+            self.warp_cooldown_expires_at,
+            now,
+        )
+    }
+}
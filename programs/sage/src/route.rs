@@ -0,0 +1,460 @@
+//! Multi-hop route planning across sectors, warp lanes and starbases.
+//!
+//! Sectors are nodes, fleets move between them with warp jumps (within the max warp distance),
+//! warp lanes (between connected sectors that both have a starbase) or subwarp. Routes respect
+//! the fuel tank and may refuel at starbases of a faction.
+//!
+//! Warp lanes are costed like a warp of the same distance times
+//! [`RouteOptions::warp_lane_fuel_factor`] and ignore the max warp distance. When planning for
+//! time a warp costs at least the warp cool down, as the next warp has to wait for it.
+use anchor_lang::prelude::{borsh::BorshDeserialize, Pubkey};
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use crate::{
+    movement::{distance, fuel_burn},
+    state::{Sector, Starbase},
+    stats::{DISTANCE_SCALE, SPEED_SCALE},
+    typedefs::{MovementStats, SectorConnection},
+};
+
+/// Decodes a `Sector` account and its `SectorConnection` tail.
+pub fn sector_connections(data: &[u8]) -> std::io::Result<(Sector, Vec<SectorConnection>)> {
+    let mut data = data.get(8..).unwrap_or_default(); // Skip the first 8 bytes
+    let sector = Sector::deserialize_reader(&mut data)?;
+    let connections = (0..sector.num_connections)
+        .map(|_| SectorConnection::deserialize_reader(&mut data))
+        .collect::<std::io::Result<Vec<_>>>()?;
+
+    Ok((sector, connections))
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteSector {
+    pub key: Pubkey,
+    pub coordinates: [i64; 2],
+    /// Connected sector keys, in `SectorConnection` order.
+    pub connections: Vec<Pubkey>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteStarbase {
+    pub key: Pubkey,
+    pub sector: [i64; 2],
+    pub faction: u8,
+}
+
+/// The sectors and starbases a route is planned over.
+#[derive(Debug, Clone, Default)]
+pub struct RouteGraph {
+    pub sectors: Vec<RouteSector>,
+    pub starbases: Vec<RouteStarbase>,
+}
+
+impl RouteGraph {
+    /// From `Sector` accounts (with their connections) and `Starbase` accounts.
+    pub fn new<'a>(
+        sectors: impl IntoIterator<Item = (Pubkey, (Sector, Vec<SectorConnection>))>,
+        starbases: impl IntoIterator<Item = (Pubkey, &'a Starbase)>,
+    ) -> Self {
+        RouteGraph {
+            sectors: sectors
+                .into_iter()
+                .map(|(key, (sector, connections))| RouteSector {
+                    key,
+                    coordinates: sector.coordinates,
+                    connections: connections
+                        .iter()
+                        .map(|connection| connection.connection_sector)
+                        .collect(),
+                })
+                .collect(),
+            starbases: starbases
+                .into_iter()
+                .map(|(key, starbase)| RouteStarbase {
+                    key,
+                    sector: starbase.sector,
+                    faction: starbase.faction,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn sector(&self, coordinates: [i64; 2]) -> Option<&RouteSector> {
+        self.sectors
+            .iter()
+            .find(|sector| sector.coordinates == coordinates)
+    }
+
+    pub fn starbase(&self, coordinates: [i64; 2]) -> Option<&RouteStarbase> {
+        self.starbases
+            .iter()
+            .find(|starbase| starbase.sector == coordinates)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Objective {
+    /// Least fuel burned.
+    Cheapest,
+    /// Least time spent moving.
+    Fastest,
+}
+
+#[derive(Debug, Clone)]
+pub struct RouteOptions {
+    pub objective: Objective,
+    /// Fuel in the tank at the start.
+    pub fuel: u64,
+    /// Fuel tank capacity, refuels fill it up.
+    pub fuel_capacity: u64,
+    /// Faction whose starbases the fleet may refuel at, `None` never refuels.
+    pub refuel_faction: Option<u8>,
+    pub use_warp: bool,
+    pub use_warp_lanes: bool,
+    pub use_subwarp: bool,
+    pub warp_lane_fuel_factor: f64,
+}
+
+impl RouteOptions {
+    pub fn new(objective: Objective, fuel: u64, fuel_capacity: u64) -> Self {
+        RouteOptions {
+            objective,
+            fuel,
+            fuel_capacity,
+            refuel_faction: None,
+            use_warp: true,
+            use_warp_lanes: true,
+            use_subwarp: true,
+            warp_lane_fuel_factor: 1.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RouteStep {
+    /// Fill the fuel tank at the starbase.
+    Refuel { starbase: Pubkey },
+    Warp {
+        from: [i64; 2],
+        to: [i64; 2],
+        fuel: u64,
+        duration: f64,
+    },
+    WarpLane {
+        from: [i64; 2],
+        to: [i64; 2],
+        from_sector: Pubkey,
+        to_sector: Pubkey,
+        from_starbase: Pubkey,
+        to_starbase: Pubkey,
+        /// Index of `to_sector` in the connections of `from_sector`.
+        to_sector_index: u16,
+        /// Index of `from_sector` in the connections of `to_sector`.
+        from_sector_index: u16,
+        fuel: u64,
+        duration: f64,
+    },
+    Subwarp {
+        from: [i64; 2],
+        to: [i64; 2],
+        fuel: u64,
+        duration: f64,
+    },
+}
+
+impl RouteStep {
+    pub fn fuel(&self) -> u64 {
+        match self {
+            RouteStep::Refuel { .. } => 0,
+            RouteStep::Warp { fuel, .. }
+            | RouteStep::WarpLane { fuel, .. }
+            | RouteStep::Subwarp { fuel, .. } => *fuel,
+        }
+    }
+
+    pub fn duration(&self) -> f64 {
+        match self {
+            RouteStep::Refuel { .. } => 0.0,
+            RouteStep::Warp { duration, .. }
+            | RouteStep::WarpLane { duration, .. }
+            | RouteStep::Subwarp { duration, .. } => *duration,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub steps: Vec<RouteStep>,
+    /// Total fuel burned.
+    pub fuel: u64,
+    /// Total seconds moving, warp cool downs excluded.
+    pub duration: f64,
+}
+
+/// A reached state, at `node` with `fuel` left for `cost`.
+#[derive(Debug, Clone)]
+struct Label {
+    cost: f64,
+    node: usize,
+    fuel: u64,
+    /// The previous label and the step from it.
+    previous: Option<(usize, RouteStep)>,
+    /// A later label of the node has less or equal cost and more or equal fuel.
+    dominated: bool,
+}
+
+/// A label index queued by its cost.
+#[derive(Debug, Clone, Copy)]
+struct Queued {
+    cost: f64,
+    label: usize,
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cost == other.cost
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // min-heap on cost
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Plans a route from `from` to `to` for a fleet with `stats`, `None` when there is none.
+///
+/// `from` and `to` don't have to be loaded sectors.
+pub fn plan(
+    graph: &RouteGraph,
+    stats: &MovementStats,
+    from: [i64; 2],
+    to: [i64; 2],
+    options: &RouteOptions,
+) -> Option<Route> {
+    let mut nodes: Vec<[i64; 2]> = graph
+        .sectors
+        .iter()
+        .map(|sector| sector.coordinates)
+        .collect();
+    for coordinates in [from, to] {
+        if !nodes.contains(&coordinates) {
+            nodes.push(coordinates);
+        }
+    }
+    let node_of = |coordinates: [i64; 2]| nodes.iter().position(|node| *node == coordinates);
+    let (start, end) = (node_of(from)?, node_of(to)?);
+
+    let sector_keys: HashMap<Pubkey, usize> = graph
+        .sectors
+        .iter()
+        .filter_map(|sector| node_of(sector.coordinates).map(|node| (sector.key, node)))
+        .collect();
+    let refuel_starbase = |node: usize| {
+        let faction = options.refuel_faction?;
+        graph
+            .starbases
+            .iter()
+            .find(|starbase| starbase.sector == nodes[node] && starbase.faction == faction)
+    };
+
+    let max_warp_distance = stats.max_warp_distance as f64 / DISTANCE_SCALE;
+    let warp_speed = stats.warp_speed as f64 / SPEED_SCALE;
+    let subwarp_speed = stats.subwarp_speed as f64 / SPEED_SCALE;
+
+    // every label reached, and per node the labels no other label of the node dominates
+    let mut labels = vec![Label {
+        cost: 0.0,
+        node: start,
+        fuel: options.fuel,
+        previous: None,
+        dominated: false,
+    }];
+    let mut frontier: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    frontier[start].push(0);
+    let mut heap = BinaryHeap::from([Queued {
+        cost: 0.0,
+        label: 0,
+    }]);
+
+    let cost_of = |step: &RouteStep| match options.objective {
+        Objective::Cheapest => step.fuel() as f64,
+        Objective::Fastest => match step {
+            RouteStep::Warp { duration, .. } | RouteStep::WarpLane { duration, .. } => {
+                duration.max(stats.warp_cool_down as f64)
+            }
+            step => step.duration(),
+        },
+    };
+
+    while let Some(Queued { label: index, .. }) = heap.pop() {
+        if labels[index].dominated {
+            continue;
+        }
+        let label = labels[index].clone();
+        if label.node == end {
+            return Some(route(&labels, index));
+        }
+
+        let mut steps: Vec<(usize, u64, RouteStep)> = Vec::new();
+
+        if let Some(starbase) = refuel_starbase(label.node) {
+            if label.fuel < options.fuel_capacity {
+                steps.push((
+                    label.node,
+                    options.fuel_capacity,
+                    RouteStep::Refuel {
+                        starbase: starbase.key,
+                    },
+                ));
+            }
+        }
+
+        let here = nodes[label.node];
+        for (node, there) in nodes.iter().enumerate() {
+            if node == label.node {
+                continue;
+            }
+            let distance = distance(here, *there);
+
+            if options.use_warp && warp_speed > 0.0 && distance <= max_warp_distance {
+                let fuel = fuel_burn(distance, stats.warp_fuel_consumption_rate);
+                if fuel <= label.fuel {
+                    steps.push((
+                        node,
+                        label.fuel - fuel,
+                        RouteStep::Warp {
+                            from: here,
+                            to: *there,
+                            fuel,
+                            duration: distance / warp_speed,
+                        },
+                    ));
+                }
+            }
+
+            if options.use_subwarp && subwarp_speed > 0.0 {
+                let fuel = fuel_burn(distance, stats.subwarp_fuel_consumption_rate);
+                if fuel <= label.fuel {
+                    steps.push((
+                        node,
+                        label.fuel - fuel,
+                        RouteStep::Subwarp {
+                            from: here,
+                            to: *there,
+                            fuel,
+                            duration: distance / subwarp_speed,
+                        },
+                    ));
+                }
+            }
+        }
+
+        if options.use_warp_lanes && warp_speed > 0.0 {
+            if let (Some(from_sector), Some(from_starbase)) =
+                (graph.sector(here), graph.starbase(here))
+            {
+                for (to_sector_index, to_sector_key) in from_sector.connections.iter().enumerate() {
+                    let Some(node) = sector_keys.get(to_sector_key).copied() else {
+                        continue;
+                    };
+                    let there = nodes[node];
+                    let (Some(to_sector), Some(to_starbase)) =
+                        (graph.sector(there), graph.starbase(there))
+                    else {
+                        continue;
+                    };
+                    let Some(from_sector_index) = to_sector
+                        .connections
+                        .iter()
+                        .position(|key| *key == from_sector.key)
+                    else {
+                        continue;
+                    };
+
+                    let distance = distance(here, there);
+                    let fuel = (fuel_burn(distance, stats.warp_fuel_consumption_rate) as f64
+                        * options.warp_lane_fuel_factor)
+                        .ceil() as u64;
+                    if fuel <= label.fuel {
+                        steps.push((
+                            node,
+                            label.fuel - fuel,
+                            RouteStep::WarpLane {
+                                from: here,
+                                to: there,
+                                from_sector: from_sector.key,
+                                to_sector: to_sector.key,
+                                from_starbase: from_starbase.key,
+                                to_starbase: to_starbase.key,
+                                to_sector_index: to_sector_index as u16,
+                                from_sector_index: from_sector_index as u16,
+                                fuel,
+                                duration: distance / warp_speed,
+                            },
+                        ));
+                    }
+                }
+            }
+        }
+
+        for (node, fuel, step) in steps {
+            let cost = label.cost + cost_of(&step);
+            let dominated_by = |other: &Label| other.cost <= cost && other.fuel >= fuel;
+            if frontier[node]
+                .iter()
+                .any(|other| dominated_by(&labels[*other]))
+            {
+                continue;
+            }
+            frontier[node].retain(|other| {
+                let other = &mut labels[*other];
+                other.dominated = cost <= other.cost && fuel >= other.fuel;
+                !other.dominated
+            });
+
+            labels.push(Label {
+                cost,
+                node,
+                fuel,
+                previous: Some((index, step)),
+                dominated: false,
+            });
+            frontier[node].push(labels.len() - 1);
+            heap.push(Queued {
+                cost,
+                label: labels.len() - 1,
+            });
+        }
+    }
+
+    None
+}
+
+fn route(labels: &[Label], end: usize) -> Route {
+    let mut steps = Vec::new();
+    let mut label = end;
+    while let Some((previous, step)) = &labels[label].previous {
+        steps.push(step.clone());
+        label = *previous;
+    }
+    steps.reverse();
+
+    Route {
+        fuel: steps.iter().map(RouteStep::fuel).sum(),
+        duration: steps.iter().map(RouteStep::duration).sum(),
+        steps,
+    }
+}
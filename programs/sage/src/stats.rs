@@ -0,0 +1,237 @@
+//! Derived ship and fleet metrics from [`ShipStats`](crate::typedefs::ShipStats).
+//!
+//! Distances are in sectors (AU). On chain speeds are stored in millionths of AU per second,
+//! the max warp distance in hundredths of AU and fuel consumption rates per hundredth of AU.
+use std::fmt;
+
+use crate::{state, typedefs::ShipStats};
+
+/// Scale of `subwarp_speed` and `warp_speed`.
+pub const SPEED_SCALE: f64 = 1_000_000.0;
+/// Scale of `max_warp_distance`.
+pub const DISTANCE_SCALE: f64 = 100.0;
+/// Scale of `subwarp_fuel_consumption_rate` and `warp_fuel_consumption_rate`.
+pub const FUEL_RATE_SCALE: f64 = 100.0;
+/// Scale of `mining_rate`.
+pub const MINING_RATE_SCALE: f64 = 10_000.0;
+
+/// Ship stats in human units.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DerivedStats {
+    /// Max distance of a single warp, AU.
+    pub warp_range: f64,
+    /// AU per second.
+    pub warp_speed: f64,
+    /// AU per second.
+    pub subwarp_speed: f64,
+    /// Fuel burned per AU warped.
+    pub warp_fuel_per_au: f64,
+    /// Fuel burned per AU in subwarp.
+    pub subwarp_fuel_per_au: f64,
+    /// Seconds until the next warp.
+    pub warp_cool_down: u16,
+    /// Fuel burned to leave a planet's gravity well.
+    pub planet_exit_fuel_amount: u32,
+    pub cargo_capacity: u32,
+    pub fuel_capacity: u32,
+    pub ammo_capacity: u32,
+    /// Resource mined per second, before richness and hardness are applied.
+    pub mining_rate: f64,
+    pub required_crew: u16,
+    pub passenger_capacity: u16,
+}
+
+impl DerivedStats {
+    pub fn from_stats(stats: &ShipStats) -> Self {
+        let movement = &stats.movement_stats;
+        let cargo = &stats.cargo_stats;
+        let misc = &stats.misc_stats;
+
+        DerivedStats {
+            warp_range: movement.max_warp_distance as f64 / DISTANCE_SCALE,
+            warp_speed: movement.warp_speed as f64 / SPEED_SCALE,
+            subwarp_speed: movement.subwarp_speed as f64 / SPEED_SCALE,
+            warp_fuel_per_au: movement.warp_fuel_consumption_rate as f64 / FUEL_RATE_SCALE,
+            subwarp_fuel_per_au: movement.subwarp_fuel_consumption_rate as f64 / FUEL_RATE_SCALE,
+            warp_cool_down: movement.warp_cool_down,
+            planet_exit_fuel_amount: movement.planet_exit_fuel_amount,
+            cargo_capacity: cargo.cargo_capacity,
+            fuel_capacity: cargo.fuel_capacity,
+            ammo_capacity: cargo.ammo_capacity,
+            mining_rate: cargo.mining_rate as f64 / MINING_RATE_SCALE,
+            required_crew: misc.required_crew,
+            passenger_capacity: misc.passenger_capacity,
+        }
+    }
+
+    /// Seconds to warp `distance` AU, ignoring the warp range.
+    pub fn warp_time(&self, distance: f64) -> Option<f64> {
+        (self.warp_speed > 0.0).then(|| distance / self.warp_speed)
+    }
+
+    /// Seconds to subwarp `distance` AU.
+    pub fn subwarp_time(&self, distance: f64) -> Option<f64> {
+        (self.subwarp_speed > 0.0).then(|| distance / self.subwarp_speed)
+    }
+
+    pub fn warp_fuel(&self, distance: f64) -> f64 {
+        distance * self.warp_fuel_per_au
+    }
+
+    pub fn subwarp_fuel(&self, distance: f64) -> f64 {
+        distance * self.subwarp_fuel_per_au
+    }
+
+    /// AU that can be warped on a full fuel tank, across several warps.
+    pub fn warp_distance_per_tank(&self) -> f64 {
+        match self.warp_fuel_per_au > 0.0 {
+            true => self.fuel_capacity as f64 / self.warp_fuel_per_au,
+            false => f64::INFINITY,
+        }
+    }
+}
+
+impl state::Ship {
+    pub fn derived_stats(&self) -> DerivedStats {
+        DerivedStats::from_stats(&self.stats)
+    }
+}
+
+impl state::Fleet {
+    /// Derived stats of the fleet's aggregated stats.
+    pub fn derived_stats(&self) -> DerivedStats {
+        DerivedStats::from_stats(&self.stats)
+    }
+}
+
+/// A metric ships are ranked by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Metric {
+    WarpRange,
+    WarpSpeed,
+    SubwarpSpeed,
+    WarpFuelPerAu,
+    SubwarpFuelPerAu,
+    CargoCapacity,
+    FuelCapacity,
+    AmmoCapacity,
+    MiningRate,
+    RequiredCrew,
+}
+
+impl Metric {
+    pub const ALL: [Metric; 10] = [
+        Metric::WarpRange,
+        Metric::WarpSpeed,
+        Metric::SubwarpSpeed,
+        Metric::WarpFuelPerAu,
+        Metric::SubwarpFuelPerAu,
+        Metric::CargoCapacity,
+        Metric::FuelCapacity,
+        Metric::AmmoCapacity,
+        Metric::MiningRate,
+        Metric::RequiredCrew,
+    ];
+
+    pub fn value(&self, stats: &DerivedStats) -> f64 {
+        match self {
+            Metric::WarpRange => stats.warp_range,
+            Metric::WarpSpeed => stats.warp_speed,
+            Metric::SubwarpSpeed => stats.subwarp_speed,
+            Metric::WarpFuelPerAu => stats.warp_fuel_per_au,
+            Metric::SubwarpFuelPerAu => stats.subwarp_fuel_per_au,
+            Metric::CargoCapacity => stats.cargo_capacity as f64,
+            Metric::FuelCapacity => stats.fuel_capacity as f64,
+            Metric::AmmoCapacity => stats.ammo_capacity as f64,
+            Metric::MiningRate => stats.mining_rate,
+            Metric::RequiredCrew => stats.required_crew as f64,
+        }
+    }
+
+    /// Fuel consumption and crew requirements rank lowest first.
+    pub fn higher_is_better(&self) -> bool {
+        !matches!(
+            self,
+            Metric::WarpFuelPerAu | Metric::SubwarpFuelPerAu | Metric::RequiredCrew
+        )
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Metric::WarpRange => "warp range (AU)",
+            Metric::WarpSpeed => "warp speed (AU/s)",
+            Metric::SubwarpSpeed => "subwarp speed (AU/s)",
+            Metric::WarpFuelPerAu => "warp fuel per AU",
+            Metric::SubwarpFuelPerAu => "subwarp fuel per AU",
+            Metric::CargoCapacity => "cargo capacity",
+            Metric::FuelCapacity => "fuel capacity",
+            Metric::AmmoCapacity => "ammo capacity",
+            Metric::MiningRate => "mining rate (/s)",
+            Metric::RequiredCrew => "required crew",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Indices of `ships` ordered best first by `metric`.
+pub fn rank(ships: &[(String, DerivedStats)], metric: Metric) -> Vec<usize> {
+    let mut ranked: Vec<usize> = (0..ships.len()).collect();
+    ranked.sort_by(|a, b| {
+        let a = metric.value(&ships[*a].1);
+        let b = metric.value(&ships[*b].1);
+        match metric.higher_is_better() {
+            true => b.total_cmp(&a),
+            false => a.total_cmp(&b),
+        }
+    });
+    ranked
+}
+
+/// Ships ranked by every [`Metric`].
+#[derive(Debug, Clone)]
+pub struct ComparisonReport {
+    pub ships: Vec<(String, DerivedStats)>,
+    pub rankings: Vec<(Metric, Vec<usize>)>,
+}
+
+/// Ranks named ships by every metric.
+pub fn compare(ships: Vec<(String, DerivedStats)>) -> ComparisonReport {
+    let rankings = Metric::ALL
+        .iter()
+        .map(|metric| (*metric, rank(&ships, *metric)))
+        .collect();
+
+    ComparisonReport { ships, rankings }
+}
+
+impl ComparisonReport {
+    /// Position (0 is best) of the ship in the ranking of `metric`.
+    pub fn position(&self, name: &str, metric: Metric) -> Option<usize> {
+        let (_, ranking) = self.rankings.iter().find(|(m, _)| *m == metric)?;
+        ranking
+            .iter()
+            .position(|index| self.ships[*index].0 == name)
+    }
+}
+
+impl fmt::Display for ComparisonReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (metric, ranking) in self.rankings.iter() {
+            writeln!(f, "{}", metric)?;
+            for (position, index) in ranking.iter().enumerate() {
+                let (name, stats) = &self.ships[*index];
+                writeln!(
+                    f,
+                    "  {:>3}. {:<32} {}",
+                    position + 1,
+                    name,
+                    metric.value(stats)
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
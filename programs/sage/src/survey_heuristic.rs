@@ -0,0 +1,252 @@
+//! Heuristic survey data unit (SDU) sector scores, to rank sectors to scan.
+//!
+//! This is not SAGE's scan chance: the on chain formula isn't published, so a score is not the
+//! probability of a scan finding SDUs. Compare scores of the same tracker and time, don't read
+//! them as odds.
+//!
+//! The score of a sector blends a noise term with a distance term, built from the tracker's
+//! fields:
+//!
+//! - the noise is a smooth 3D value noise sampled at `(x * x_mul, y * y_mul, now / t_max * z_mul)`
+//!   (multipliers in thousandths), so it changes every `t_max` seconds;
+//! - the distance term grows with the distance to the nearest CSS (up to `css_max_distance`) and
+//!   shrinks with the distance to the origin (up to `origin_max_distance`);
+//! - `distance_weighting` (in percent) is the share of the distance term.
+//!
+//! The score is scaled by the sector's chance, assumed to drop to `last_scan_chance` (in
+//! millionths) on a scan and to regenerate linearly back to full over `scan_chance_regen_period`
+//! seconds.
+//!
+//! ```ignore
+//! let heatmap = survey_heuristic::heatmap(&tracker, &sectors, now);
+//! std::fs::write("sdu.csv", heatmap.to_csv())?;
+//! ```
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    movement::distance,
+    state::{Sector, SurveyDataUnitTracker},
+};
+
+/// Scale of `Sector.last_scan_chance`.
+pub const SCAN_CHANCE_SCALE: f64 = 1_000_000.0;
+/// Scale of `x_mul`, `y_mul` and `z_mul`.
+pub const NOISE_MUL_SCALE: f64 = 1_000.0;
+/// Scale of `distance_weighting`.
+pub const DISTANCE_WEIGHTING_SCALE: f64 = 100.0;
+
+/// Last scan of a sector, from `Sector.last_scan_time` and `Sector.last_scan_chance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SectorScan {
+    pub last_scan_time: i64,
+    pub last_scan_chance: u32,
+}
+
+/// SDU score of a sector, higher is better.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SectorScore {
+    /// Score before the sector's scan chance, `0..=1`.
+    pub base: f64,
+    /// Regenerated scan chance of the sector, `0..=1`.
+    pub sector_chance: f64,
+    /// `base * sector_chance`.
+    pub score: f64,
+}
+
+impl Sector {
+    pub fn last_scan(&self) -> SectorScan {
+        SectorScan {
+            last_scan_time: self.last_scan_time,
+            last_scan_chance: self.last_scan_chance,
+        }
+    }
+}
+
+pub fn in_range(tracker: &SurveyDataUnitTracker, coordinates: [i64; 2]) -> bool {
+    let [min, max] = tracker.coordinates_range;
+    coordinates
+        .iter()
+        .all(|coordinate| (min..=max).contains(coordinate))
+}
+
+/// Scan chance of a sector at `now`, regenerating from its last scan.
+pub fn sector_chance(tracker: &SurveyDataUnitTracker, scan: &SectorScan, now: i64) -> f64 {
+    let last_chance = (scan.last_scan_chance as f64 / SCAN_CHANCE_SCALE).clamp(0.0, 1.0);
+    let regen = match tracker.scan_chance_regen_period {
+        period if period > 0 => {
+            ((now - scan.last_scan_time) as f64 / period as f64).clamp(0.0, 1.0)
+        }
+        _ => 1.0,
+    };
+    last_chance + (1.0 - last_chance) * regen
+}
+
+/// Score at `coordinates` at `now`, before the sector's scan chance.
+pub fn base_score(tracker: &SurveyDataUnitTracker, coordinates: [i64; 2], now: i64) -> f64 {
+    if !in_range(tracker, coordinates) {
+        return 0.0;
+    }
+
+    let t = match tracker.t_max {
+        t_max if t_max > 0 => now as f64 / t_max as f64,
+        _ => 0.0,
+    };
+    let noise = value_noise(
+        coordinates[0] as f64 * tracker.x_mul as f64 / NOISE_MUL_SCALE,
+        coordinates[1] as f64 * tracker.y_mul as f64 / NOISE_MUL_SCALE,
+        t * tracker.z_mul as f64 / NOISE_MUL_SCALE,
+    );
+
+    let ratio = |distance: f64, max: u32| match max {
+        0 => 1.0,
+        max => (distance / max as f64).clamp(0.0, 1.0),
+    };
+    let css_distance = tracker
+        .css_coordinates
+        .iter()
+        .map(|css| distance(*css, coordinates))
+        .fold(f64::INFINITY, f64::min);
+    let origin_distance = distance(tracker.origin_coordinates, coordinates);
+    let distance_term = (ratio(css_distance, tracker.css_max_distance)
+        + (1.0 - ratio(origin_distance, tracker.origin_max_distance)))
+        / 2.0;
+
+    let weighting = (tracker.distance_weighting as f64 / DISTANCE_WEIGHTING_SCALE).clamp(0.0, 1.0);
+    noise * (1.0 - weighting) + distance_term * weighting
+}
+
+/// Score of the sector at `coordinates` at `now`.
+pub fn score(
+    tracker: &SurveyDataUnitTracker,
+    coordinates: [i64; 2],
+    scan: &SectorScan,
+    now: i64,
+) -> SectorScore {
+    let base = base_score(tracker, coordinates, now);
+    let sector_chance = sector_chance(tracker, scan, now);
+
+    SectorScore {
+        base,
+        sector_chance,
+        score: base * sector_chance,
+    }
+}
+
+/// Score of `sector` at `now`.
+pub fn sector_score(tracker: &SurveyDataUnitTracker, sector: &Sector, now: i64) -> SectorScore {
+    score(tracker, sector.coordinates, &sector.last_scan(), now)
+}
+
+/// Sector scores across the tracker's coordinates range.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heatmap {
+    pub min: i64,
+    pub max: i64,
+    /// Rows by y from `min` to `max`, columns by x.
+    pub rows: Vec<Vec<SectorScore>>,
+}
+
+impl Heatmap {
+    pub fn get(&self, coordinates: [i64; 2]) -> Option<&SectorScore> {
+        let [x, y] = coordinates;
+        if x < self.min || y < self.min {
+            return None;
+        }
+        self.rows
+            .get((y - self.min) as usize)?
+            .get((x - self.min) as usize)
+    }
+
+    /// The `n` sectors with the highest score, best first.
+    pub fn best(&self, n: usize) -> Vec<([i64; 2], SectorScore)> {
+        let mut sectors: Vec<([i64; 2], SectorScore)> = self
+            .rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .map(move |(x, score)| ([self.min + x as i64, self.min + y as i64], *score))
+            })
+            .collect();
+        sectors.sort_by(|(_, a), (_, b)| b.score.total_cmp(&a.score));
+        sectors.truncate(n);
+        sectors
+    }
+
+    /// `x,y,base,sector_chance,score` lines with a header.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("x,y,base,sector_chance,score\n");
+        for (y, row) in self.rows.iter().enumerate() {
+            for (x, score) in row.iter().enumerate() {
+                let _ = writeln!(
+                    csv,
+                    "{},{},{},{},{}",
+                    self.min + x as i64,
+                    self.min + y as i64,
+                    score.base,
+                    score.sector_chance,
+                    score.score
+                );
+            }
+        }
+        csv
+    }
+}
+
+/// Scores of every sector of the tracker's coordinates range at `now`, `scans` holds
+/// the last scans by sector coordinates, sectors without one have a full scan chance.
+pub fn heatmap(
+    tracker: &SurveyDataUnitTracker,
+    scans: &HashMap<[i64; 2], SectorScan>,
+    now: i64,
+) -> Heatmap {
+    let [min, max] = tracker.coordinates_range;
+    let never_scanned = SectorScan {
+        last_scan_time: i64::MIN / 2,
+        last_scan_chance: 0,
+    };
+
+    let rows = (min..=max)
+        .map(|y| {
+            (min..=max)
+                .map(|x| {
+                    let scan = scans.get(&[x, y]).unwrap_or(&never_scanned);
+                    score(tracker, [x, y], scan, now)
+                })
+                .collect()
+        })
+        .collect();
+
+    Heatmap { min, max, rows }
+}
+
+/// Smooth 3D value noise in `0..=1`.
+fn value_noise(x: f64, y: f64, z: f64) -> f64 {
+    let (x0, y0, z0) = (x.floor(), y.floor(), z.floor());
+    let (fx, fy, fz) = (smooth(x - x0), smooth(y - y0), smooth(z - z0));
+    let corner =
+        |dx: i64, dy: i64, dz: i64| lattice(x0 as i64 + dx, y0 as i64 + dy, z0 as i64 + dz);
+    let lerp = |a: f64, b: f64, t: f64| a + (b - a) * t;
+
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), fx);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), fx);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), fx);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), fx);
+    lerp(lerp(x00, x10, fy), lerp(x01, x11, fy), fz)
+}
+
+fn smooth(t: f64) -> f64 {
+    t * t * (3.0 - 2.0 * t)
+}
+
+/// Pseudo random value in `0..=1` of a lattice point.
+fn lattice(x: i64, y: i64, z: i64) -> f64 {
+    let mut h = (x as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        ^ (y as u64).wrapping_mul(0xC2B2_AE3D_27D4_EB4F)
+        ^ (z as u64).wrapping_mul(0x1656_67B1_9E37_79F9);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xFF51_AFD7_ED55_8CCD);
+    h ^= h >> 33;
+    (h >> 11) as f64 / (1u64 << 53) as f64
+}
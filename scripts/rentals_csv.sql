@@ -4,6 +4,7 @@ COPY (
         rc.rate AS fleet_rental_rate,
         rc.current_rental_state AS fleet_rental_state,
         f.faction,
+        sf.name AS faction_name,
         json_extract (f.ship_counts, '$.total') AS fleet_ship_counts,
         f.fleet_label,
         s.name AS ship_name,
@@ -15,6 +16,7 @@ COPY (
     FROM
         db.rental_contract_states AS rc
         JOIN db.sage_fleets AS f on f.pubkey = rc.fleet
+        JOIN db.sage_factions AS sf ON sf.id = f.faction
         JOIN db.sage_fleet_ships AS fs ON fs.pubkey = f.fleet_ships
         JOIN db.sage_ships AS s ON s.pubkey = fs.ship
 ) TO 'tmp/rentals.csv' (HEADER, DELIMITER ',');
@@ -0,0 +1,123 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, packet::PACKET_DATA_SIZE, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction,
+};
+
+use staratlas_starbased_sdk::{
+    admin::RegisterSector,
+    batch::{transaction_size, BatchError, Batcher},
+};
+
+/// Compute units counted for a transfer, with room for the limit instruction.
+const TRANSFER_COMPUTE_UNITS: u32 = 10_000;
+
+fn transfer<'a>(from_kp: &'a Keypair, to_pk: &Pubkey, batcher: Batcher<'a>) -> Batcher<'a> {
+    let ix = system_instruction::transfer(&from_kp.pubkey(), to_pk, LAMPORTS_PER_SOL);
+    batcher.add_instructions(
+        vec![ix],
+        vec![from_kp.into()],
+        vec![*to_pk],
+        TRANSFER_COMPUTE_UNITS,
+    )
+}
+
+#[test]
+fn batch_test() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000 * LAMPORTS_PER_SOL)
+        .unwrap();
+
+    let recipients: Vec<Pubkey> = (0..60).map(|_| Pubkey::new_unique()).collect();
+    let mut batcher = Batcher::new(&payer_kp);
+    for to_pk in recipients.iter() {
+        batcher = transfer(&payer_kp, to_pk, batcher);
+    }
+
+    let chunks = batcher.chunks();
+    assert!(chunks.len() > 1);
+    for chunk in chunks.iter() {
+        let instructions: Vec<_> = chunk
+            .clone()
+            .map(|index| {
+                system_instruction::transfer(
+                    &payer_kp.pubkey(),
+                    &recipients[index],
+                    LAMPORTS_PER_SOL,
+                )
+            })
+            .collect();
+        assert!(transaction_size(&instructions, &payer_kp.pubkey()) <= PACKET_DATA_SIZE);
+    }
+
+    let results = batcher.send(&mut svm);
+    assert_eq!(results.len(), chunks.len());
+    assert!(results.iter().all(|chunk| chunk.result.is_ok()));
+    let pubkeys: Vec<Pubkey> = results
+        .into_iter()
+        .flat_map(|chunk| chunk.pubkeys)
+        .collect();
+    assert_eq!(pubkeys, recipients);
+    for to_pk in recipients.iter() {
+        assert_eq!(svm.get_balance(to_pk), Some(LAMPORTS_PER_SOL));
+    }
+}
+
+#[test]
+fn batch_compute_units_test() {
+    let payer_kp = Keypair::new();
+    let mut batcher = Batcher::new(&payer_kp);
+    for _ in 0..5 {
+        let ix = system_instruction::transfer(&payer_kp.pubkey(), &Pubkey::new_unique(), 1);
+        batcher = batcher.add_instructions(vec![ix], vec![], vec![], 500_000);
+    }
+
+    assert_eq!(batcher.chunks(), vec![0..2, 2..4, 4..5]);
+
+    // builders without a limit count the runtime default per instruction
+    let (authority_kp, profile_pk, game_pk) =
+        (Keypair::new(), Pubkey::new_unique(), Pubkey::new_unique());
+    let mut batcher = Batcher::new(&payer_kp).set_max_compute_units(400_000);
+    for x in 0..4 {
+        batcher = batcher.add(
+            RegisterSector::new(&authority_kp, &profile_pk, &profile_pk, &game_pk, &payer_kp)
+                .set_coordinates([x, 0]),
+        );
+    }
+    assert_eq!(batcher.chunks(), vec![0..2, 2..4]);
+}
+
+#[test]
+fn batch_failure_test() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000 * LAMPORTS_PER_SOL)
+        .unwrap();
+    let unfunded_kp = Keypair::new();
+
+    // one transfer per chunk
+    let mut batcher = Batcher::new(&payer_kp).set_max_compute_units(TRANSFER_COMPUTE_UNITS);
+    for from_kp in [&payer_kp, &unfunded_kp, &payer_kp] {
+        let ix = system_instruction::transfer(
+            &from_kp.pubkey(),
+            &Pubkey::new_unique(),
+            LAMPORTS_PER_SOL,
+        );
+        batcher = batcher.add_instructions(
+            vec![ix],
+            vec![from_kp.into()],
+            vec![],
+            TRANSFER_COMPUTE_UNITS,
+        );
+    }
+
+    // the unfunded transfer fails without retries and the last one is skipped
+    let results = batcher.send(&mut svm);
+    assert_eq!(results.len(), 3);
+    assert!(results[0].result.is_ok());
+    assert!(matches!(results[1].result, Err(BatchError::Transaction(_))));
+    assert_eq!(results[1].attempts, 1);
+    assert!(matches!(results[2].result, Err(BatchError::Skipped)));
+    assert_eq!(results[2].attempts, 0);
+}
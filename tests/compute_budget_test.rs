@@ -0,0 +1,171 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    compute_budget, native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair,
+    signer::Signer, system_instruction,
+};
+
+use staratlas_starbased_sdk::{
+    admin::{find_sector_pda, RegisterSector, RegisterStarbase},
+    batch::{DEFAULT_INSTRUCTION_COMPUTE_UNITS, MAX_COMPUTE_UNITS},
+    compute_budget::{ComputeBudget, ComputeUnitLimit},
+    profile::permissions::SAGE_MANAGER_KEY_INDEX,
+    sender::Sender,
+    testing::TestWorld,
+    tx::TxBuilder,
+    Faction,
+};
+
+fn transfer(payer_kp: &Keypair, lamports: u64) -> TxBuilder<'_> {
+    let ix = system_instruction::transfer(&payer_kp.pubkey(), &Pubkey::new_unique(), lamports);
+    TxBuilder::new(payer_kp)
+        .add_instructions(vec![ix], vec![], vec![])
+        .set_compute_budget(ComputeBudget::new().set_auto_unit_limit(50))
+}
+
+#[test]
+fn compute_budget_test() {
+    let mut test_world = TestWorld::new();
+    let world = &test_world.world;
+    let funder_kp = &test_world.funder;
+
+    let coordinates = [7, 7];
+    let (sector_pk, _) = find_sector_pda(&world.game, coordinates);
+
+    let tx_builder = TxBuilder::new(funder_kp)
+        .add(
+            RegisterSector::new(
+                &world.authority,
+                &world.profile,
+                &world.profile,
+                &world.game,
+                funder_kp,
+            )
+            .set_coordinates(coordinates)
+            .set_name("New Sector".into())
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .set_compute_budget(
+                ComputeBudget::new()
+                    .set_unit_limit(100_000)
+                    .set_unit_price(10),
+            ),
+        )
+        .add(
+            RegisterStarbase::new(
+                &world.authority,
+                &world.profile,
+                &world.game,
+                &world.game_state,
+                &sector_pk,
+                funder_kp,
+            )
+            .set_coordinates(coordinates)
+            .set_name("New Starbase".into())
+            .set_starbase_level_index(1)
+            .set_faction(Faction::MUD)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+            .set_compute_budget(
+                ComputeBudget::new()
+                    .set_unit_limit(150_000)
+                    .set_unit_price(426),
+            ),
+        );
+    assert_eq!(
+        tx_builder.compute_budget(),
+        ComputeBudget::new()
+            .set_unit_limit(250_000)
+            .set_unit_price(426)
+    );
+
+    // limit and price ahead of the builders' instructions
+    let tx = tx_builder
+        .transaction(test_world.svm.latest_blockhash())
+        .unwrap();
+    assert_eq!(tx.message.instructions.len(), 4);
+    for ix in &tx.message.instructions[..2] {
+        assert_eq!(
+            tx.message.account_keys[ix.program_id_index as usize],
+            compute_budget::id()
+        );
+    }
+
+    tx_builder.send(&mut test_world.svm).unwrap();
+}
+
+#[test]
+fn auto_compute_budget_test() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 10 * LAMPORTS_PER_SOL)
+        .unwrap();
+
+    // consumed units plus the margin
+    let mut tx_builder = transfer(&payer_kp, LAMPORTS_PER_SOL);
+    let block_hash = svm.latest_blockhash();
+    let simulation =
+        Sender::simulate(&mut svm, tx_builder.transaction(block_hash).unwrap()).unwrap();
+    assert!(simulation.err.is_none());
+    tx_builder.resolve_compute_budget(&mut svm, &[]).unwrap();
+    assert_eq!(
+        tx_builder.compute_budget().unit_limit,
+        Some(ComputeUnitLimit::Fixed(
+            (simulation.units_consumed * 3 / 2) as u32
+        ))
+    );
+    tx_builder.send(&mut svm).unwrap();
+
+    // a failing simulation keeps the maximum, sending reports the error
+    let mut tx_builder = transfer(&payer_kp, 100 * LAMPORTS_PER_SOL);
+    tx_builder.resolve_compute_budget(&mut svm, &[]).unwrap();
+    assert_eq!(
+        tx_builder.compute_budget().unit_limit,
+        Some(ComputeUnitLimit::Fixed(MAX_COMPUTE_UNITS))
+    );
+    assert!(tx_builder.send(&mut svm).is_err());
+}
+
+#[test]
+fn default_unit_limit_test() {
+    let (authority_kp, funder_kp) = (Keypair::new(), Keypair::new());
+    let (profile_pk, game_pk, game_state_pk) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let (sector_pk, _) = find_sector_pda(&game_pk, [7, 7]);
+
+    // the starbase without a limit counts the runtime default of its instruction
+    let tx_builder = TxBuilder::new(&funder_kp)
+        .add(
+            RegisterSector::new(
+                &authority_kp,
+                &profile_pk,
+                &profile_pk,
+                &game_pk,
+                &funder_kp,
+            )
+            .set_coordinates([7, 7])
+            .set_compute_budget(ComputeBudget::new().set_unit_limit(100_000)),
+        )
+        .add(RegisterStarbase::new(
+            &authority_kp,
+            &profile_pk,
+            &game_pk,
+            &game_state_pk,
+            &sector_pk,
+            &funder_kp,
+        ));
+    assert_eq!(
+        tx_builder.compute_budget(),
+        ComputeBudget::new().set_unit_limit(100_000 + DEFAULT_INSTRUCTION_COMPUTE_UNITS)
+    );
+
+    // without any limit, none is requested
+    let tx_builder = TxBuilder::new(&funder_kp).add(RegisterSector::new(
+        &authority_kp,
+        &profile_pk,
+        &profile_pk,
+        &game_pk,
+        &funder_kp,
+    ));
+    assert_eq!(tx_builder.compute_budget(), ComputeBudget::new());
+}
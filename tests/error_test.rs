@@ -0,0 +1,74 @@
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+use staratlas_starbased_sdk::error::{
+    CargoErrorCode, Program, ProgramError, SageErrorCode, StarAtlasError,
+};
+
+#[test]
+fn error_test() {
+    assert_eq!(Program::Sage.errors().len(), 97);
+    assert_eq!(Program::Cargo.errors().len(), 26);
+    assert_eq!(Program::Crew.errors().len(), 20);
+    assert_eq!(Program::PlayerProfile.errors().len(), 16);
+    assert_eq!(Program::FleetRentals.errors().len(), 11);
+
+    let sage = Program::Sage.id();
+    let cargo = Program::Cargo.id();
+    assert_eq!(Program::from_id(&cargo), Some(Program::Cargo));
+
+    // a SAGE instruction at index 1 failing in SAGE
+    let logs: Vec<String> = [
+        "Program ComputeBudget111111111111111111111111111111 invoke [1]".to_string(),
+        "Program ComputeBudget111111111111111111111111111111 success".to_string(),
+        format!("Program {} invoke [1]", sage),
+        "Program log: Instruction: WarpToCoordinate".to_string(),
+        "Program log: AnchorError occurred. Error Code: InsufficientFuel.".to_string(),
+        format!("Program {} failed: custom program error: 0x1781", sage),
+    ]
+    .to_vec();
+    let err = TransactionError::InstructionError(1, InstructionError::Custom(6017));
+    let sage_err = StarAtlasError::from_transaction_error(&err, &logs).unwrap();
+    assert_eq!(sage_err.program, Some(Program::Sage));
+    assert_eq!(
+        sage_err.error,
+        Some(ProgramError::Sage(SageErrorCode::InsufficientFuel))
+    );
+    assert_eq!(sage_err.name, "InsufficientFuel");
+    assert_eq!(sage_err.message, "Insufficient Fuel to complete movement");
+    assert_eq!(sage_err.instruction_index, 1);
+    assert_eq!(sage_err.logs.len(), 4);
+    assert_eq!(
+        sage_err.to_string(),
+        "SAGE error InsufficientFuel (6017) in instruction 1: Insufficient Fuel to complete movement"
+    );
+
+    // a cargo error raised in a CPI from SAGE
+    let logs: Vec<String> = [
+        format!("Program {} invoke [1]", sage),
+        format!("Program {} invoke [2]", cargo),
+        format!("Program {} failed: custom program error: 0x1770", cargo),
+        format!("Program {} failed: custom program error: 0x1770", sage),
+    ]
+    .to_vec();
+    let err = TransactionError::InstructionError(0, InstructionError::Custom(6000));
+    let cargo_err = StarAtlasError::from_transaction_error(&err, &logs).unwrap();
+    assert_eq!(cargo_err.program, Some(Program::Cargo));
+    assert_eq!(
+        cargo_err.error,
+        Some(ProgramError::Cargo(CargoErrorCode::StatOutOfBounds))
+    );
+    assert_eq!(cargo_err.logs, logs[1..=2].to_vec());
+
+    // an Anchor framework error raised by SAGE
+    let logs = vec![
+        format!("Program {} invoke [1]", sage),
+        format!("Program {} failed: custom program error: 0xbc4", sage),
+    ];
+    let err = TransactionError::InstructionError(0, InstructionError::Custom(3012));
+    let anchor_err = StarAtlasError::from_transaction_error(&err, &logs).unwrap();
+    assert!(matches!(anchor_err.error, Some(ProgramError::Anchor(_))));
+    assert_eq!(anchor_err.name, "AccountNotInitialized");
+
+    let err = TransactionError::InstructionError(0, InstructionError::MissingAccount);
+    assert!(StarAtlasError::from_transaction_error(&err, &[]).is_none());
+}
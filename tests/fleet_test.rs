@@ -0,0 +1,190 @@
+use anchor_lang::AccountDeserialize;
+use solana_sdk::{clock::Clock, pubkey::Pubkey};
+use std::collections::HashMap;
+
+use staratlas_sage::{
+    aggregate,
+    state::{Fleet, Ship},
+};
+
+use staratlas_starbased_sdk::{
+    fleet::{ScanError, ScanForSurveyDataUnits, ScanXpAccounts},
+    sender::Sender,
+    testing::{TestWorld, TEST_FOOD, TEST_SHIP},
+    world::SDU,
+    Faction,
+};
+
+#[test]
+fn fleet_test() {
+    let mut test_world = TestWorld::new();
+
+    let player = test_world.new_player(Faction::MUD);
+    assert!(player.starbase_player.is_some());
+
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 2)]);
+
+    let fleet_acc = test_world.svm.get_account(&fleet.fleet).unwrap();
+    let fleet_data = Fleet::try_deserialize(&mut &fleet_acc.data[..]).unwrap();
+    assert_eq!(fleet_data.owner_profile.as_ref(), player.profile.as_ref());
+    assert_eq!(fleet_data.fleet_ships.as_ref(), fleet.fleet_ships.as_ref());
+    assert_eq!(fleet_data.ship_counts.total, 2);
+}
+
+#[test]
+fn fleet_aggregate_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::ONI);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 3)]);
+
+    let fleet_acc = test_world.svm.get_account(&fleet.fleet).unwrap();
+    let fleet_data = Fleet::try_deserialize(&mut &fleet_acc.data[..]).unwrap();
+    let fleet_ships_acc = test_world.svm.get_account(&fleet.fleet_ships).unwrap();
+    let (_, infos) = aggregate::fleet_ships_infos(&fleet_ships_acc.data).unwrap();
+    assert_eq!(infos.len(), 1);
+
+    let ship_pk = test_world.world.ships[TEST_SHIP];
+    let ship_acc = test_world.svm.get_account(&ship_pk).unwrap();
+    let ship = Ship::try_deserialize(&mut &ship_acc.data[..]).unwrap();
+    let ships = HashMap::from([(infos[0].ship, ship)]);
+
+    let fleet_aggregate = aggregate::recompute(&fleet_data, &infos, &ships).unwrap();
+    assert_eq!(fleet_aggregate.ship_counts.total, 3);
+    assert_eq!(fleet_aggregate.ship_counts.small, 3);
+    assert_eq!(fleet_aggregate.stats.cargo_stats.fuel_capacity, 3000);
+    assert!(!fleet_aggregate.needs_update());
+    assert!(
+        fleet_aggregate.is_consistent(),
+        "{:?}",
+        fleet_aggregate.mismatches
+    );
+}
+
+#[test]
+fn fleet_scan_cooldown_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::ONI);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    let fleet_acc = test_world.svm.get_account(&fleet.fleet).unwrap();
+    let fleet_data = Fleet::try_deserialize(&mut &fleet_acc.data[..]).unwrap();
+    let cargo_hold = Pubkey::new_from_array(fleet_data.cargo_hold.to_bytes());
+
+    // the scan cooldown expires 10 seconds from now
+    let mut clock = test_world.svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = fleet_data.scan_cooldown_expires_at - 10;
+    test_world.svm.set_sysvar(&clock);
+
+    let world = &test_world.world;
+    let tracker = world.survey_data_unit_tracker.unwrap();
+    let sector = world.sectors["ONI Sector"];
+    let unknown = Pubkey::new_unique();
+    let xp_accounts = ScanXpAccounts {
+        data_running_user_points_pk: &unknown,
+        data_running_points_category_pk: &unknown,
+        data_running_points_modifier_pk: &unknown,
+        council_rank_user_points_pk: &unknown,
+        council_rank_points_category_pk: &unknown,
+        council_rank_points_modifier_pk: &unknown,
+        progression_config_pk: &unknown,
+        points_program_pk: &unknown,
+    };
+    let scan = ScanForSurveyDataUnits::new(
+        &player.keypair,
+        &player.profile,
+        &player.profile_faction,
+        &fleet.fleet,
+        &world.game,
+        &world.game_state,
+        &tracker,
+        &cargo_hold,
+        &sector,
+        &world.mints[SDU],
+        &world.mints[TEST_FOOD],
+        &world.cargo_types[SDU],
+        &world.cargo_types[TEST_FOOD],
+        &world.cargo_stats_definition,
+        &xp_accounts,
+        &test_world.funder,
+    );
+
+    let expires_at = fleet_data.scan_cooldown_expires_at;
+    assert_eq!(
+        scan.cooldown(&mut test_world.svm).unwrap(),
+        Some(expires_at)
+    );
+    assert!(matches!(
+        scan.send(&mut test_world.svm),
+        Err(ScanError::ScanIsOnCooldown { expires_at: e }) if e == expires_at
+    ));
+    assert_eq!(scan.scan_loop(&mut test_world.svm, 0).unwrap(), 0);
+
+    // waiting sets the clock to the end of the cooldown
+    test_world.svm.wait_until(expires_at).unwrap();
+    assert_eq!(scan.cooldown(&mut test_world.svm).unwrap(), None);
+    assert_eq!(scan.cargo_used(&mut test_world.svm).unwrap(), 0);
+    assert_eq!(scan.sdu_amount(&mut test_world.svm).unwrap(), 0);
+}
+
+#[test]
+#[ignore = "a scan credits XP through the points program, which isn't vendored in programs/"]
+fn fleet_scan_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::ONI);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    let fleet_data = test_world.fleet(&fleet);
+    let cargo_hold = Pubkey::new_from_array(fleet_data.cargo_hold.to_bytes());
+    test_world.deposit_cargo(&player, &fleet, &cargo_hold, TEST_FOOD, 100);
+    test_world.undock(&player, &fleet);
+
+    let world = &test_world.world;
+    let tracker = world.survey_data_unit_tracker.unwrap();
+    let sector = world.sectors["ONI Sector"];
+    // the points program accounts, registered once the program is loaded
+    let unknown = Pubkey::new_unique();
+    let xp_accounts = ScanXpAccounts {
+        data_running_user_points_pk: &unknown,
+        data_running_points_category_pk: &unknown,
+        data_running_points_modifier_pk: &unknown,
+        council_rank_user_points_pk: &unknown,
+        council_rank_points_category_pk: &unknown,
+        council_rank_points_modifier_pk: &unknown,
+        progression_config_pk: &unknown,
+        points_program_pk: &unknown,
+    };
+    let scan = ScanForSurveyDataUnits::new(
+        &player.keypair,
+        &player.profile,
+        &player.profile_faction,
+        &fleet.fleet,
+        &world.game,
+        &world.game_state,
+        &tracker,
+        &cargo_hold,
+        &sector,
+        &world.mints[SDU],
+        &world.mints[TEST_FOOD],
+        &world.cargo_types[SDU],
+        &world.cargo_types[TEST_FOOD],
+        &world.cargo_stats_definition,
+        &xp_accounts,
+        &test_world.funder,
+    );
+
+    let expires_at = fleet_data.scan_cooldown_expires_at;
+    test_world.svm.wait_until(expires_at).unwrap();
+    scan.send(&mut test_world.svm).unwrap();
+
+    // the scan put the fleet back on cooldown
+    let scanned_expires_at = test_world.fleet(&fleet).scan_cooldown_expires_at;
+    assert!(scanned_expires_at > expires_at);
+    assert_eq!(
+        scan.cooldown(&mut test_world.svm).unwrap(),
+        Some(scanned_expires_at)
+    );
+
+    // the loop waits out both cooldowns
+    assert_eq!(scan.scan_loop(&mut test_world.svm, 2).unwrap(), 2);
+    assert!(test_world.fleet(&fleet).scan_cooldown_expires_at > scanned_expires_at);
+}
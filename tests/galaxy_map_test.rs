@@ -0,0 +1,59 @@
+use anchor_lang::{prelude::Pubkey, AccountDeserialize};
+use litesvm::LiteSVM;
+use solana_sdk::signature::{Keypair, Signer};
+
+use staratlas_sage::{
+    galaxy_map::GalaxyMap,
+    state::{MineItem, Planet, Resource, Sector, Starbase},
+};
+
+use staratlas_starbased_sdk as based_sdk;
+
+fn accounts<T: AccountDeserialize>(
+    svm: &LiteSVM,
+    keys: &std::collections::HashMap<String, solana_sdk::pubkey::Pubkey>,
+) -> Vec<(Pubkey, T)> {
+    keys.values()
+        .map(|key| {
+            let acc = svm.get_account(key).unwrap();
+            let data = T::try_deserialize(&mut &acc.data[..]).unwrap();
+            (Pubkey::new_from_array(key.to_bytes()), data)
+        })
+        .collect()
+}
+
+#[test]
+fn galaxy_map_test() {
+    let mut svm = based_sdk::testing::new_svm();
+
+    let funder_kp = Keypair::new();
+    svm.airdrop(&funder_kp.pubkey(), 100_000_000_000).unwrap();
+
+    let world = based_sdk::world::load(&mut svm, "data/world.toml", &funder_kp).unwrap();
+
+    let sectors: Vec<(Pubkey, Sector)> = accounts(&svm, &world.sectors);
+    let planets: Vec<(Pubkey, Planet)> = accounts(&svm, &world.planets);
+    let resources: Vec<(Pubkey, Resource)> = accounts(&svm, &world.resources);
+    let mine_items: Vec<(Pubkey, MineItem)> = accounts(&svm, &world.mine_items);
+    let starbases: Vec<(Pubkey, Starbase)> = accounts(&svm, &world.starbases);
+
+    let map = GalaxyMap::new(
+        sectors.iter().map(|(key, sector)| (*key, sector)),
+        planets.iter().map(|(key, planet)| (*key, planet)),
+        resources.iter().map(|(key, resource)| (*key, resource)),
+        mine_items.iter().map(|(key, mine_item)| (*key, mine_item)),
+        starbases.iter().map(|(key, starbase)| (*key, starbase)),
+    );
+    assert_eq!(map.sectors.len(), 2);
+
+    assert_eq!(map.sectors_with_resource("Hydrogen", 100), vec![[1, 1]]);
+    assert!(map.sectors_with_resource("Hydrogen", 101).is_empty());
+    assert!(map.sectors_with_resource("Carbon", 0).is_empty());
+
+    let starbase = Pubkey::new_from_array(world.starbases["Starbase Alpha"].to_bytes());
+    let (belt, distance) = map.nearest_asteroid_belt(&starbase).unwrap();
+    assert_eq!(belt.name, "Belt A");
+    assert_eq!(distance, 0.0);
+
+    assert_eq!(map.miners_per_resource().get("Hydrogen"), Some(&0));
+}
@@ -0,0 +1,83 @@
+use anchor_lang::{AccountDeserialize, Discriminator};
+use solana_sdk::pubkey;
+
+use staratlas_sage::state::{MineItem, Ship};
+
+use staratlas_starbased_sdk::{
+    galaxy::{Galaxy, GalaxyError, ItemType},
+    snapshot::Snapshot,
+    testing::{TestWorld, TEST_FOOD, TEST_SHIP},
+};
+
+#[test]
+fn galaxy_test() {
+    let galaxy = Galaxy::from_path("data/galaxy.json").unwrap();
+    assert_eq!(galaxy.items.len(), 292);
+    assert_eq!(galaxy.items_of(ItemType::Ship).count(), 59);
+
+    let pearce_x4 = galaxy
+        .item(&pubkey!("2iMhgB4pbdKvwJHVyitpvX5z1NBNypFonUgaSAt9dtDt"))
+        .unwrap();
+    assert_eq!(pearce_x4.symbol, "PX4");
+    assert_eq!(pearce_x4.attributes.make.as_deref(), Some("Pearce"));
+    assert_eq!(pearce_x4.label(), "Pearce X4 (xx-small, fighter)");
+
+    let hydrogen = pubkey!("HYDR4EPHJcDPcaLYUcNCtrXUdt1PnaN4MvE655pevBYp");
+    assert_eq!(galaxy.label(&hydrogen), "Hydrogen (raw material)");
+
+    let unknown = pubkey!("SAGE2HAwep459SNq61LHvjxPk4pLPEJLoMETef7f7EE");
+    assert_eq!(galaxy.label(&unknown), unknown.to_string());
+}
+
+#[test]
+fn galaxy_join_test() {
+    let test_world = TestWorld::new();
+    let world = &test_world.world;
+    let catalog = Galaxy::from_path("data/galaxy.json").unwrap();
+
+    // the catalog's Pearce X4 and Hydrogen for the test world's mints
+    let mut pearce_x4 = catalog
+        .item(&pubkey!("2iMhgB4pbdKvwJHVyitpvX5z1NBNypFonUgaSAt9dtDt"))
+        .unwrap()
+        .clone();
+    pearce_x4.mint = world.mints[TEST_SHIP].to_string();
+    let mut hydrogen = catalog
+        .item(&pubkey!("HYDR4EPHJcDPcaLYUcNCtrXUdt1PnaN4MvE655pevBYp"))
+        .unwrap()
+        .clone();
+    hydrogen.mint = world.mints[TEST_FOOD].to_string();
+    let galaxy = Galaxy::new(vec![pearce_x4, hydrogen]);
+
+    let ship_pk = world.ships[TEST_SHIP];
+    let ship_acc = test_world.svm.get_account(&ship_pk).unwrap();
+    let ship = Ship::try_deserialize(&mut &ship_acc.data[..]).unwrap();
+    assert_eq!(galaxy.ship_label(&ship), "Pearce X4 (xx-small, fighter)");
+
+    let mine_item_pk = world.mine_items[TEST_FOOD];
+    let mine_item_acc = test_world.svm.get_account(&mine_item_pk).unwrap();
+    let mine_item = MineItem::try_deserialize(&mut &mine_item_acc.data[..]).unwrap();
+    assert_eq!(
+        galaxy.mine_item_label(&mine_item),
+        "Hydrogen (raw material)"
+    );
+
+    let mut snapshot = Snapshot::default();
+    snapshot.insert(Ship::DISCRIMINATOR, vec![(ship_pk, ship_acc)]);
+    snapshot.insert(MineItem::DISCRIMINATOR, vec![(mine_item_pk, mine_item_acc)]);
+    let joined = galaxy.join(&snapshot);
+    assert_eq!(joined.len(), 2);
+    assert_eq!(joined[&ship_pk].symbol, "PX4");
+    assert_eq!(joined[&mine_item_pk].label(), "Hydrogen (raw material)");
+
+    // ships of unknown mints are not joined
+    assert!(catalog.join(&snapshot).is_empty());
+    assert_eq!(
+        catalog.ship_label(&ship),
+        world.mints[TEST_SHIP].to_string()
+    );
+
+    assert!(matches!(
+        Galaxy::from_path("data/missing.json"),
+        Err(GalaxyError::Io(_))
+    ));
+}
@@ -0,0 +1,265 @@
+use anchor_lang::{AnchorDeserialize, InstructionData};
+use solana_sdk::{
+    address_lookup_table::AddressLookupTableAccount,
+    message::{v0::LoadedAddresses, VersionedMessage},
+    pubkey::Pubkey,
+    signer::Signer,
+    system_instruction,
+};
+
+use staratlas_starbased_sdk::{
+    error::Program,
+    fleet::StartSubwarp,
+    ix_decode::{decode, decode_instruction, decode_message, sage, StarAtlasInstruction},
+    testing::{TestWorld, TEST_SHIP},
+    tx::{InstructionBuilder, TxBuilder},
+    Faction,
+};
+
+fn anchor_pubkey(pubkey: &Pubkey) -> anchor_lang::prelude::Pubkey {
+    anchor_lang::prelude::Pubkey::new_from_array(pubkey.to_bytes())
+}
+
+#[test]
+fn ix_decode_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+    let start_subwarp = || {
+        StartSubwarp::new(
+            &player.keypair,
+            &player.profile,
+            &player.profile_faction,
+            &fleet.fleet,
+            &test_world.world.game,
+            &test_world.world.game_state,
+            [2, 2],
+            &test_world.funder,
+        )
+    };
+
+    let (ixs, _, _) = start_subwarp().instructions();
+    let ix = decode(&ixs[0]).unwrap();
+    assert_eq!(ix.program(), Program::Sage);
+    assert_eq!(ix.name(), "StartSubwarp");
+    let StarAtlasInstruction::Sage(sage::Instruction::StartSubwarp { accounts, args, .. }) = ix
+    else {
+        panic!("not a StartSubwarp");
+    };
+    assert_eq!(accounts.fleet, anchor_pubkey(&fleet.fleet));
+    assert_eq!(accounts.owning_profile, anchor_pubkey(&player.profile));
+    assert_eq!(args.input.to_sector, [2, 2]);
+
+    // other programs are skipped
+    let transfer =
+        system_instruction::transfer(&test_world.funder.pubkey(), &Pubkey::new_unique(), 1);
+    let tx = TxBuilder::new(&test_world.funder)
+        .add_instructions(vec![transfer], vec![], vec![])
+        .add(start_subwarp())
+        .transaction(test_world.svm.latest_blockhash())
+        .unwrap();
+    let decoded = decode_message(&VersionedMessage::Legacy(tx.message), None);
+    assert_eq!(decoded.len(), 1);
+    assert_eq!(decoded[0].0, 1);
+    assert_eq!(decoded[0].1.name(), "StartSubwarp");
+
+    // accounts loaded from a lookup table
+    let lookup_table = AddressLookupTableAccount {
+        key: Pubkey::new_unique(),
+        addresses: vec![fleet.fleet, test_world.world.game_state],
+    };
+    let tx = TxBuilder::new(&test_world.funder)
+        .add(start_subwarp())
+        .versioned_transaction(
+            test_world.svm.latest_blockhash(),
+            std::slice::from_ref(&lookup_table),
+        )
+        .unwrap();
+    let lookup = &tx.message.address_table_lookups().unwrap()[0];
+    let loaded_addresses = LoadedAddresses {
+        writable: lookup
+            .writable_indexes
+            .iter()
+            .map(|index| lookup_table.addresses[*index as usize])
+            .collect(),
+        readonly: lookup
+            .readonly_indexes
+            .iter()
+            .map(|index| lookup_table.addresses[*index as usize])
+            .collect(),
+    };
+    assert!(decode_message(&tx.message, None).is_empty());
+    let decoded = decode_message(&tx.message, Some(&loaded_addresses));
+    assert_eq!(decoded.len(), 1);
+    let StarAtlasInstruction::Sage(sage::Instruction::StartSubwarp { accounts, .. }) =
+        &decoded[0].1
+    else {
+        panic!("not a StartSubwarp");
+    };
+    assert_eq!(accounts.fleet, anchor_pubkey(&fleet.fleet));
+    assert_eq!(
+        accounts.game_state,
+        anchor_pubkey(&test_world.world.game_state)
+    );
+
+    // missing accounts and unknown data
+    let program_id = anchor_pubkey(&ixs[0].program_id);
+    assert!(matches!(
+        sage::decode(&program_id, &[], &ixs[0].data),
+        Err(sage::DecodeError::MissingAccounts { .. })
+    ));
+    assert!(matches!(
+        sage::decode(&program_id, &[], &[0; 8]),
+        Err(sage::DecodeError::UnknownDiscriminator)
+    ));
+}
+
+/// Asserts that every listed instruction, built with zeroed arguments (empty vectors, `None`, first
+/// enum variants), decodes to itself.
+macro_rules! assert_round_trips {
+    ($program:expr, $krate:ident, [$($name:ident),+ $(,)?]) => {
+        let account_keys: Vec<_> = (0..32).map(|_| Pubkey::new_unique()).collect();
+        $(
+            let data = $krate::instruction::$name::deserialize(&mut &[0; 1024][..])
+                .unwrap()
+                .data();
+            let ix = decode_instruction(&$program.id(), &account_keys, &data)
+                .unwrap_or_else(|| panic!("{} doesn't decode", stringify!($name)));
+            assert_eq!(ix.program(), $program);
+            assert_eq!(ix.name(), stringify!($name));
+        )+
+    };
+}
+
+#[test]
+fn ix_decode_round_trip_test() {
+    assert_round_trips!(
+        Program::Sage,
+        staratlas_sage,
+        [
+            ActivateGameState,
+            AddConnection,
+            AddCrewToGame,
+            AddRental,
+            AddShipEscrow,
+            AddShipToFleet,
+            BurnCraftingConsumables,
+            CancelCraftingProcess,
+            ChangeRental,
+            ClaimCraftingNonConsumables,
+            ClaimCraftingOutputs,
+            CloseCraftingProcess,
+            CloseDisbandedFleet,
+            CloseFleetCargoPodTokenAccount,
+            ClosePlayerCrewRecord,
+            CloseStarbaseCargoTokenAccount,
+            CloseUpgradeProcess,
+            CompleteStarbaseUpgrade,
+            CopyGameState,
+            CreateCargoPod,
+            CreateCertificateMint,
+            CreateCraftingProcess,
+            CreateFleet,
+            CreateStarbaseUpgradeResourceProcess,
+            DepositCargoToFleet,
+            DepositCargoToGame,
+            DepositCraftingIngredient,
+            DepositStarbaseUpkeepResource,
+            DeregisterMineItem,
+            DeregisterProgressionConfig,
+            DeregisterResource,
+            DeregisterStarbase,
+            DeregisterSurveyDataUnitTracker,
+            DisbandFleet,
+            DisbandedFleetToEscrow,
+            DiscoverSector,
+            DrainMineItemBank,
+            DrainSurveyDataUnitsBank,
+            FleetStateHandler,
+            ForceDisbandFleet,
+            ForceDropFleetCargo,
+            IdleToLoadingBay,
+            IdleToRespawn,
+            InitGame,
+            InitGameState,
+            InvalidateRental,
+            InvalidateShip,
+            LoadFleetCrew,
+            LoadingBayToIdle,
+            MineAsteroidToRespawn,
+            MintCertificate,
+            MintCrewToGame,
+            RedeemCertificate,
+            RegisterMineItem,
+            RegisterPlanet,
+            RegisterProgressionConfig,
+            RegisterResource,
+            RegisterSageCrewConfig,
+            RegisterSagePlayerProfile,
+            RegisterSagePointModifier,
+            RegisterSector,
+            RegisterShip,
+            RegisterStar,
+            RegisterStarbase,
+            RegisterStarbasePlayer,
+            RegisterSurveyDataUnitTracker,
+            RemoveCargoPod,
+            RemoveConnection,
+            RemoveCrewFromGame,
+            RemoveInvalidShipEscrow,
+            RemoveShipEscrow,
+            RespawnToLoadingBay,
+            ScanForSurveyDataUnits,
+            SetNextShip,
+            StartCraftingProcess,
+            StartMiningAsteroid,
+            StartStarbaseUpgrade,
+            StartSubwarp,
+            StopCraftingProcess,
+            StopMiningAsteroid,
+            StopSubwarp,
+            SubmitStarbaseUpgradeResource,
+            SyncStarbasePlayer,
+            SyncStarbaseUpgradeIngredients,
+            TransferCargoAtStarbase,
+            TransferCargoWithinFleet,
+            UnloadFleetCrew,
+            UpdateGame,
+            UpdateGameState,
+            UpdateMineItem,
+            UpdatePlanet,
+            UpdateProgressionConfig,
+            UpdateResource,
+            UpdateShip,
+            UpdateShipEscrow,
+            UpdateShipInFleet,
+            UpdateStar,
+            UpdateStarbase,
+            UpdateSurveyDataUnitTracker,
+            WarpLane,
+            WarpToCoordinate,
+            WithdrawCargoFromFleet,
+            WithdrawCargoFromGame,
+            WithdrawCraftingIngredient,
+        ]
+    );
+    assert_round_trips!(Program::Cargo, staratlas_cargo, [AddCargo]);
+    assert_round_trips!(Program::Crew, staratlas_crew, [MintCrewMember]);
+    assert_round_trips!(
+        Program::PlayerProfile,
+        staratlas_player_profile,
+        [AcceptRoleInvitation]
+    );
+    assert_round_trips!(
+        Program::ProfileFaction,
+        staratlas_profile_faction,
+        [ChooseFaction]
+    );
+
+    // fleet rentals has no generated instructions, the discriminator is taken from its IDL
+    let data = [97, 204, 63, 8, 84, 34, 28, 43];
+    let account_keys: Vec<_> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let ix = decode_instruction(&Program::FleetRentals.id(), &account_keys, &data).unwrap();
+    assert_eq!(ix.program(), Program::FleetRentals);
+    assert_eq!(ix.name(), "CancelRental");
+}
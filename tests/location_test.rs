@@ -0,0 +1,52 @@
+use anchor_lang::prelude::Pubkey;
+
+use staratlas_sage::{
+    location::{resolve, Position},
+    state_with_data::FleetState,
+    typedefs::{Idle, MineAsteroid, MoveWarp},
+};
+
+#[test]
+fn location_test() {
+    let idle = FleetState::Idle(Idle { sector: [2, -3] });
+    let location = resolve(&idle, 0);
+    assert_eq!(location.position, Position::Coordinates([2.0, -3.0]));
+    assert_eq!(location.destination, None);
+
+    let warp = FleetState::MoveWarp(MoveWarp {
+        from_sector: [0, 0],
+        to_sector: [10, 20],
+        warp_start: 100,
+        warp_finish: 200,
+    });
+
+    let location = resolve(&warp, 150);
+    assert_eq!(location.position, Position::Coordinates([5.0, 10.0]));
+    assert_eq!(location.destination, Some([10, 20]));
+    assert_eq!(location.remaining(150), Some(50));
+    assert!(location.is_moving());
+    assert!(!location.needs_state_handler);
+
+    let location = resolve(&warp, 250);
+    assert_eq!(location.position, Position::Coordinates([10.0, 20.0]));
+    assert!(location.needs_state_handler);
+
+    // `end` is 0 until mining stops
+    let mining = |end| {
+        FleetState::MineAsteroid(MineAsteroid {
+            asteroid: Pubkey::default(),
+            resource: Pubkey::default(),
+            start: 100,
+            end,
+            amount_mined: 0,
+            last_update: 100,
+        })
+    };
+    let location = resolve(&mining(0), 1_000);
+    assert_eq!(location.eta, None);
+    assert!(!location.needs_state_handler);
+
+    let location = resolve(&mining(500), 1_000);
+    assert_eq!(location.eta, Some(500));
+    assert!(location.needs_state_handler);
+}
@@ -0,0 +1,79 @@
+use litesvm::LiteSVM;
+use solana_sdk::{
+    native_token::LAMPORTS_PER_SOL, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction,
+};
+
+use staratlas_starbased_sdk::{
+    batch::transaction_size,
+    lookup_table::{fetch_lookup_table, lookup_addresses, LookupTableCache},
+    tx::TxBuilder,
+};
+
+#[test]
+fn lookup_table_test() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000 * LAMPORTS_PER_SOL)
+        .unwrap();
+
+    // too many accounts for a legacy transaction
+    let recipients: Vec<Pubkey> = (0..40).map(|_| Pubkey::new_unique()).collect();
+    let instructions: Vec<_> = recipients
+        .iter()
+        .map(|to_pk| system_instruction::transfer(&payer_kp.pubkey(), to_pk, LAMPORTS_PER_SOL))
+        .collect();
+    assert!(transaction_size(&instructions, &payer_kp.pubkey()) > 1232);
+
+    let addresses = lookup_addresses(&instructions);
+    assert_eq!(addresses, recipients);
+
+    let mut cache = LookupTableCache::new();
+    let lookup_tables = cache
+        .ensure(&mut svm, &payer_kp, &payer_kp, &addresses)
+        .unwrap();
+    assert_eq!(lookup_tables.len(), 1);
+    let lookup_table = &lookup_tables[0];
+    let fetched = fetch_lookup_table(&mut svm, &lookup_table.key)
+        .unwrap()
+        .unwrap();
+    assert_eq!(fetched.addresses, recipients);
+
+    // cached, no new table
+    let cached = cache
+        .ensure(&mut svm, &payer_kp, &payer_kp, &addresses[..10])
+        .unwrap();
+    assert_eq!(cached.len(), 1);
+    assert_eq!(cached[0].key, lookup_table.key);
+
+    TxBuilder::new(&payer_kp)
+        .add_instructions(instructions, vec![], vec![])
+        .send_with_lookup_tables(&mut svm, &lookup_tables)
+        .unwrap();
+    for to_pk in recipients.iter() {
+        assert_eq!(svm.get_balance(to_pk), Some(LAMPORTS_PER_SOL));
+    }
+}
+
+#[test]
+fn lookup_table_split_test() {
+    let mut svm = LiteSVM::new();
+    let payer_kp = Keypair::new();
+    svm.airdrop(&payer_kp.pubkey(), 1_000 * LAMPORTS_PER_SOL)
+        .unwrap();
+
+    // more addresses than a table holds
+    let addresses: Vec<Pubkey> = (0..300).map(|_| Pubkey::new_unique()).collect();
+    let mut cache = LookupTableCache::new();
+    let lookup_tables = cache
+        .ensure(&mut svm, &payer_kp, &payer_kp, &addresses)
+        .unwrap();
+    assert_eq!(lookup_tables.len(), 2);
+    assert_ne!(lookup_tables[0].key, lookup_tables[1].key);
+    for (lookup_table, chunk) in lookup_tables.iter().zip(addresses.chunks(256)) {
+        let fetched = fetch_lookup_table(&mut svm, &lookup_table.key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(fetched.addresses, chunk);
+    }
+}
@@ -0,0 +1,86 @@
+use anchor_lang::prelude::Pubkey;
+
+use staratlas_sage::{
+    mining::{estimate, MiningLimit, MiningSupplies},
+    state::{MineItem, Resource},
+    typedefs::{CargoStats, MineAsteroid},
+};
+
+#[test]
+fn mining_test() {
+    let resource = Resource {
+        version: 0,
+        game_id: Pubkey::default(),
+        location: Pubkey::default(),
+        mine_item: Pubkey::default(),
+        location_type: 1,
+        system_richness: 200,
+        amount_mined: 0,
+        num_miners: 1,
+        bump: 0,
+    };
+    let mine_item = MineItem {
+        version: 0,
+        game_id: Pubkey::default(),
+        name: [0; 64],
+        mint: Pubkey::default(),
+        resource_hardness: 100,
+        num_resource_accounts: 1,
+        bump: 0,
+    };
+    let cargo_stats = CargoStats {
+        cargo_capacity: 1000,
+        fuel_capacity: 0,
+        ammo_capacity: 1000,
+        ammo_consumption_rate: 5000,
+        food_consumption_rate: 10000,
+        mining_rate: 10000,
+        upgrade_rate: 0,
+        cargo_transfer_rate: 0,
+        tractor_beam_gather_rate: 0,
+    };
+    let state = MineAsteroid {
+        asteroid: Pubkey::default(),
+        resource: Pubkey::default(),
+        start: 100,
+        end: 0,
+        amount_mined: 0,
+        last_update: 100,
+    };
+    let mut supplies = MiningSupplies {
+        cargo_space: 1000,
+        food: 1000,
+        ammo: 1000,
+    };
+
+    let mining = estimate(&state, &resource, &mine_item, &cargo_stats, &supplies, 200);
+    assert_eq!(mining.rate, 2.0);
+    assert_eq!(mining.mined, 200);
+    assert_eq!(mining.food_consumed, 100);
+    assert_eq!(mining.ammo_consumed, 50);
+    assert_eq!(mining.time_to_full, Some(400.0));
+    assert_eq!(mining.stop_at, Some(600));
+    assert_eq!(mining.limit, Some(MiningLimit::CargoHold));
+
+    let done = estimate(&state, &resource, &mine_item, &cargo_stats, &supplies, 1000);
+    assert_eq!(done.mined, 1000);
+    assert_eq!(done.food_consumed, 500);
+    assert_eq!(done.time_to_full, Some(0.0));
+
+    supplies.food = 300;
+    let hungry = estimate(&state, &resource, &mine_item, &cargo_stats, &supplies, 1000);
+    assert_eq!(hungry.limit, Some(MiningLimit::Food));
+    assert_eq!(hungry.stop_at, Some(400));
+    assert_eq!(hungry.mined, 600);
+
+    // 300 mined until the update at 250, mining continues from there
+    let state = MineAsteroid {
+        amount_mined: 300,
+        last_update: 250,
+        ..state
+    };
+    let mining = estimate(&state, &resource, &mine_item, &cargo_stats, &supplies, 300);
+    assert_eq!(mining.mined, 400);
+    assert_eq!(mining.food_consumed, 50);
+    assert_eq!(mining.stop_at, Some(550));
+}
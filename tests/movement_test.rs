@@ -0,0 +1,66 @@
+use staratlas_sage::{
+    movement::{estimate, MovementError},
+    typedefs::MovementStats,
+};
+
+fn movement_stats() -> MovementStats {
+    MovementStats {
+        subwarp_speed: 1000,
+        warp_speed: 50000,
+        max_warp_distance: 300,
+        warp_cool_down: 30,
+        subwarp_fuel_consumption_rate: 10,
+        warp_fuel_consumption_rate: 100,
+        planet_exit_fuel_amount: 50,
+    }
+}
+
+#[test]
+fn movement_test() {
+    let stats = movement_stats();
+
+    let short_move = estimate(&stats, [0, 0], [0, 2], 1000, 0, 100);
+    let warp = short_move.warp.unwrap();
+    assert_eq!(warp.distance, 2.0);
+    assert_eq!(warp.fuel, 2);
+    assert_eq!(warp.duration, 40.0);
+    assert_eq!(warp.warp_cooldown_expires_at, Some(130));
+    let subwarp = short_move.subwarp.unwrap();
+    assert_eq!(subwarp.fuel, 1);
+    assert_eq!(subwarp.duration, 2000.0);
+    assert_eq!(short_move.fastest(), Some(&warp));
+
+    let too_far = estimate(&stats, [0, 0], [3, 4], 1000, 0, 100);
+    assert!(matches!(
+        too_far.warp,
+        Err(MovementError::DistanceGreaterThanMax { .. })
+    ));
+
+    let on_cooldown = estimate(&stats, [0, 0], [0, 2], 1000, 130, 100);
+    assert_eq!(
+        on_cooldown.warp,
+        Err(MovementError::WarpIsOnCooldown { expires_at: 130 })
+    );
+
+    let same_sector = estimate(&stats, [1, 1], [1, 1], 1000, 0, 100);
+    assert_eq!(same_sector.warp, Err(MovementError::InvalidWarpRange));
+    assert_eq!(same_sector.subwarp, Err(MovementError::InvalidWarpRange));
+    assert_eq!(same_sector.fastest(), None);
+
+    let no_fuel = estimate(&stats, [0, 0], [0, 2], 1, 0, 100);
+    assert_eq!(
+        no_fuel.warp,
+        Err(MovementError::InsufficientFuel {
+            required: 2,
+            available: 1
+        })
+    );
+
+    let stalled = MovementStats {
+        subwarp_speed: 0,
+        ..movement_stats()
+    };
+    let no_subwarp = estimate(&stalled, [0, 0], [0, 2], 1000, 0, 100);
+    assert_eq!(no_subwarp.subwarp, Err(MovementError::FaultyMovement));
+    assert_eq!(no_subwarp.fastest(), no_subwarp.warp.as_ref().ok());
+}
@@ -0,0 +1,282 @@
+use anchor_lang::{prelude::Pubkey, AnchorDeserialize};
+use solana_sdk::{
+    clock::Clock, program_pack::Pack, pubkey::Pubkey as SdkPubkey, signer::Signer,
+    transaction::Transaction,
+};
+use spl_associated_token_account_client::address::get_associated_token_address;
+use spl_token::state::Account as TokenAccount;
+
+use staratlas_sage::{
+    route::{plan, Objective, RouteGraph, RouteOptions, RouteSector, RouteStarbase, RouteStep},
+    state_with_data::{FleetState, FleetWithState},
+    typedefs::{Idle, MovementStats},
+};
+
+use staratlas_starbased_sdk::{
+    fleet::{route_actions, RouteAccounts, RouteAction},
+    sender::Sender,
+    testing::{TestFleet, TestPlayer, TestWorld, TEST_FUEL, TEST_SHIP},
+    Faction,
+};
+
+fn movement_stats() -> MovementStats {
+    MovementStats {
+        subwarp_speed: 1000,
+        warp_speed: 50000,
+        max_warp_distance: 300,
+        warp_cool_down: 30,
+        subwarp_fuel_consumption_rate: 10,
+        warp_fuel_consumption_rate: 100,
+        planet_exit_fuel_amount: 0,
+    }
+}
+
+#[test]
+fn route_test() {
+    let stats = movement_stats();
+    let keys: Vec<Pubkey> = (0..5).map(|_| Pubkey::new_unique()).collect();
+
+    // [0, 0] - [0, 3] - [0, 6], with a warp lane between [0, 0] and [0, 6]
+    let graph = RouteGraph {
+        sectors: vec![
+            RouteSector {
+                key: keys[0],
+                coordinates: [0, 0],
+                connections: vec![keys[2]],
+            },
+            RouteSector {
+                key: keys[1],
+                coordinates: [0, 3],
+                connections: vec![],
+            },
+            RouteSector {
+                key: keys[2],
+                coordinates: [0, 6],
+                connections: vec![keys[0]],
+            },
+        ],
+        starbases: vec![
+            RouteStarbase {
+                key: keys[3],
+                sector: [0, 3],
+                faction: 1,
+            },
+            RouteStarbase {
+                key: keys[4],
+                sector: [0, 6],
+                faction: 2,
+            },
+        ],
+    };
+
+    let mut options = RouteOptions::new(Objective::Fastest, 10, 10);
+    options.use_warp_lanes = false;
+    let fastest = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(fastest.steps.len(), 2);
+    assert!(matches!(
+        fastest.steps[0],
+        RouteStep::Warp { to: [0, 3], .. }
+    ));
+    assert_eq!(fastest.fuel, 6);
+    assert_eq!(fastest.duration, 120.0);
+
+    options.objective = Objective::Cheapest;
+    let cheapest = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(cheapest.fuel, 1);
+    assert!(matches!(cheapest.steps[..], [RouteStep::Subwarp { .. }]));
+
+    // not enough fuel for a second warp without a refuel
+    options.objective = Objective::Fastest;
+    options.fuel = 4;
+    let short_on_fuel = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(short_on_fuel.fuel, 4);
+    assert_eq!(short_on_fuel.duration, 3060.0);
+
+    options.refuel_faction = Some(1);
+    let refueled = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(refueled.steps[1], RouteStep::Refuel { starbase: keys[3] });
+    assert_eq!(refueled.duration, 120.0);
+
+    // the lane needs a starbase at both ends
+    options.objective = Objective::Cheapest;
+    options.use_subwarp = false;
+    options.use_warp_lanes = true;
+    options.warp_lane_fuel_factor = 0.5;
+    options.fuel = 10;
+    let no_lane = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(no_lane.fuel, 6);
+
+    let mut graph = graph;
+    graph.starbases.push(RouteStarbase {
+        key: Pubkey::new_unique(),
+        sector: [0, 0],
+        faction: 2,
+    });
+    let lane = plan(&graph, &stats, [0, 0], [0, 6], &options).unwrap();
+    assert_eq!(lane.fuel, 3);
+    assert!(matches!(
+        lane.steps[..],
+        [RouteStep::WarpLane {
+            to_sector_index: 0,
+            from_sector_index: 0,
+            ..
+        }]
+    ));
+
+    options.fuel = 2;
+    assert!(plan(&graph, &stats, [0, 0], [0, 6], &options).is_none());
+}
+
+#[test]
+fn route_grid_test() {
+    let stats = movement_stats();
+
+    // a 20 x 20 grid of sectors with a large tank, labels of the same node dominate each other
+    let graph = RouteGraph {
+        sectors: (0..400)
+            .map(|index| RouteSector {
+                key: Pubkey::new_unique(),
+                coordinates: [index / 20, index % 20],
+                connections: vec![],
+            })
+            .collect(),
+        starbases: vec![],
+    };
+    let options = RouteOptions::new(Objective::Cheapest, 100_000, 100_000);
+
+    let route = plan(&graph, &stats, [0, 0], [19, 19], &options).unwrap();
+    assert_eq!(route.fuel, 3);
+    assert!(route
+        .steps
+        .iter()
+        .all(|step| matches!(step, RouteStep::Subwarp { .. })));
+}
+
+/// Sends the instructions of `actions` and waits out their waits on the test world's clock.
+fn run_route(test_world: &mut TestWorld, player: &TestPlayer, actions: Vec<RouteAction>) {
+    for action in actions {
+        match action {
+            RouteAction::Instruction(ix) => {
+                let mut signers = vec![&test_world.funder];
+                let key_signs = ix
+                    .accounts
+                    .iter()
+                    .any(|meta| meta.is_signer && meta.pubkey == player.keypair.pubkey());
+                if key_signs {
+                    signers.push(&player.keypair);
+                }
+                let tx = Transaction::new_signed_with_payer(
+                    &[ix],
+                    Some(&test_world.funder.pubkey()),
+                    &signers,
+                    test_world.svm.latest_blockhash(),
+                );
+                test_world.svm.send_transaction(tx).unwrap();
+            }
+            RouteAction::Wait { seconds } => {
+                let now = test_world.svm.get_sysvar::<Clock>().unix_timestamp;
+                test_world.svm.wait_until(now + seconds as i64).unwrap();
+            }
+            RouteAction::Refuel { .. } => panic!("the route has no refuels"),
+        }
+    }
+}
+
+fn fleet_state(test_world: &TestWorld, fleet: &TestFleet) -> FleetState {
+    let fleet_acc = test_world.svm.get_account(&fleet.fleet).unwrap();
+    let FleetWithState(_, state) = FleetWithState::deserialize(&mut &fleet_acc.data[..]).unwrap();
+    state
+}
+
+#[test]
+fn route_execution_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    let fleet_data = test_world.fleet(&fleet);
+    let fuel_tank = SdkPubkey::new_from_array(fleet_data.fuel_tank.to_bytes());
+    let stats = fleet_data.stats.movement_stats;
+    test_world.deposit_cargo(&player, &fleet, &fuel_tank, TEST_FUEL, 1000);
+    test_world.undock(&player, &fleet);
+
+    let world = &test_world.world;
+    let fuel_mint = world.mints[TEST_FUEL];
+    let fuel_cargo_type = world.cargo_types[TEST_FUEL];
+    let cargo_stats_definition = world.cargo_stats_definition;
+    let (game, game_state) = (world.game, world.game_state);
+    let fuel_token_account = get_associated_token_address(&fuel_tank, &fuel_mint);
+    let fuel = |test_world: &TestWorld| {
+        let token_acc = test_world.svm.get_account(&fuel_token_account).unwrap();
+        TokenAccount::unpack(&token_acc.data).unwrap().amount
+    };
+    assert_eq!(fuel(&test_world), 1000);
+
+    // the MUD starbase sector [0, 10] and the sectors a warp apart towards [0, 4]
+    let graph = RouteGraph {
+        sectors: [[0, 10], [0, 7], [0, 4], [1, 4]]
+            .into_iter()
+            .map(|coordinates| RouteSector {
+                key: Pubkey::new_unique(),
+                coordinates,
+                connections: vec![],
+            })
+            .collect(),
+        starbases: vec![],
+    };
+    let unused = SdkPubkey::new_unique();
+    let accounts = RouteAccounts {
+        key_kp: &player.keypair,
+        profile_pk: &player.profile,
+        profile_faction_pk: &player.profile_faction,
+        fleet_pk: &fleet.fleet,
+        game_pk: &game,
+        game_state_pk: &game_state,
+        fuel_tank_pk: &fuel_tank,
+        fuel_cargo_type_pk: &fuel_cargo_type,
+        cargo_stats_definition_pk: &cargo_stats_definition,
+        fuel_mint_pk: &fuel_mint,
+        fee_token_from_pk: &unused,
+        fee_token_to_pk: &unused,
+        fee_mint_pk: &unused,
+        key_index: 0,
+        funder_kp: &test_world.funder,
+    };
+
+    // two warps, the second waits out the warp cool down, then a subwarp whose fuel is burned
+    // by the fleet state handler on arrival
+    let options = RouteOptions::new(Objective::Fastest, 1000, 1000);
+    let warps = plan(&graph, &stats, [0, 10], [0, 4], &options).unwrap();
+    assert!(matches!(
+        warps.steps[..],
+        [
+            RouteStep::Warp { to: [0, 7], .. },
+            RouteStep::Warp { to: [0, 4], .. }
+        ]
+    ));
+
+    let mut options = RouteOptions::new(Objective::Cheapest, 1000 - warps.fuel, 1000);
+    options.use_warp = false;
+    let subwarp = plan(&graph, &stats, [0, 4], [1, 4], &options).unwrap();
+    assert!(matches!(
+        subwarp.steps[..],
+        [RouteStep::Subwarp { to: [1, 4], .. }]
+    ));
+
+    let warp_actions = route_actions(&warps, &accounts, stats.warp_cool_down);
+    let subwarp_actions = route_actions(&subwarp, &accounts, stats.warp_cool_down);
+
+    run_route(&mut test_world, &player, warp_actions);
+    assert!(matches!(
+        fleet_state(&test_world, &fleet),
+        FleetState::Idle(Idle { sector: [0, 4] })
+    ));
+    assert_eq!(fuel(&test_world), 1000 - warps.fuel);
+
+    run_route(&mut test_world, &player, subwarp_actions);
+    assert!(matches!(
+        fleet_state(&test_world, &fleet),
+        FleetState::Idle(Idle { sector: [1, 4] })
+    ));
+    assert_eq!(fuel(&test_world), 1000 - warps.fuel - subwarp.fuel);
+}
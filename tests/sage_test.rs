@@ -22,16 +22,18 @@ use spl_token::{
 use staratlas_cargo::{instruction::InitDefinition, typedefs::InitDefinitionInput};
 use staratlas_crew::state::CrewConfig;
 use staratlas_player_profile::{instruction::CreateProfile, typedefs::AddKeyInput};
-use staratlas_profile_faction::{instruction::ChooseFaction, typedefs::Faction};
+use staratlas_profile_faction::typedefs::Faction;
 use staratlas_sage::{
-    instruction::{RegisterSagePlayerProfile, RegisterStarbasePlayer, UpdateGame, UpdateGameState},
+    instruction::{RegisterSagePlayerProfile, RegisterStarbasePlayer},
     state::{Game, GameState, Sector},
-    typedefs::{
-        FleetInput, SectorRing, StarbaseLevelInfoArrayInput, UpdateGameInput, UpdateGameStateInput,
-    },
+    typedefs::{SectorRing, StarbaseLevelInfoArrayInput},
 };
 
 use staratlas_starbased_sdk as based_sdk;
+use staratlas_starbased_sdk::{
+    simulation::SimulationReport,
+    tx::{InstructionBuilder, TxBuilder},
+};
 
 mod shared;
 use shared::{
@@ -43,6 +45,14 @@ use shared::{
     helpers,
 };
 
+/// Asserts the dry run succeeded and created `pubkey` as a `name` account.
+fn assert_created(report: &SimulationReport, pubkey: &Pubkey, name: &str) {
+    assert!(report.is_ok(), "{}", report);
+    let account = report.account(pubkey).unwrap();
+    assert!(account.created);
+    assert_eq!(account.name, Some(name));
+}
+
 #[test]
 fn sage_test() {
     let feature_set = FeatureSet::all_enabled();
@@ -68,10 +78,10 @@ fn sage_test() {
 
     // starbased-sdk: profile create profile (player)
     let player_profile_kp = Keypair::new();
-    let player_profile_pk = based_sdk::profile::CreateProfile::new(&player_profile_kp, &wallet_kp)
-        .send(&mut svm)
-        .unwrap();
-    dbg!(&player_profile_pk);
+    let create_profile = || based_sdk::profile::CreateProfile::new(&player_profile_kp, &wallet_kp);
+    let report = create_profile().simulate(&mut svm).unwrap();
+    let player_profile_pk = create_profile().send(&mut svm).unwrap();
+    assert_created(&report, &player_profile_pk, "Profile");
 
     let authority_kp = Keypair::new();
     let authority_pk = authority_kp.pubkey();
@@ -205,35 +215,18 @@ fn sage_test() {
     let tx_result = svm.send_transaction(tx);
     assert!(tx_result.is_ok());
 
-    let (player_faction_pda, _bump) = Pubkey::find_program_address(
-        &[b"player_faction", player_profile_pk.as_ref()],
-        &PROFILE_FACTION_PROGRAM_ID,
-    );
-
-    let choose_faction_ix = Instruction {
-        program_id: PROFILE_FACTION_PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new(player_profile_pk, true), // pub key: Signer<'info>,
-            AccountMeta::new(wallet_pk, true),         // pub funder: Signer<'info>,
-            AccountMeta::new(player_profile_pk, false), // pub profile: AccountInfo<'info>,
-            AccountMeta::new(player_faction_pda, false), // pub faction: AccountInfo<'info>,
-            AccountMeta::new_readonly(system_program::ID, false), // system program
-        ],
-        data: ChooseFaction {
-            _key_index: 0,
-            _faction: Faction::Ustur,
-        }
-        .data(),
-    };
+    // starbased-sdk: profile choose faction (player)
+    let player_faction_pda = based_sdk::profile::ChooseFaction::new(
+        &player_profile_kp,
+        &player_profile_pk,
+        Faction::Ustur,
+        &wallet_kp,
+    )
+    .send(&mut svm)
+    .unwrap();
 
-    let message = Message::new(&[choose_faction_ix], Some(&wallet_pk));
-    let tx = Transaction::new(
-        &[&player_profile_kp, &wallet_kp],
-        message,
-        svm.latest_blockhash(),
-    );
-    let tx_result = svm.send_transaction(tx);
-    assert!(tx_result.is_ok());
+    let player_faction = based_sdk::profile::get_profile_faction(&svm, &player_profile_pk).unwrap();
+    assert!(matches!(player_faction.faction(), Ok(Faction::Ustur)));
 
     // create cargo stats definition
     let cargo_stats_definition_kp = Keypair::new();
@@ -264,124 +257,64 @@ fn sage_test() {
 
     // starbased-sdk: admin create game
     let game_kp = Keypair::new();
-    let game_pk = based_sdk::admin::CreateGame::new(&authority_kp, &sage_profile_pk, &wallet_kp)
-        .set_game_kp(game_kp)
-        .send(&mut svm)
-        .unwrap();
-    dbg!(&game_pk);
+    let create_game = || {
+        based_sdk::admin::CreateGame::new(&authority_kp, &sage_profile_pk, &wallet_kp)
+            .set_game_kp(game_kp.insecure_clone())
+    };
+    let report = create_game().simulate(&mut svm).unwrap();
+    let game_pk = create_game().send(&mut svm).unwrap();
+    assert_created(&report, &game_pk, "Game");
 
     let game_acc = svm.get_account(&game_pk).unwrap();
     let game_data = Game::try_deserialize(&mut &game_acc.data[..]).unwrap();
 
     // starbased-sdk: admin create game state
-    let game_state_pk = based_sdk::admin::CreateGameState::new(
+    let create_game_state = || {
+        based_sdk::admin::CreateGameState::new(
+            &authority_kp,
+            &sage_profile_pk,
+            &game_pk,
+            &wallet_kp,
+        )
+        .set_game_update_id(game_data.update_id)
+        .set_profile_key_index(2) // SAGE_MANAGER
+    };
+    let report = create_game_state().simulate(&mut svm).unwrap();
+    let game_state_pk = create_game_state().send(&mut svm).unwrap();
+    assert_created(&report, &game_state_pk, "GameState");
+
+    // starbased-sdk: admin update game state
+    let _ = based_sdk::admin::UpdateGameState::new(
         &authority_kp,
         &sage_profile_pk,
         &game_pk,
+        &game_state_pk,
         &wallet_kp,
     )
-    .set_game_update_id(game_data.update_id)
+    .add_starbase_level_info(StarbaseLevelInfoArrayInput {
+        level: 6,
+        faction: Faction::Ustur.into(),
+        hp: 6000,
+        sp: 6000,
+        sector_ring_available: SectorRing::Inner,
+        warp_lane_movement_fee: 0,
+    })
+    .set_max_fleet_size(64)
     .set_profile_key_index(2) // SAGE_MANAGER
     .send(&mut svm)
     .unwrap();
-    dbg!(&game_state_pk);
-
-    // update game state
-    let update_game_state_ix = Instruction {
-        program_id: SAGE_PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new_readonly(authority_pk, true), // UpdateGameStateGameAndProfile<'info> pub key: Signer<'info>,
-            AccountMeta::new_readonly(sage_profile_pk, false), // UpdateGameStateGameAndProfile<'info>pub profile: AccountInfo<'info>,
-            AccountMeta::new_readonly(game_pk, false), // UpdateGameStateGameAndProfile<'info> pub game_id: AccountInfo<'info>,
-            AccountMeta::new(game_state_pk, false),    // pub game_state: AccountInfo<'info>,
-            AccountMeta::new(Pubkey::default(), false), // old_recipe_for_upgrade
-            AccountMeta::new(Pubkey::default(), false), // new_recipe_for_upgrade
-            AccountMeta::new(Pubkey::default(), false), // recipe_category_for_level
-        ],
-        data: UpdateGameState {
-            _input: UpdateGameStateInput {
-                fleet: Some(FleetInput {
-                    starbase_level_info_array: Some(vec![StarbaseLevelInfoArrayInput {
-                        level: 6,
-                        faction: Faction::Ustur as u8,
-                        hp: 6000,
-                        sp: 6000,
-                        sector_ring_available: SectorRing::Inner,
-                        warp_lane_movement_fee: 0,
-                    }]),
-                    upkeep_info_array: None,
-                    max_fleet_size: Some(64),
-                }),
-                misc: None,
-                key_index: 2, // SAGE_MANAGER
-            },
-        }
-        .data(),
-    };
-
-    let message = Message::new(&[update_game_state_ix], Some(&wallet_pk));
-    let tx = Transaction::new(
-        &[&authority_kp, &wallet_kp],
-        message,
-        svm.latest_blockhash(),
-    );
-    let tx_result = svm.send_transaction(tx);
-    assert!(tx_result.is_ok());
-
-    // update game
-    // let ammo_kp = Keypair::new();
-    // let ammo_pk = ammo_kp.pubkey();
-
-    // let food_kp = Keypair::new();
-    // let food_pk = food_kp.pubkey();
-
-    // let fuel_kp = Keypair::new();
-    // let fuel_pk = fuel_kp.pubkey();
 
-    // let repair_kit_kp = Keypair::new();
-    // let repair_kit_pk = repair_kit_kp.pubkey();
+    // starbased-sdk: admin update game
+    let _ =
+        based_sdk::admin::UpdateGame::new(&authority_kp, &sage_profile_pk, &game_pk, &wallet_kp)
+            .set_cargo_stats_definition(cargo_stats_definition_pk)
+            .set_profile_key_index(2) // SAGE_MANAGER
+            .send(&mut svm)
+            .unwrap();
 
-    // // ammo = 1 << 2      // 0b00000100
-    // // food = 1 << 3      // 0b00001000
-    // // fuel = 1 << 4      // 0b00010000
-    // // repairKit = 1 << 5 // 0b00100000
-    // let mints: u8 = (1 << 2) | (1 << 3) | (1 << 4) | (1 << 5); // 0b00111100 = 60 in decimal
-
-    let update_game_ix = Instruction {
-        program_id: SAGE_PROGRAM_ID,
-        accounts: vec![
-            AccountMeta::new_readonly(authority_pk, true), // UpdateGameGameAndProfile<'info> pub key: Signer<'info>,
-            AccountMeta::new_readonly(sage_profile_pk, false), // UpdateGameGameAndProfile<'info>pub profile: AccountInfo<'info>,
-            AccountMeta::new(game_pk, false), // UpdateGameGameAndProfile<'info> pub game_id: AccountInfo<'info>,
-            // // Add the mint accounts as remaining_accounts
-            // AccountMeta::new_readonly(ammo_pk, false),
-            // AccountMeta::new_readonly(food_pk, false),
-            // AccountMeta::new_readonly(fuel_pk, false),
-            // AccountMeta::new_readonly(repair_kit_pk, false),
-            AccountMeta::new_readonly(cargo_stats_definition_pk, false),
-        ],
-        data: UpdateGame {
-            _input: UpdateGameInput {
-                cargo: 1,
-                crafting: 0,
-                mints: 0,
-                vaults: 0,
-                points: 0,
-                risk_zones: None, // pub risk_zones: Option<RiskZonesDataUnpacked>,
-                key_index: 2,     // SAGE_MANAGER
-            },
-        }
-        .data(),
-    };
-
-    let message = Message::new(&[update_game_ix], Some(&wallet_pk));
-    let tx = Transaction::new(
-        &[&authority_kp, &wallet_kp],
-        message,
-        svm.latest_blockhash(),
-    );
-    let tx_result = svm.send_transaction(tx);
-    assert!(tx_result.is_ok());
+    let game_acc = svm.get_account(&game_pk).unwrap();
+    let game_data = Game::try_deserialize(&mut &game_acc.data[..]).unwrap();
+    assert_eq!(game_data.cargo.stats_definition, cargo_stats_definition_pk);
 
     // starbased-sdk: admin activate game state
     let _ = based_sdk::admin::ActivateGameState::new(
@@ -400,19 +333,21 @@ fn sage_test() {
     assert_eq!(game_pk.as_ref(), game_state_data.game_id.as_ref());
 
     // starbased-sdk: admin register sector
-    let sector_pk = based_sdk::admin::RegisterSector::new(
-        &authority_kp,
-        &sage_profile_pk,
-        &sage_profile_pk, // discoverer
-        &game_pk,
-        &wallet_kp,
-    )
-    .set_coordinates([1, 1])
-    .set_name("Super Sector".into())
-    .set_profile_key_index(2) // SAGE_MANAGER
-    .send(&mut svm)
-    .unwrap();
-    dbg!(sector_pk);
+    let register_sector = || {
+        based_sdk::admin::RegisterSector::new(
+            &authority_kp,
+            &sage_profile_pk,
+            &sage_profile_pk, // discoverer
+            &game_pk,
+            &wallet_kp,
+        )
+        .set_coordinates([1, 1])
+        .set_name("Super Sector".into())
+        .set_profile_key_index(2) // SAGE_MANAGER
+    };
+    let report = register_sector().simulate(&mut svm).unwrap();
+    let sector_pk = register_sector().send(&mut svm).unwrap();
+    assert_created(&report, &sector_pk, "Sector");
 
     // TODO: setup crew (fleetCRUD.test.ts 868)
 
@@ -422,23 +357,25 @@ fn sage_test() {
     assert_eq!(game_pk.as_ref(), sector_data.game_id.as_ref());
 
     // starbased-sdk: admin register starbase
-    let starbase_pk = based_sdk::admin::RegisterStarbase::new(
-        &authority_kp,
-        &sage_profile_pk,
-        &game_pk,
-        &game_state_pk,
-        &sector_pk,
-        &wallet_kp,
-    )
-    .set_coordinates([1, 1])
-    .set_name("Starbase Alpha".into())
-    .set_sub_coordinates([1, 1])
-    .set_starbase_level_index(6)
-    .set_faction(Faction::Ustur as u8)
-    .set_profile_key_index(2) // SAGE_MANAGER
-    .send(&mut svm)
-    .unwrap();
-    dbg!(starbase_pk);
+    let register_starbase = || {
+        based_sdk::admin::RegisterStarbase::new(
+            &authority_kp,
+            &sage_profile_pk,
+            &game_pk,
+            &game_state_pk,
+            &sector_pk,
+            &wallet_kp,
+        )
+        .set_coordinates([1, 1])
+        .set_name("Starbase Alpha".into())
+        .set_sub_coordinates([1, 1])
+        .set_starbase_level_index(6)
+        .set_faction(Faction::Ustur)
+        .set_profile_key_index(2) // SAGE_MANAGER
+    };
+    let report = register_starbase().simulate(&mut svm).unwrap();
+    let starbase_pk = register_starbase().send(&mut svm).unwrap();
+    assert_created(&report, &starbase_pk, "Starbase");
 
     // create sage player profile
     let (sage_player_profile_pda, _bump) = Pubkey::find_program_address(
@@ -500,25 +437,23 @@ fn sage_test() {
     let tx_result = svm.send_transaction(tx);
     assert!(tx_result.is_ok());
 
-    let (crew_config_ix, crew_merkle_tree_pk) =
+    let (crew_config_ix, _crew_merkle_tree_pk) =
         helpers::setup_crew_config_instructions(&sage_profile_pk, &wallet_pk, &game_pk);
-
-    let tx = Transaction::new_signed_with_payer(
-        &[crew_config_ix],
-        Some(&wallet_pk),
-        &[&wallet_kp],
-        svm.latest_blockhash(),
-    );
-    let tx_result = svm.send_transaction(tx);
-    assert!(tx_result.is_ok());
-
     let (crew_config_pda, _bump) =
         Pubkey::find_program_address(&[b"crew_config", game_pk.as_ref()], &CREW_PROGRAM_ID);
 
+    let register_crew_config = || {
+        TxBuilder::new(&wallet_kp).add_instructions(vec![crew_config_ix.clone()], vec![], vec![])
+    };
+    let report = register_crew_config().simulate(&mut svm).unwrap();
+    assert!(report.is_ok(), "{}", report);
+    let crew_config = report.account(&crew_config_pda).unwrap();
+    assert!(crew_config.created);
+    register_crew_config().send(&mut svm).unwrap();
+
     let crew_config_acc = svm.get_account(&crew_config_pda).unwrap();
     let crew_config_data = CrewConfig::try_deserialize(&mut &crew_config_acc.data[..]).unwrap();
     assert_eq!(crew_config_data.seed_pubkey.as_ref(), game_pk.as_ref());
-    dbg!(&crew_merkle_tree_pk);
 
     // see `starbaseCrafting.test.ts` for examples
 
@@ -856,7 +791,8 @@ fn sage_test() {
 
     let raw_account = svm.get_account(&ata).unwrap();
     let token_acc = TokenAccount::unpack(&raw_account.data).unwrap();
-    dbg!(&token_acc);
+    assert_eq!(token_acc.mint, ship_mint);
+    assert_eq!(token_acc.owner, wallet_pk);
     assert_eq!(token_acc.amount, ship_to_own);
 
     assert!(true);
@@ -0,0 +1,92 @@
+use solana_client::nonblocking::rpc_client::RpcClient;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    system_instruction,
+    transaction::Transaction,
+};
+
+use staratlas_starbased_sdk::{
+    fleet::StartSubwarp,
+    sender::Sender,
+    testing::{TestWorld, TEST_SHIP},
+    tx::InstructionBuilder,
+    Faction,
+};
+
+fn transfer<S: Sender>(
+    sender: &mut S,
+    from_kp: &Keypair,
+    to_pk: &Pubkey,
+    lamports: u64,
+) -> Result<Signature, S::Error> {
+    let ix = system_instruction::transfer(&from_kp.pubkey(), to_pk, lamports);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&from_kp.pubkey()),
+        &[from_kp],
+        sender.latest_blockhash()?,
+    );
+    sender.send(tx)
+}
+
+#[test]
+fn sender_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    let svm = &mut test_world.svm;
+    assert!(Sender::get_account(svm, &fleet.fleet).unwrap().is_some());
+    assert!(Sender::get_account(svm, &Pubkey::new_unique())
+        .unwrap()
+        .is_none());
+
+    let to_pk = Pubkey::new_unique();
+    transfer(svm, &test_world.funder, &to_pk, 1_000_000).unwrap();
+    assert_eq!(svm.get_balance(&to_pk), Some(1_000_000));
+
+    // a docked fleet can't subwarp, the simulation reports the error without sending
+    let subwarp = StartSubwarp::new(
+        &player.keypair,
+        &player.profile,
+        &player.profile_faction,
+        &fleet.fleet,
+        &test_world.world.game,
+        &test_world.world.game_state,
+        [2, 2],
+        &test_world.funder,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[subwarp.instruction()],
+        Some(&test_world.funder.pubkey()),
+        &[&player.keypair, &test_world.funder],
+        svm.latest_blockhash(),
+    );
+    let simulation = svm.simulate(tx).unwrap();
+    assert!(simulation.err.is_some());
+    assert!(!simulation.logs.is_empty());
+    assert!(simulation.units_consumed > 0);
+
+    assert!(subwarp.send(svm).is_err());
+}
+
+#[test]
+fn nonblocking_sender_runtime_test() {
+    let url = "http://127.0.0.1:8899".to_string();
+
+    // outside of a tokio runtime
+    let mut client = RpcClient::new(url.clone());
+    assert!(Sender::latest_blockhash(&mut client).is_err());
+
+    // in a current thread runtime
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    runtime.block_on(async {
+        let mut client = RpcClient::new(url);
+        assert!(Sender::latest_blockhash(&mut client).is_err());
+    });
+}
@@ -0,0 +1,85 @@
+use solana_sdk::signer::Signer;
+
+use staratlas_starbased_sdk::{
+    admin::{find_sector_pda, RegisterSector},
+    error::{Program, ProgramError},
+    fleet::StartSubwarp,
+    profile::permissions::SAGE_MANAGER_KEY_INDEX,
+    testing::{TestWorld, TEST_SHIP},
+    tx::InstructionBuilder,
+    Faction,
+};
+
+#[test]
+fn simulation_test() {
+    let mut test_world = TestWorld::new();
+    let world = &test_world.world;
+    let funder_kp = &test_world.funder;
+
+    let coordinates = [7, 7];
+    let (sector_pk, _) = find_sector_pda(&world.game, coordinates);
+
+    let report = RegisterSector::new(
+        &world.authority,
+        &world.profile,
+        &world.profile,
+        &world.game,
+        funder_kp,
+    )
+    .set_coordinates(coordinates)
+    .set_name("New Sector".into())
+    .set_profile_key_index(SAGE_MANAGER_KEY_INDEX)
+    .simulate(&mut test_world.svm)
+    .unwrap();
+    assert!(report.is_ok());
+    assert!(report.error.is_none());
+    assert!(report.units_consumed > 0);
+    assert!(!report.logs.is_empty());
+
+    let sector = report.account(&sector_pk).unwrap();
+    assert!(sector.created);
+    assert_eq!(sector.name, Some("Sector"));
+    assert!(sector.field("coordinates.0").is_some());
+
+    let funder = report.account(&funder_kp.pubkey()).unwrap();
+    assert!(funder.field("lamports").unwrap().delta.unwrap() < 0);
+
+    // a dry run
+    assert!(test_world.svm.get_account(&sector_pk).is_none());
+}
+
+#[test]
+fn simulation_failure_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    // a docked fleet can't subwarp
+    let report = StartSubwarp::new(
+        &player.keypair,
+        &player.profile,
+        &player.profile_faction,
+        &fleet.fleet,
+        &test_world.world.game,
+        &test_world.world.game_state,
+        [2, 2],
+        &test_world.funder,
+    )
+    .simulate(&mut test_world.svm)
+    .unwrap();
+    assert!(!report.is_ok());
+    assert!(report.accounts.is_empty());
+    assert!(!report.logs.is_empty());
+    assert!(report.to_string().contains("compute units"));
+
+    let error = report.error.as_ref().unwrap();
+    assert_eq!(error.program, Some(Program::Sage));
+    assert_eq!(error.program_id, Some(Program::Sage.id()));
+    assert_eq!(error.instruction_index, 0);
+    let Some(ProgramError::Sage(code)) = error.error else {
+        panic!("expected a SAGE error, got {:?}", error.error);
+    };
+    assert_eq!(error.code, code.code());
+    assert_eq!(error.name, code.name());
+    assert!(!error.logs.is_empty());
+}
@@ -0,0 +1,214 @@
+use anchor_lang::Discriminator;
+use solana_sdk::pubkey::Pubkey;
+
+use staratlas_sage::state::{Fleet, FleetShips, Game, Sector, Starbase, StarbasePlayer};
+
+use staratlas_starbased_sdk::{
+    snapshot::{self, Snapshot, SnapshotFilter},
+    testing::{self, TestWorld, TEST_SHIP},
+    Faction,
+};
+
+#[test]
+fn snapshot_test() {
+    let mut test_world = TestWorld::new();
+
+    let mud_player = test_world.new_player(Faction::MUD);
+    let mud_fleet = test_world.new_fleet(&mud_player, &[(TEST_SHIP, 1)]);
+
+    let oni_player = test_world.new_player(Faction::ONI);
+    let oni_fleet = test_world.new_fleet(&oni_player, &[(TEST_SHIP, 1)]);
+
+    let take = |pubkeys: Vec<Pubkey>| {
+        pubkeys
+            .into_iter()
+            .map(|pubkey| (pubkey, test_world.svm.get_account(&pubkey).unwrap()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut snapshot = Snapshot::default();
+    snapshot.insert(Game::DISCRIMINATOR, take(vec![test_world.world.game]));
+    snapshot.insert(
+        Sector::DISCRIMINATOR,
+        take(test_world.world.sectors.values().copied().collect()),
+    );
+    snapshot.insert(
+        Starbase::DISCRIMINATOR,
+        take(test_world.world.starbases.values().copied().collect()),
+    );
+    snapshot.insert(
+        StarbasePlayer::DISCRIMINATOR,
+        take(vec![
+            mud_player.starbase_player.unwrap(),
+            oni_player.starbase_player.unwrap(),
+        ]),
+    );
+    snapshot.insert(
+        Fleet::DISCRIMINATOR,
+        take(vec![mud_fleet.fleet, oni_fleet.fleet]),
+    );
+    snapshot.insert(
+        FleetShips::DISCRIMINATOR,
+        take(vec![mud_fleet.fleet_ships, oni_fleet.fleet_ships]),
+    );
+
+    let snapshot = Snapshot::from_bytes(&snapshot.to_bytes().unwrap()).unwrap();
+    assert_eq!(snapshot.len(), 13);
+
+    let mut svm = testing::new_svm();
+    let filter = SnapshotFilter::default()
+        .set_game_id(test_world.world.game)
+        .set_owner_profile(mud_player.profile);
+    let count = snapshot::load_into_svm(&mut svm, &snapshot, &filter).unwrap();
+
+    // game, sectors and starbases plus the MUD player's starbase player, fleet and fleet ships
+    assert_eq!(count, 10);
+    assert!(svm.get_account(&mud_fleet.fleet).is_some());
+    assert!(svm.get_account(&mud_fleet.fleet_ships).is_some());
+    assert!(svm.get_account(&oni_fleet.fleet).is_none());
+    assert!(svm.get_account(&oni_fleet.fleet_ships).is_none());
+
+    let mut svm = testing::new_svm();
+    let mud_sector = test_world.world.sectors["MUD Sector"];
+    let filter = SnapshotFilter::default().set_sector([0, 10]);
+    snapshot::load_into_svm(&mut svm, &snapshot, &filter).unwrap();
+
+    assert!(svm.get_account(&mud_sector).is_some());
+    assert!(svm.get_account(&mud_fleet.fleet).is_some());
+    assert!(svm.get_account(&mud_fleet.fleet_ships).is_some());
+    // docked at the ONI starbase, outside of the sector
+    assert!(svm.get_account(&oni_fleet.fleet).is_none());
+    assert!(svm.get_account(&oni_fleet.fleet_ships).is_none());
+    assert!(svm
+        .get_account(&oni_player.starbase_player.unwrap())
+        .is_none());
+}
+
+#[test]
+fn snapshot_format_test() {
+    use flate2::{write::GzEncoder, Compression};
+    use solana_sdk::account::Account;
+    use staratlas_starbased_sdk::snapshot::{
+        sage_idl_hash, SerializableAccount, SerializablePubkey, SnapshotHeader, SnapshotReader,
+        SNAPSHOT_VERSION,
+    };
+    use std::{collections::HashMap, io::Write};
+
+    let account = Account {
+        lamports: 1_000,
+        data: Sector::DISCRIMINATOR.to_vec(),
+        owner: staratlas_sage::ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    let pubkey = Pubkey::new_unique();
+
+    let mut snapshot = Snapshot {
+        header: SnapshotHeader {
+            slot: 42,
+            timestamp: 1_700_000_000,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    snapshot.insert(Sector::DISCRIMINATOR, vec![(pubkey, account.clone())]);
+
+    let bytes = snapshot.to_bytes().unwrap();
+    let mut reader = SnapshotReader::new(&bytes[..]).unwrap();
+    assert_eq!(reader.header().version, SNAPSHOT_VERSION);
+    assert_eq!(reader.header().slot, 42);
+    assert_eq!(reader.header().program_ids, vec![staratlas_sage::ID]);
+    assert_eq!(reader.header().idl_hash, Some(sage_idl_hash()));
+
+    let entries = reader.by_ref().collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].pubkey, pubkey);
+    assert_eq!(entries[0].account, account);
+    assert_eq!(reader.header().count(Sector::DISCRIMINATOR), 1);
+
+    // version 0, a bare bincode map with base58 pubkeys
+    let mut legacy = HashMap::new();
+    legacy.insert(
+        Sector::DISCRIMINATOR.to_vec(),
+        vec![(
+            SerializablePubkey::from(pubkey),
+            SerializableAccount::from(account.clone()),
+        )],
+    );
+    let encoded = bincode::encode_to_vec(&legacy, bincode::config::standard()).unwrap();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&encoded).unwrap();
+    let legacy_bytes = encoder.finish().unwrap();
+
+    let legacy_snapshot = Snapshot::from_bytes(&legacy_bytes).unwrap();
+    assert_eq!(legacy_snapshot.header.version, 0);
+    assert_eq!(
+        legacy_snapshot.accounts[Sector::DISCRIMINATOR],
+        vec![(pubkey, account)]
+    );
+}
+
+#[test]
+fn snapshot_diff_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let starbase_player = player.starbase_player.unwrap();
+
+    let before = {
+        let mut snapshot = Snapshot::default();
+        let account = test_world.svm.get_account(&starbase_player).unwrap();
+        snapshot.insert(
+            StarbasePlayer::DISCRIMINATOR,
+            vec![(starbase_player, account)],
+        );
+        snapshot
+    };
+
+    let fleet = test_world.new_fleet(&player, &[(TEST_SHIP, 1)]);
+
+    let after = {
+        let mut snapshot = Snapshot::default();
+        for (discriminator, pubkey) in [
+            (StarbasePlayer::DISCRIMINATOR, starbase_player),
+            (Fleet::DISCRIMINATOR, fleet.fleet),
+        ] {
+            let account = test_world.svm.get_account(&pubkey).unwrap();
+            snapshot.insert(discriminator, vec![(pubkey, account)]);
+        }
+        snapshot
+    };
+
+    let snapshot_diff = snapshot::diff(&before, &after);
+
+    let fleet_diff = snapshot_diff
+        .types
+        .iter()
+        .find(|type_diff| type_diff.name == "Fleet")
+        .unwrap();
+    assert_eq!(fleet_diff.created, vec![fleet.fleet.to_string()]);
+
+    let starbase_player_diff = snapshot_diff
+        .types
+        .iter()
+        .find(|type_diff| type_diff.name == "StarbasePlayer")
+        .unwrap();
+    assert_eq!(starbase_player_diff.modified.len(), 1);
+    assert!(snapshot_diff.to_string().contains("Fleet: 1 created"));
+
+    // byte array names are one field
+    let mut renamed = after.clone();
+    let (_, fleet_account) = &mut renamed.accounts.get_mut(Fleet::DISCRIMINATOR).unwrap()[0];
+    let label = fleet_account
+        .data
+        .windows(12)
+        .position(|window| window == b"Test Fleet 1")
+        .unwrap();
+    fleet_account.data[label..label + 12].copy_from_slice(b"Fleet: {x}\0\0");
+
+    let snapshot_diff = snapshot::diff(&after, &renamed);
+    let fields = &snapshot_diff.types[0].modified[0].fields;
+    assert_eq!(fields.len(), 1);
+    assert_eq!(fields[0].field, "fleet_label");
+    assert_eq!(fields[0].before.as_deref(), Some("\"Test Fleet 1\""));
+    assert_eq!(fields[0].after.as_deref(), Some("\"Fleet: {x}\""));
+}
@@ -0,0 +1,36 @@
+use anchor_lang::AccountDeserialize;
+
+use staratlas_sage::{
+    state::Ship,
+    stats::{compare, DerivedStats, Metric},
+};
+use staratlas_starbased_sdk::testing::{TestWorld, TEST_SHIP};
+
+#[test]
+fn stats_test() {
+    let test_world = TestWorld::new();
+
+    let ship_acc = test_world
+        .svm
+        .get_account(&test_world.world.ships[TEST_SHIP])
+        .unwrap();
+    let ship = Ship::try_deserialize(&mut &ship_acc.data[..]).unwrap();
+
+    let stats = ship.derived_stats();
+    assert_eq!(stats.warp_range, 3.0);
+    assert_eq!(stats.warp_speed, 0.05);
+    assert_eq!(stats.warp_fuel_per_au, 1.0);
+    assert_eq!(stats.subwarp_fuel_per_au, 0.1);
+    assert_eq!(stats.fuel_capacity, 1000);
+    assert_eq!(stats.warp_time(3.0), Some(60.0));
+    assert_eq!(stats.warp_fuel(3.0), 3.0);
+
+    let hauler = DerivedStats {
+        cargo_capacity: 50_000,
+        warp_fuel_per_au: 4.0,
+        ..stats.clone()
+    };
+    let report = compare(vec![(TEST_SHIP.into(), stats), ("Hauler".into(), hauler)]);
+    assert_eq!(report.position("Hauler", Metric::CargoCapacity), Some(0));
+    assert_eq!(report.position(TEST_SHIP, Metric::WarpFuelPerAu), Some(0));
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::Pubkey;
+use std::collections::HashMap;
+
+use staratlas_sage::{
+    state::SurveyDataUnitTracker,
+    survey_heuristic::{self, SectorScan},
+};
+
+fn tracker() -> SurveyDataUnitTracker {
+    SurveyDataUnitTracker {
+        version: 0,
+        game_id: Pubkey::default(),
+        sdu_mint: Pubkey::default(),
+        resource_mint: Pubkey::default(),
+        signer: Pubkey::default(),
+        signer_bump: 0,
+        coordinates_range: [-5, 5],
+        css_coordinates: [[-5, 5], [5, 5], [0, -5]],
+        origin_coordinates: [0, 0],
+        css_max_distance: 5,
+        origin_max_distance: 8,
+        distance_weighting: 50,
+        t_max: 3600,
+        x_mul: 300,
+        y_mul: 300,
+        z_mul: 1000,
+        sdu_max_per_sector: 10,
+        scan_chance_regen_period: 120,
+    }
+}
+
+#[test]
+fn survey_heuristic_test() {
+    let tracker = tracker();
+    let now = 1_000_000;
+    let fresh = SectorScan::default();
+
+    let outside = survey_heuristic::score(&tracker, [6, 0], &fresh, now);
+    assert_eq!(outside.score, 0.0);
+
+    let inside = survey_heuristic::score(&tracker, [2, 1], &fresh, now);
+    assert!((0.0..=1.0).contains(&inside.score));
+    assert_eq!(inside.sector_chance, 1.0);
+    assert_eq!(
+        inside,
+        survey_heuristic::score(&tracker, [2, 1], &fresh, now)
+    );
+
+    let scanned = SectorScan {
+        last_scan_time: now,
+        last_scan_chance: 0,
+    };
+    assert_eq!(
+        survey_heuristic::sector_chance(&tracker, &scanned, now),
+        0.0
+    );
+    assert_eq!(
+        survey_heuristic::sector_chance(&tracker, &scanned, now + 60),
+        0.5
+    );
+    assert_eq!(
+        survey_heuristic::sector_chance(&tracker, &scanned, now + 600),
+        1.0
+    );
+
+    let scans = HashMap::from([([2, 1], scanned)]);
+    let heatmap = survey_heuristic::heatmap(&tracker, &scans, now);
+    assert_eq!(heatmap.rows.len(), 11);
+    assert!(heatmap.rows.iter().all(|row| row.len() == 11));
+    assert_eq!(heatmap.get([2, 1]).unwrap().score, 0.0);
+    assert_eq!(
+        heatmap.get([1, 2]).unwrap().base,
+        survey_heuristic::base_score(&tracker, [1, 2], now)
+    );
+    assert_eq!(heatmap.to_csv().lines().count(), 1 + 121);
+
+    let best = heatmap.best(3);
+    assert_eq!(best.len(), 3);
+    assert!(best[0].1.score >= best[2].1.score);
+}
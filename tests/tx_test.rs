@@ -0,0 +1,99 @@
+use solana_sdk::{
+    signature::Keypair,
+    signer::{Signer, SignerError},
+    system_instruction,
+    transaction::TransactionError,
+};
+
+use staratlas_starbased_sdk::{
+    admin::{find_sector_pda, find_starbase_pda, RegisterSector, RegisterStarbase},
+    player::{find_starbase_player_pda, RegisterStarbasePlayer},
+    profile::permissions::SAGE_MANAGER_KEY_INDEX,
+    testing::TestWorld,
+    tx::{TxBuilder, TxError},
+    Faction,
+};
+
+#[test]
+fn tx_builder_test() {
+    let mut test_world = TestWorld::new();
+    let player = test_world.new_player(Faction::MUD);
+    let world = &test_world.world;
+    let funder_kp = &test_world.funder;
+
+    let coordinates = [7, 7];
+    let (sector_pk, _) = find_sector_pda(&world.game, coordinates);
+    let (starbase_pk, _) = find_starbase_pda(&world.game, coordinates);
+    let (starbase_player_pk, _) =
+        find_starbase_player_pda(&starbase_pk, &player.sage_player_profile, 0);
+
+    let tx_builder = TxBuilder::new(funder_kp)
+        .add(
+            RegisterSector::new(
+                &world.authority,
+                &world.profile,
+                &world.profile,
+                &world.game,
+                funder_kp,
+            )
+            .set_coordinates(coordinates)
+            .set_name("New Sector".into())
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX),
+        )
+        .add(
+            RegisterStarbase::new(
+                &world.authority,
+                &world.profile,
+                &world.game,
+                &world.game_state,
+                &sector_pk,
+                funder_kp,
+            )
+            .set_coordinates(coordinates)
+            .set_name("New Starbase".into())
+            .set_starbase_level_index(1)
+            .set_faction(Faction::MUD)
+            .set_profile_key_index(SAGE_MANAGER_KEY_INDEX),
+        )
+        .add(RegisterStarbasePlayer::new(
+            &world.game,
+            &world.game_state,
+            &player.sage_player_profile,
+            &player.profile_faction,
+            &starbase_pk,
+            &player.keypair,
+        ));
+    assert_eq!(tx_builder.instructions().len(), 3);
+
+    // funder, authority and the player paying for its starbase player, each signing once
+    let tx = tx_builder
+        .transaction(test_world.svm.latest_blockhash())
+        .unwrap();
+    assert_eq!(tx.signatures.len(), 3);
+    assert_eq!(tx.message.account_keys[0], funder_kp.pubkey());
+
+    let pubkeys = tx_builder.send(&mut test_world.svm).unwrap();
+    assert_eq!(pubkeys, vec![sector_pk, starbase_pk, starbase_player_pk]);
+    for pubkey in pubkeys {
+        assert!(test_world.svm.get_account(&pubkey).is_some());
+    }
+}
+
+#[test]
+fn missing_signer_test() {
+    let mut test_world = TestWorld::new();
+    let from_kp = Keypair::new();
+    let transfer = system_instruction::transfer(&from_kp.pubkey(), &test_world.funder.pubkey(), 1);
+
+    // `from_kp` signs the transfer but was not added
+    let tx_builder =
+        TxBuilder::new(&test_world.funder).add_instructions(vec![transfer], vec![], vec![]);
+    let block_hash = test_world.svm.latest_blockhash();
+    assert_eq!(
+        tx_builder.transaction(block_hash).unwrap_err(),
+        TxError::Signer(SignerError::NotEnoughSigners)
+    );
+
+    let err = tx_builder.send(&mut test_world.svm).unwrap_err();
+    assert_eq!(err.err, TransactionError::SignatureFailure);
+}
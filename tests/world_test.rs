@@ -0,0 +1,70 @@
+use anchor_lang::AccountDeserialize;
+use solana_sdk::signature::{Keypair, Signer};
+
+use staratlas_sage::state::{Sector, Starbase};
+
+use staratlas_starbased_sdk as based_sdk;
+
+#[test]
+fn world_test() {
+    let mut svm = based_sdk::testing::new_svm();
+
+    let funder_kp = Keypair::new();
+    svm.airdrop(&funder_kp.pubkey(), 100_000_000_000).unwrap();
+
+    let world = based_sdk::world::load(&mut svm, "data/world.toml", &funder_kp).unwrap();
+
+    let sector_pk = world.sectors["Super Sector"];
+    let sector_acc = svm.get_account(&sector_pk).unwrap();
+    let sector_data = Sector::try_deserialize(&mut &sector_acc.data[..]).unwrap();
+    assert_eq!(sector_data.game_id.as_ref(), world.game.as_ref());
+
+    let starbase_pk = world.pubkey("Starbase Alpha").unwrap();
+    let starbase_acc = svm.get_account(&starbase_pk).unwrap();
+    let starbase_data = Starbase::try_deserialize(&mut &starbase_acc.data[..]).unwrap();
+    assert_eq!(starbase_data.level, 6);
+    assert!(matches!(
+        starbase_data.faction(),
+        Ok(based_sdk::Faction::Ustur)
+    ));
+
+    let alice = &world.players["alice"];
+    assert!(svm
+        .get_account(&alice.starbase_players["Starbase Alpha"])
+        .is_some());
+    assert!(world.resources.contains_key("Belt A Hydrogen"));
+    assert!(world.ships.contains_key("Pearce X4"));
+}
+
+#[test]
+fn world_config_test() {
+    let config = based_sdk::world::WorldConfig::from_toml_str(
+        r#"
+        [[sectors]]
+        name = "Sector"
+        coordinates = [0, 0]
+
+        [[sectors]]
+        name = "Sector"
+        coordinates = [1, 1]
+        "#,
+    )
+    .unwrap();
+    assert!(matches!(
+        config.validate(),
+        Err(based_sdk::world::WorldError::Invalid(_))
+    ));
+
+    let config = based_sdk::world::WorldConfig::from_toml_str(
+        r#"
+        [[starbases]]
+        name = "Starbase"
+        sector = "Sector"
+        faction = "Jorvik"
+        "#,
+    );
+    assert!(matches!(
+        config,
+        Err(based_sdk::world::WorldError::Parse(_))
+    ));
+}